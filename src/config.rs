@@ -1,10 +1,22 @@
-use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration, NaiveTime, Utc};
 use dirs::config_dir;
 use serde::{Deserialize, Serialize};
 use shellexpand;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::yaml_edit;
+
+/// Commented configuration used as the starting point for new config files
+const CONFIG_TEMPLATE: &str = include_str!("../config.example.yml");
+
+/// Directory for repositories without the layout topic ("topic:<name>" layout)
+const TOPIC_LAYOUT_FALLBACK_DIR: &str = "misc";
+
+/// Directory under the base directory holding gists (`github.include_gists`)
+const GISTS_DIR: &str = "gists";
+
 /// Main configuration structure for RepoSentry
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -19,6 +31,14 @@ pub struct Config {
     #[serde(default)]
     pub github: GitHubConfig,
 
+    /// Providers repositories are discovered from ("github", "gitlab")
+    #[serde(default = "default_providers")]
+    pub providers: Vec<String>,
+
+    /// GitLab discovery settings
+    #[serde(default)]
+    pub gitlab: GitLabConfig,
+
     /// Synchronization behavior settings
     #[serde(default)]
     pub sync: SyncConfig,
@@ -35,6 +55,18 @@ pub struct Config {
     #[serde(default)]
     pub logging: LoggingConfig,
 
+    /// Sync event history settings
+    #[serde(default)]
+    pub events: EventsConfig,
+
+    /// Desktop notification settings
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Terminal UI settings
+    #[serde(default)]
+    pub tui: TuiConfig,
+
     /// Directory structure organization
     #[serde(default)]
     pub organization: OrganizationConfig,
@@ -42,6 +74,23 @@ pub struct Config {
     /// Advanced settings
     #[serde(default)]
     pub advanced: AdvancedConfig,
+
+    /// Keys in the loaded file that don't match any setting (e.g. typos)
+    ///
+    /// Serde ignores them, so they are collected here for the caller to
+    /// report (see `find_unknown_keys`).
+    #[serde(skip)]
+    pub unknown_keys: Vec<String>,
+
+    /// Problems found while loading that were worked around, for the caller
+    /// to report once logging is set up
+    #[serde(skip)]
+    pub load_warnings: Vec<String>,
+
+    /// File the configuration was loaded from (re-read when the daemon
+    /// reloads its configuration)
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
 }
 
 /// Repository filtering configuration
@@ -54,20 +103,46 @@ pub struct FilterConfig {
     /// Size-based filtering
     #[serde(default)]
     pub size: SizeFilter,
+
+    /// GitHub topic that always includes a repository, bypassing the
+    /// fork, age and size filters
+    #[serde(default)]
+    pub keep_topic: Option<String>,
+
+    /// GitHub topic that always excludes a repository
+    #[serde(default)]
+    pub drop_topic: Option<String>,
+}
+
+impl FilterConfig {
+    /// Check if `topics` include `keep_topic`
+    pub fn is_kept(&self, topics: &[String]) -> bool {
+        has_topic(topics, self.keep_topic.as_deref())
+    }
+
+    /// Check if `topics` include `drop_topic`
+    pub fn is_dropped(&self, topics: &[String]) -> bool {
+        has_topic(topics, self.drop_topic.as_deref())
+    }
+}
+
+/// Whether `topics` contain `topic` (case-insensitively; false if unset)
+fn has_topic(topics: &[String], topic: Option<&str>) -> bool {
+    topic.is_some_and(|topic| topics.iter().any(|t| t.eq_ignore_ascii_case(topic)))
 }
 
 /// Age-based repository filtering
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct AgeFilter {
     /// Maximum age for repositories to be cloned
-    pub max_age: Option<String>, // "1month", "3month", "6month"
+    pub max_age: Option<String>, // "10d", "2week", "3month", "1y" or "never"
 }
 
 /// Size-based repository filtering
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct SizeFilter {
     /// Maximum size for repositories to be cloned
-    pub max_size: Option<String>, // "100MB", "1GB"
+    pub max_size: Option<String>, // "500MB", "2.5GB" or "unlimited"
 }
 
 /// GitHub configuration
@@ -75,7 +150,14 @@ pub struct SizeFilter {
 pub struct GitHubConfig {
     /// Authentication method
     #[serde(default = "default_auth_method")]
-    pub auth_method: String, // "auto", "gh_cli", "token"
+    pub auth_method: String, // "auto", "gh_cli", "token", "keyring"
+
+    /// File holding the GitHub token (e.g. a mounted Docker/Kubernetes secret)
+    ///
+    /// Takes precedence over GITHUB_TOKEN and the GitHub CLI for the "auto"
+    /// and "token" methods. `GITHUB_TOKEN_FILE` is used when unset.
+    #[serde(default)]
+    pub token_file: Option<String>,
 
     /// GitHub username (auto-detected if null)
     pub username: Option<String>,
@@ -91,6 +173,55 @@ pub struct GitHubConfig {
     /// Include forked repositories
     #[serde(default)]
     pub include_forks: bool,
+
+    /// Which remote forks are synced from
+    #[serde(default = "default_fork_tracking")]
+    pub fork_tracking: String, // "origin", "upstream"
+
+    /// Also clone each repository's wiki into a `<repo>.wiki` sibling directory
+    #[serde(default)]
+    pub include_wikis: bool,
+
+    /// Also clone the authenticated user's gists into `gists/<id>`
+    #[serde(default)]
+    pub include_gists: bool,
+
+    /// Repositories to never sync (owner/repo format, managed from the TUI)
+    #[serde(default)]
+    pub ignored_repos: Vec<String>,
+
+    /// Only discover repositories owned by these users or organizations
+    /// (empty means all; set from `--org` on the command line)
+    #[serde(default)]
+    pub only_owners: Vec<String>,
+}
+
+impl GitHubConfig {
+    /// Check if forks should be synced from their parent repository
+    pub fn tracks_upstream(&self) -> bool {
+        self.fork_tracking == "upstream"
+    }
+
+    /// Check if repositories owned by `owner` pass the `only_owners` filter
+    pub fn is_owner_included(&self, owner: &str) -> bool {
+        self.only_owners.is_empty()
+            || self
+                .only_owners
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(owner))
+    }
+}
+
+/// GitLab configuration (used when `providers` includes "gitlab")
+///
+/// The personal access token is read from `GITLAB_TOKEN`. Repository filters
+/// (`github.exclude_patterns`, `github.include_forks`, ...) apply to GitLab
+/// projects too.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GitLabConfig {
+    /// Instance URL (gitlab.com or a self-hosted instance)
+    #[serde(default = "default_gitlab_url")]
+    pub url: String,
 }
 
 /// Synchronization configuration
@@ -104,10 +235,35 @@ pub struct SyncConfig {
     #[serde(default = "default_max_parallel")]
     pub max_parallel: usize,
 
+    /// Maximum parallel repository analyses (TUI, dry runs, `--analyze-only`)
+    ///
+    /// Analysis only inspects checkouts, so it can run wider than syncing.
+    #[serde(default = "default_max_parallel_analyze")]
+    pub max_parallel_analyze: usize,
+
     /// Timeout for git operations in seconds
     #[serde(default = "default_timeout")]
     pub timeout: u64,
 
+    /// Per-repository timeouts in seconds, keyed by "owner/repo"
+    #[serde(default)]
+    pub timeout_overrides: BTreeMap<String, u64>,
+
+    /// Consecutive timeouts of a repository before a longer timeout is
+    /// suggested (0 to never suggest one)
+    #[serde(default = "default_timeout_strike_limit")]
+    pub timeout_strike_limit: u32,
+
+    /// Double a repository's timeout itself once it reaches the strike limit,
+    /// instead of only suggesting a `timeout_overrides` entry
+    #[serde(default)]
+    pub auto_extend_timeout: bool,
+
+    /// Retries of a fetch or pull that failed with a network error, with
+    /// exponential backoff (0 to never retry)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
     /// Auto-stash uncommitted changes
     #[serde(default)]
     pub auto_stash: bool,
@@ -115,6 +271,96 @@ pub struct SyncConfig {
     /// Fast-forward only pulls
     #[serde(default = "default_true")]
     pub fast_forward_only: bool,
+
+    /// Tag fetching behavior
+    /// - "all": Fetch all tags (--tags)
+    /// - "none": Fetch no tags (--no-tags)
+    /// - "follow": Fetch tags pointing at fetched commits (git's default)
+    #[serde(default = "default_fetch_tags")]
+    pub fetch_tags: String,
+
+    /// Prune deleted remote branches on fetch, and delete local branches
+    /// whose upstream is gone (only those without unpushed commits)
+    #[serde(default)]
+    pub prune: bool,
+
+    /// Behavior for existing checkouts RepoSentry has never synced before
+    /// - "sync": Sync normally
+    /// - "fetch-only": Only fetch on first encounter and record what would be done
+    #[serde(default = "default_first_run_mode")]
+    pub first_run_mode: String,
+
+    /// History depth for clones (None for full history)
+    ///
+    /// Existing shallow checkouts are converted to full history when unset.
+    #[serde(default)]
+    pub clone_depth: Option<u32>,
+
+    /// Bandwidth limit (e.g., "5MB/s")
+    ///
+    /// Git can't throttle transfers, so when set, repositories are synced one
+    /// at a time instead of in parallel.
+    #[serde(default)]
+    pub rate_limit: Option<String>,
+
+    /// Interleave repositories across owners so one large organization
+    /// doesn't occupy every parallel slot
+    #[serde(default)]
+    pub fair_scheduling: bool,
+
+    /// Adjust the number of parallel operations during a sync from observed
+    /// completion times (back off when the network is saturated)
+    #[serde(default)]
+    pub dynamic_concurrency: bool,
+
+    /// Keep syncing when the first repositories all fail to authenticate,
+    /// instead of stopping the run (usually an expired token)
+    #[serde(default)]
+    pub continue_on_auth_error: bool,
+}
+
+impl SyncConfig {
+    /// Git flag for the configured tag fetching behavior, if any
+    pub fn fetch_tags_arg(&self) -> Option<&'static str> {
+        match self.fetch_tags.as_str() {
+            "all" => Some("--tags"),
+            "none" => Some("--no-tags"),
+            _ => None,
+        }
+    }
+
+    /// Git flag pruning deleted remote branches on fetch (`prune`), if set
+    pub fn prune_arg(&self) -> Option<&'static str> {
+        self.prune.then_some("--prune")
+    }
+
+    /// Configured timeout for a repository in seconds (its override, if any)
+    pub fn timeout_for(&self, full_name: &str) -> u64 {
+        self.timeout_overrides
+            .get(full_name)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+
+    /// Check if never-synced repositories should only be fetched on first encounter
+    pub fn is_first_run_fetch_only(&self) -> bool {
+        self.first_run_mode == "fetch-only"
+    }
+
+    /// Convert the rate limit string to bytes per second
+    ///
+    /// Accepts a size (see `parse_size`) with an optional "/s" suffix.
+    /// Returns None if unset or unparseable.
+    pub fn rate_limit_bytes(&self) -> Option<u64> {
+        let limit = self.rate_limit.as_deref()?.trim();
+        parse_size(limit.strip_suffix("/s").unwrap_or(limit)).ok()
+    }
+
+    /// The configured rate limit, if it is valid and in effect
+    pub fn effective_rate_limit(&self) -> Option<&str> {
+        self.rate_limit_bytes()?;
+        self.rate_limit.as_deref()
+    }
 }
 
 /// Branch tracking configuration
@@ -130,6 +376,12 @@ pub struct BranchConfig {
     /// (e.g., dependabot/*, renovate/*, wip/*)
     #[serde(default = "default_branch_exclude_patterns")]
     pub exclude_patterns: Vec<String>,
+
+    /// Which branches to fetch
+    /// - "all": Fetch every branch
+    /// - "default-only": Fetch only the default branch (disables "most-recent")
+    #[serde(default = "default_branch_fetch_mode")]
+    pub fetch_mode: String,
 }
 
 impl BranchConfig {
@@ -138,6 +390,11 @@ impl BranchConfig {
         self.strategy == "most-recent"
     }
 
+    /// Check if only the default branch should be fetched
+    pub fn is_default_only_fetch(&self) -> bool {
+        self.fetch_mode == "default-only"
+    }
+
     /// Check if a branch name matches any of the exclude patterns
     pub fn is_branch_excluded(&self, branch_name: &str) -> bool {
         for pattern in &self.exclude_patterns {
@@ -181,6 +438,97 @@ pub struct DaemonConfig {
     /// Empty string means log to stdout/stderr
     #[serde(default = "default_log_filename")]
     pub log_file: String,
+
+    /// Per-cycle log line style
+    /// - "verbose": Multi-field sentence per sync cycle
+    /// - "compact": One terse, grep-friendly line per sync cycle
+    #[serde(default = "default_log_style")]
+    pub log_style: String,
+
+    /// Skip scheduled syncs while running on battery power
+    #[serde(default)]
+    pub skip_on_battery: bool,
+
+    /// Skip scheduled syncs while on a metered (e.g. tethered) connection
+    #[serde(default)]
+    pub skip_on_metered: bool,
+
+    /// Prune acknowledged events older than this many days once a day
+    /// (None to keep them until `events cleanup` is run)
+    #[serde(default)]
+    pub auto_cleanup_days: Option<u32>,
+
+    /// Local time-of-day window for scheduled syncs, e.g. "22:00-06:00"
+    /// (None to sync at any time)
+    #[serde(default)]
+    pub active_hours: Option<String>,
+}
+
+impl DaemonConfig {
+    /// Check if compact one-line cycle logging is enabled
+    pub fn is_compact_log_style(&self) -> bool {
+        self.log_style == "compact"
+    }
+
+    /// Parse `active_hours`, if set
+    pub fn active_window(&self) -> Result<Option<ActiveHours>> {
+        self.active_hours
+            .as_deref()
+            .map(ActiveHours::parse)
+            .transpose()
+            .context("Invalid daemon.active_hours")
+    }
+}
+
+/// Time-of-day window ("HH:MM-HH:MM") during which the daemon syncs
+///
+/// A window whose end is before its start crosses midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ActiveHours {
+    /// Parse a window like "09:00-17:30" or "22:00-06:00"
+    pub fn parse(value: &str) -> Result<Self> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| anyhow!("expected HH:MM-HH:MM, got '{}'", value))?;
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .with_context(|| format!("Invalid time '{}': expected HH:MM", time.trim()))
+        };
+
+        let window = Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        };
+        if window.start == window.end {
+            return Err(anyhow!("start and end of '{}' are the same", value));
+        }
+        Ok(window)
+    }
+
+    /// Whether `time` falls inside the window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl std::fmt::Display for ActiveHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
 }
 
 /// Logging configuration
@@ -199,6 +547,67 @@ pub struct LoggingConfig {
     pub color: bool,
 }
 
+/// Sync event history configuration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EventsConfig {
+    /// Record info-level events (clones, pulls) as already acknowledged
+    #[serde(default)]
+    pub auto_ack_info: bool,
+
+    /// Consecutive failed syncs before a repository is reported as
+    /// chronically failing (0 disables the alert)
+    #[serde(default = "default_chronic_threshold")]
+    pub chronic_threshold: u32,
+}
+
+/// Desktop notification configuration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotificationsConfig {
+    /// Show desktop notifications from the daemon for chronic failures and
+    /// failing sync cycles (at most one per repository per day)
+    #[serde(default)]
+    pub desktop: bool,
+
+    /// Failed repositories in one sync cycle that trigger a notification
+    /// (0 disables it)
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: usize,
+}
+
+/// Terminal UI configuration
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TuiConfig {
+    /// Re-analyze local repository states on this interval (e.g., "30s", "5m")
+    ///
+    /// Unset, the TUI only refreshes when asked to.
+    #[serde(default)]
+    pub auto_refresh: Option<String>,
+
+    /// Also re-discover the repository list on each auto-refresh
+    #[serde(default)]
+    pub auto_refresh_discovery: bool,
+}
+
+impl TuiConfig {
+    /// Interval between auto-refreshes (None when unset or zero)
+    pub fn auto_refresh_interval(&self) -> Option<std::time::Duration> {
+        let interval = self.auto_refresh.as_deref()?;
+        match parse_duration_secs(interval) {
+            Ok(0) | Err(_) => None,
+            Ok(secs) => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Check that `auto_refresh` is a valid duration
+    pub fn validate_auto_refresh(&self) -> Result<()> {
+        if let Some(interval) = &self.auto_refresh {
+            parse_duration_secs(interval)
+                .with_context(|| format!("Invalid tui.auto_refresh '{}'", interval))?;
+        }
+        Ok(())
+    }
+}
+
 /// Organization directory configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct OrganizationConfig {
@@ -209,6 +618,51 @@ pub struct OrganizationConfig {
     /// Handle repository name conflicts
     #[serde(default = "default_conflict_resolution")]
     pub conflict_resolution: String, // "prefix-org"
+
+    /// Directory layout: "org", "topic:<name>" or "flat"
+    ///
+    /// Overrides `separate_org_dirs` when set. With "topic:<name>", repos
+    /// tagged with that GitHub topic go under `<base>/<name>/` and the rest
+    /// under `<base>/misc/`.
+    #[serde(default)]
+    pub layout: Option<String>,
+
+    /// Replace characters outside `A-Z a-z 0-9 . - _` in owner and
+    /// repository directory names (see `sanitize_dir_name`)
+    #[serde(default = "default_true")]
+    pub sanitize: bool,
+}
+
+impl OrganizationConfig {
+    /// The topic repositories are grouped by ("topic:<name>" layout)
+    pub fn layout_topic(&self) -> Option<&str> {
+        self.layout
+            .as_deref()?
+            .strip_prefix("topic:")
+            .map(str::trim)
+            .filter(|topic| !topic.is_empty())
+    }
+
+    /// Check if repositories go in per-owner directories ("org" layout)
+    pub fn uses_org_dirs(&self) -> bool {
+        match self.layout.as_deref() {
+            Some("org") => true,
+            Some(_) => false,
+            None => self.separate_org_dirs,
+        }
+    }
+
+    /// Directory grouping a repository with `topics` under the topic layout
+    ///
+    /// Returns None unless the "topic:<name>" layout is configured.
+    pub fn topic_dir<'a>(&'a self, topics: &[String]) -> Option<&'a str> {
+        let topic = self.layout_topic()?;
+        if topics.iter().any(|t| t.eq_ignore_ascii_case(topic)) {
+            Some(topic)
+        } else {
+            Some(TOPIC_LAYOUT_FALLBACK_DIR)
+        }
+    }
 }
 
 /// Advanced configuration options
@@ -226,9 +680,301 @@ pub struct AdvancedConfig {
     #[serde(default = "default_true")]
     pub cleanup_on_error: bool,
 
+    /// Check that a remote can be reached (`git ls-remote`) before cloning
+    /// it, skipping inaccessible repositories instead of failing the clone
+    #[serde(default)]
+    pub precheck_remote: bool,
+
     /// Repository metadata cache duration
     #[serde(default = "default_cache_duration")]
     pub cache_duration: String, // "1h"
+
+    /// What to do when a checkout's origin doesn't match the expected clone URL
+    #[serde(default = "default_on_remote_mismatch")]
+    pub on_remote_mismatch: String, // "skip", "update-remote", "reclone"
+
+    /// Git settings passed as `-c key=value` to every git invocation
+    #[serde(default)]
+    pub git_config: BTreeMap<String, String>,
+
+    /// How many directory levels below base_directory to look for checkouts
+    #[serde(default = "default_max_scan_depth")]
+    pub max_scan_depth: usize,
+
+    /// Keep bare mirror clones (`<repo>.git`, every ref) instead of working trees
+    #[serde(default)]
+    pub mirror_mode: bool,
+
+    /// Seconds to wait for the GitHub authentication check in `doctor` and
+    /// the TUI preflight
+    #[serde(default = "default_auth_check_timeout")]
+    pub auth_check_timeout: u64,
+
+    /// Move the checkout of a repository transferred to another owner to its
+    /// new location (instead of only warning about the orphaned copy)
+    #[serde(default)]
+    pub follow_org_moves: bool,
+
+    /// Pass the GitHub token to git for HTTPS remotes, so each clone or fetch
+    /// doesn't go through a credential helper (opt-in)
+    #[serde(default)]
+    pub git_token_auth: bool,
+
+    /// Git LFS handling: "on" (download objects as usual), "skip-smudge"
+    /// (check out pointer files only) or "off" (bypass the LFS filter)
+    #[serde(default = "default_lfs")]
+    pub lfs: String,
+
+    /// Oldest git version `doctor` accepts without a warning
+    #[serde(default = "default_min_git_version")]
+    pub min_git_version: String,
+}
+
+impl AdvancedConfig {
+    /// Check if origin should be repointed when it doesn't match
+    pub fn is_update_remote_on_mismatch(&self) -> bool {
+        self.on_remote_mismatch == "update-remote"
+    }
+
+    /// Check if mismatched checkouts should be moved aside and re-cloned
+    pub fn is_reclone_on_mismatch(&self) -> bool {
+        self.on_remote_mismatch == "reclone"
+    }
+
+    /// Check if LFS objects should be left as pointer files on checkout
+    pub fn is_lfs_skip_smudge(&self) -> bool {
+        self.lfs == "skip-smudge"
+    }
+
+    /// Check if the LFS filter should be bypassed entirely
+    pub fn is_lfs_off(&self) -> bool {
+        self.lfs == "off"
+    }
+
+    /// How long discovered repository metadata stays fresh (`cache_duration`)
+    ///
+    /// None disables caching: the duration is zero or can't be parsed.
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        match parse_duration_secs(&self.cache_duration) {
+            Ok(0) | Err(_) => None,
+            Ok(secs) => Some(Duration::seconds(secs as i64)),
+        }
+    }
+
+    /// Check that every `git_config` key looks like `section.key`
+    pub fn validate_git_config(&self) -> Result<()> {
+        for key in self.git_config.keys() {
+            if !is_git_config_key(key) {
+                return Err(anyhow!(
+                    "Invalid advanced.git_config key '{}': expected section.key (e.g. protocol.version)",
+                    key
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `lfs` is one of the supported modes
+    pub fn validate_lfs(&self) -> Result<()> {
+        match self.lfs.as_str() {
+            "on" | "off" | "skip-smudge" => Ok(()),
+            other => Err(anyhow!(
+                "Invalid advanced.lfs '{}': expected on, off or skip-smudge",
+                other
+            )),
+        }
+    }
+}
+
+/// Make an owner or repository name safe to use as a directory name
+///
+/// Names made only of ASCII letters, digits, `.`, `-` and `_` are kept as
+/// they are. Anything else becomes `-`, and a hash of the original name is
+/// appended so different names never end up in the same directory.
+pub fn sanitize_dir_name(name: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_');
+    if name.chars().all(is_safe) && !matches!(name, "" | "." | "..") {
+        return name.to_string();
+    }
+
+    let cleaned: String = name
+        .chars()
+        .map(|c| if is_safe(c) { c } else { '-' })
+        .collect();
+    // FNV-1a: stable across Rust versions, unlike `DefaultHasher`
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    match cleaned.trim_matches(|c| c == '-' || c == '.') {
+        "" => format!("{:08x}", hash),
+        cleaned => format!("{}-{:08x}", cleaned, hash),
+    }
+}
+
+/// Parse a duration string like "30m", "1h" or "2d" (bare numbers are seconds) into seconds
+pub fn parse_duration_secs(duration_str: &str) -> Result<u64> {
+    let duration_str = duration_str.trim().to_lowercase();
+
+    if let Some(value) = duration_str.strip_suffix('s') {
+        value.parse::<u64>().context("Invalid seconds value")
+    } else if let Some(value) = duration_str.strip_suffix('m') {
+        value
+            .parse::<u64>()
+            .map(|v| v * 60)
+            .context("Invalid minutes value")
+    } else if let Some(value) = duration_str.strip_suffix('h') {
+        value
+            .parse::<u64>()
+            .map(|v| v * 3600)
+            .context("Invalid hours value")
+    } else if let Some(value) = duration_str.strip_suffix('d') {
+        value
+            .parse::<u64>()
+            .map(|v| v * 86400)
+            .context("Invalid days value")
+    } else {
+        // Try to parse as raw seconds
+        duration_str
+            .parse::<u64>()
+            .context("Invalid duration format. Use format like '30m', '1h', '2d'")
+    }
+}
+
+/// Parse an age like "10d", "2week", "3month" or "1y" (`filters.age.max_age`)
+///
+/// Units are days (`d`, `day`), weeks (`w`, `week`), months of 30 days
+/// (`month`) and years of 365 days (`y`, `year`), optionally pluralized.
+pub fn parse_age(age: &str) -> Result<Duration> {
+    let age = age.trim().to_lowercase();
+    let split = age.find(|c: char| !c.is_ascii_digit()).unwrap_or(age.len());
+    let (number, unit) = age.split_at(split);
+
+    let invalid = || {
+        anyhow!(
+            "Invalid age '{}': use a number and a unit, like '10d', '2week', '3month' or '1y'",
+            age
+        )
+    };
+    let number: i64 = number.parse().map_err(|_| invalid())?;
+    let days_per_unit = match unit.trim().trim_end_matches('s') {
+        "d" | "day" => 1,
+        "w" | "week" => 7,
+        "month" => 30,
+        "y" | "year" => 365,
+        _ => return Err(invalid()),
+    };
+
+    number
+        .checked_mul(days_per_unit)
+        .and_then(Duration::try_days)
+        .ok_or_else(invalid)
+}
+
+/// Parse a size like "500MB" or "2.5GB" into bytes
+///
+/// Accepts a positive integer or decimal with a B, KB, MB, GB or TB unit
+/// (case-insensitive, binary multiples).
+pub fn parse_size(size: &str) -> Result<u64> {
+    let upper = size.trim().to_uppercase();
+    let invalid = || {
+        anyhow!(
+            "Invalid size '{}': use a number and a unit, like '500MB' or '2.5GB'",
+            size.trim()
+        )
+    };
+
+    let (number, multiplier) = [
+        ("TB", 1u64 << 40),
+        ("GB", 1 << 30),
+        ("MB", 1 << 20),
+        ("KB", 1 << 10),
+        ("B", 1),
+    ]
+    .iter()
+    .find_map(|(unit, multiplier)| {
+        upper
+            .strip_suffix(unit)
+            .map(|number| (number.trim(), *multiplier))
+    })
+    .ok_or_else(invalid)?;
+
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    if !number.is_finite() || number <= 0.0 {
+        return Err(invalid());
+    }
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Whether `key` is a git config key: `section[.subsection].name`
+///
+/// The section and name are alphanumeric (plus `-`); the subsection may be
+/// anything without a newline, as in `url.<base>.insteadOf`.
+fn is_git_config_key(key: &str) -> bool {
+    let valid_part = |part: &str| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    match (key.split_once('.'), key.rsplit_once('.')) {
+        (Some((section, _)), Some((_, name))) => {
+            valid_part(section)
+                && valid_part(name)
+                && name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && !key.contains(['\n', '='])
+        }
+        _ => false,
+    }
+}
+
+/// Describe the keys of `content` that `config` (its deserialized form) ignored
+///
+/// The file is compared with the serialized configuration: every setting is
+/// written out, so a key missing there was not recognized. Returns messages
+/// such as "unknown config key 'sync.max_paralel' at line 12".
+fn find_unknown_keys(content: &str, config: &Config) -> Vec<String> {
+    let (Ok(raw), Ok(known)) = (
+        serde_yaml::from_str::<serde_yaml::Value>(content),
+        serde_yaml::to_value(config),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut unknown = Vec::new();
+    collect_unknown_keys(&mut Vec::new(), &raw, &known, &mut unknown);
+
+    unknown
+        .into_iter()
+        .map(|path| {
+            let key = path.join(".");
+            match yaml_edit::key_line(content, &path) {
+                Some(line) => format!("unknown config key '{}' at line {}", key, line + 1),
+                None => format!("unknown config key '{}'", key),
+            }
+        })
+        .collect()
+}
+
+/// Collect the paths of mapping keys in `raw` that are absent from `known`
+fn collect_unknown_keys(
+    path: &mut Vec<String>,
+    raw: &serde_yaml::Value,
+    known: &serde_yaml::Value,
+    unknown: &mut Vec<Vec<String>>,
+) {
+    let (Some(raw), Some(known)) = (raw.as_mapping(), known.as_mapping()) else {
+        return;
+    };
+
+    for (key, value) in raw {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        path.push(key.to_string());
+        match known.get(key) {
+            Some(known_value) => collect_unknown_keys(path, value, known_value, unknown),
+            None => unknown.push(path.clone()),
+        }
+        path.pop();
+    }
 }
 
 // Default value functions
@@ -238,15 +984,36 @@ fn default_auth_method() -> String {
 fn default_true() -> bool {
     true
 }
+fn default_providers() -> Vec<String> {
+    vec!["github".to_string()]
+}
+fn default_gitlab_url() -> String {
+    "https://gitlab.com".to_string()
+}
 fn default_sync_strategy() -> String {
     "safe-pull".to_string()
 }
 fn default_max_parallel() -> usize {
     4
 }
+fn default_max_parallel_analyze() -> usize {
+    16
+}
 fn default_timeout() -> u64 {
     300
 }
+fn default_timeout_strike_limit() -> u32 {
+    3
+}
+fn default_max_retries() -> u32 {
+    2
+}
+fn default_fetch_tags() -> String {
+    "follow".to_string()
+}
+fn default_first_run_mode() -> String {
+    "sync".to_string()
+}
 fn default_interval() -> String {
     "30m".to_string()
 }
@@ -262,6 +1029,9 @@ fn default_branch_exclude_patterns() -> Vec<String> {
         "experiment/*".to_string(),
     ]
 }
+fn default_branch_fetch_mode() -> String {
+    "all".to_string()
+}
 fn default_pid_filename() -> String {
     "reposentry.pid".to_string()
 }
@@ -270,6 +1040,10 @@ fn default_log_filename() -> String {
     "daemon.log".to_string()
 }
 
+fn default_log_style() -> String {
+    "verbose".to_string()
+}
+
 /// Get the full PID file path, resolving filename to XDG-compliant location
 pub fn get_pid_file_path(filename: &str) -> String {
     if filename.is_empty() {
@@ -305,18 +1079,60 @@ pub fn get_log_file_path(filename: &str) -> String {
         format!("/tmp/{}", filename)
     }
 }
+/// Resolve an (expanded) base directory to an absolute path
+///
+/// Relative paths are taken relative to the home directory rather than the
+/// current working directory.
+fn resolve_base_directory(base_directory: &str) -> PathBuf {
+    let path = PathBuf::from(base_directory);
+    if path.is_absolute() {
+        return path;
+    }
+
+    match dirs::home_dir() {
+        Some(home) => home.join(path),
+        None => path,
+    }
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
 fn default_log_format() -> String {
     "compact".to_string()
 }
+fn default_auth_check_timeout() -> u64 {
+    10
+}
+
+fn default_failure_threshold() -> usize {
+    5
+}
+fn default_chronic_threshold() -> u32 {
+    3
+}
+
 fn default_conflict_resolution() -> String {
     "prefix-org".to_string()
 }
 fn default_cache_duration() -> String {
     "1h".to_string()
 }
+fn default_fork_tracking() -> String {
+    "origin".to_string()
+}
+fn default_on_remote_mismatch() -> String {
+    "skip".to_string()
+}
+fn default_min_git_version() -> String {
+    "2.22".to_string()
+}
+fn default_lfs() -> String {
+    "on".to_string()
+}
+fn default_max_scan_depth() -> usize {
+    2 // owner/repo
+}
 
 // Default implementations
 impl Default for FilterConfig {
@@ -328,6 +1144,8 @@ impl Default for FilterConfig {
             size: SizeFilter {
                 max_size: Some("1GB".to_string()),
             },
+            keep_topic: None,
+            drop_topic: None,
         }
     }
 }
@@ -336,6 +1154,7 @@ impl Default for GitHubConfig {
     fn default() -> Self {
         Self {
             auth_method: default_auth_method(),
+            token_file: None,
             username: None,
             include_organizations: default_true(),
             exclude_patterns: vec![
@@ -345,6 +1164,19 @@ impl Default for GitHubConfig {
                 "fork-*".to_string(),
             ],
             include_forks: false,
+            fork_tracking: default_fork_tracking(),
+            include_wikis: false,
+            include_gists: false,
+            ignored_repos: Vec::new(),
+            only_owners: Vec::new(),
+        }
+    }
+}
+
+impl Default for GitLabConfig {
+    fn default() -> Self {
+        Self {
+            url: default_gitlab_url(),
         }
     }
 }
@@ -354,9 +1186,22 @@ impl Default for SyncConfig {
         Self {
             strategy: default_sync_strategy(),
             max_parallel: default_max_parallel(),
+            max_parallel_analyze: default_max_parallel_analyze(),
             timeout: default_timeout(),
+            timeout_overrides: BTreeMap::new(),
+            timeout_strike_limit: default_timeout_strike_limit(),
+            auto_extend_timeout: false,
+            max_retries: default_max_retries(),
             auto_stash: false,
             fast_forward_only: default_true(),
+            fetch_tags: default_fetch_tags(),
+            prune: false,
+            first_run_mode: default_first_run_mode(),
+            clone_depth: None,
+            rate_limit: None,
+            fair_scheduling: false,
+            dynamic_concurrency: false,
+            continue_on_auth_error: false,
         }
     }
 }
@@ -366,6 +1211,7 @@ impl Default for BranchConfig {
         Self {
             strategy: default_branch_strategy(),
             exclude_patterns: default_branch_exclude_patterns(),
+            fetch_mode: default_branch_fetch_mode(),
         }
     }
 }
@@ -377,6 +1223,11 @@ impl Default for DaemonConfig {
             interval: default_interval(),
             pid_file: default_pid_filename(),
             log_file: default_log_filename(),
+            log_style: default_log_style(),
+            skip_on_battery: false,
+            skip_on_metered: false,
+            auto_cleanup_days: None,
+            active_hours: None,
         }
     }
 }
@@ -391,11 +1242,31 @@ impl Default for LoggingConfig {
     }
 }
 
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            auto_ack_info: false,
+            chronic_threshold: default_chronic_threshold(),
+        }
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            failure_threshold: default_failure_threshold(),
+        }
+    }
+}
+
 impl Default for OrganizationConfig {
     fn default() -> Self {
         Self {
             separate_org_dirs: default_true(),
             conflict_resolution: default_conflict_resolution(),
+            layout: None,
+            sanitize: default_true(),
         }
     }
 }
@@ -406,7 +1277,17 @@ impl Default for AdvancedConfig {
             preserve_timestamps: default_true(),
             verify_clone: default_true(),
             cleanup_on_error: default_true(),
+            precheck_remote: false,
             cache_duration: default_cache_duration(),
+            on_remote_mismatch: default_on_remote_mismatch(),
+            git_config: BTreeMap::new(),
+            max_scan_depth: default_max_scan_depth(),
+            mirror_mode: false,
+            auth_check_timeout: default_auth_check_timeout(),
+            follow_org_moves: false,
+            git_token_auth: false,
+            lfs: default_lfs(),
+            min_git_version: default_min_git_version(),
         }
     }
 }
@@ -443,16 +1324,41 @@ impl Config {
 
         let mut config: Config = serde_yaml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+        config.unknown_keys = find_unknown_keys(&content, &config);
+        config.source_path = Some(path.to_path_buf());
 
         // Expand environment variables in paths
         config.expand_paths()?;
 
+        config.validate_providers()?;
+        config.advanced.validate_git_config()?;
+        config.advanced.validate_lfs()?;
+        config.tui.validate_auto_refresh()?;
+        config.age_filter_duration()?;
+        config.size_filter_bytes()?;
+        config.load_warnings.extend(config.clone_depth_warning());
+
         Ok(config)
     }
 
     /// Save configuration to a file
+    ///
+    /// Comments and layout are preserved: only values that changed are
+    /// rewritten in the existing file. New files start from the commented
+    /// example configuration. Falls back to a plain serialization if the
+    /// existing file can't be edited in place.
     pub fn save(&self, path: &Path) -> Result<()> {
-        let content = serde_yaml::to_string(self).context("Failed to serialize configuration")?;
+        let existing = if path.exists() {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {:?}", path))?
+        } else {
+            CONFIG_TEMPLATE.to_string()
+        };
+
+        let content = match self.update_preserving_format(&existing) {
+            Some(content) => content,
+            None => serde_yaml::to_string(self).context("Failed to serialize configuration")?,
+        };
 
         std::fs::write(path, content)
             .with_context(|| format!("Failed to write config file: {:?}", path))?;
@@ -460,6 +1366,54 @@ impl Config {
         Ok(())
     }
 
+    /// Apply this configuration's values to `content`, keeping its comments
+    fn update_preserving_format(&self, content: &str) -> Option<String> {
+        // Compare against the file as it would be loaded (with paths expanded)
+        let mut current: Config = serde_yaml::from_str(content).ok()?;
+        current.expand_paths().ok()?;
+
+        let old = serde_yaml::to_value(&current).ok()?;
+        let new = serde_yaml::to_value(self).ok()?;
+        let updated = yaml_edit::update_document(content, &old, &new)?;
+
+        // Only use the edited text if it round-trips to this configuration
+        let mut saved: Config = serde_yaml::from_str(&updated).ok()?;
+        saved.expand_paths().ok()?;
+        let mut expected = self.clone();
+        expected.expand_paths().ok()?;
+        (serde_yaml::to_value(&saved).ok()? == serde_yaml::to_value(&expected).ok()?)
+            .then_some(updated)
+    }
+
+    /// Add a repository to `github.ignored_repos` in the config file at `path`
+    ///
+    /// The file is updated in place (see `save`). If it doesn't exist yet,
+    /// it is created from this configuration. Returns false if the
+    /// repository was already ignored.
+    pub fn add_ignored_repo(&self, path: &Path, full_name: &str) -> Result<bool> {
+        let mut config = if path.exists() {
+            Self::load(path)?
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+            }
+            self.clone()
+        };
+
+        if config
+            .github
+            .ignored_repos
+            .iter()
+            .any(|repo| repo == full_name)
+        {
+            return Ok(false);
+        }
+        config.github.ignored_repos.push(full_name.to_string());
+        config.save(path)?;
+
+        Ok(true)
+    }
+
     /// Get the default configuration file path (XDG compliant)
     pub fn default_config_path() -> Result<PathBuf> {
         let config_dir = config_dir().context("Failed to get user config directory")?;
@@ -468,10 +1422,23 @@ impl Config {
     }
 
     /// Expand environment variables in configuration paths
+    ///
+    /// A relative `base_directory` is resolved against the home directory, so
+    /// the CLI and the daemon (whose working directory is unpredictable)
+    /// always agree on where repositories live.
     pub fn expand_paths(&mut self) -> Result<()> {
-        self.base_directory = shellexpand::full(&self.base_directory)
+        let base_directory = shellexpand::full(&self.base_directory)
             .context("Failed to expand base_directory path")?
             .into_owned();
+        let resolved = resolve_base_directory(&base_directory);
+        if resolved.as_os_str() != base_directory.as_str() {
+            self.load_warnings.push(format!(
+                "base_directory \"{}\" is relative; using {} (set an absolute path to silence this)",
+                base_directory,
+                resolved.display()
+            ));
+        }
+        self.base_directory = resolved.to_string_lossy().into_owned();
 
         self.daemon.pid_file = shellexpand::full(&self.daemon.pid_file)
             .context("Failed to expand pid_file path")?
@@ -484,36 +1451,131 @@ impl Config {
         Ok(())
     }
 
+    /// Warning for shallow clones under the most-recent branch strategy
+    ///
+    /// The branches' tip commits are still fetched (with `--depth`), so the
+    /// most recent branch is found, but only within the truncated history.
+    pub fn clone_depth_warning(&self) -> Option<String> {
+        let depth = self.clone_depth()?;
+        if !self.branches.is_most_recent_strategy() {
+            return None;
+        }
+        Some(format!(
+            "sync.clone_depth {} with branches.strategy most-recent: branches are fetched with --depth {}, so history older than that is not available locally",
+            depth, depth
+        ))
+    }
+
+    /// History depth for clones and fetches: `sync.clone_depth`, except that
+    /// mirrors (`advanced.mirror_mode`) always keep full history
+    pub fn clone_depth(&self) -> Option<u32> {
+        self.sync.clone_depth.filter(|_| !self.advanced.mirror_mode)
+    }
+
+    /// Check whether repositories are discovered from `provider` (see `providers`)
+    pub fn uses_provider(&self, provider: &str) -> bool {
+        self.providers.iter().any(|p| p == provider)
+    }
+
+    /// Check that `providers` only names supported providers
+    pub fn validate_providers(&self) -> Result<()> {
+        if self.providers.is_empty() {
+            return Err(anyhow!("providers is empty: list github and/or gitlab"));
+        }
+        match self
+            .providers
+            .iter()
+            .find(|p| !matches!(p.as_str(), "github" | "gitlab"))
+        {
+            Some(other) => Err(anyhow!(
+                "Invalid provider '{}': expected github or gitlab",
+                other
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Compute the local checkout path for a repository (honors the organization layout)
+    ///
+    /// Owners with nested groups (GitLab's `group/subgroup`) get one
+    /// directory per level.
+    pub fn repo_local_path(&self, owner: &str, name: &str, topics: &[String]) -> PathBuf {
+        let base_dir = self.expanded_base_directory();
+        let name = self.checkout_dir_name(&self.dir_name(name));
+
+        if let Some(topic_dir) = self.organization.topic_dir(topics) {
+            base_dir.join(topic_dir).join(name)
+        } else if self.organization.uses_org_dirs() {
+            let owner_dir: PathBuf = owner.split('/').map(|part| self.dir_name(part)).collect();
+            base_dir.join(owner_dir).join(name)
+        } else {
+            base_dir.join(name)
+        }
+    }
+
+    /// Compute the local checkout path for a gist (`gists/<id>` under the base directory)
+    pub fn gist_local_path(&self, id: &str) -> PathBuf {
+        self.expanded_base_directory()
+            .join(GISTS_DIR)
+            .join(self.checkout_dir_name(id))
+    }
+
+    /// Base directory with variables expanded, resolved against home if relative
+    pub fn expanded_base_directory(&self) -> PathBuf {
+        let base_dir = shellexpand::full(&self.base_directory)
+            .unwrap_or_else(|_| std::borrow::Cow::Borrowed(&self.base_directory));
+        resolve_base_directory(&base_dir)
+    }
+
+    /// Directory name for an owner or repository name (`organization.sanitize`)
+    pub fn dir_name(&self, name: &str) -> String {
+        if self.organization.sanitize {
+            sanitize_dir_name(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Directory name of a checkout (bare mirrors follow the `<repo>.git` convention)
+    fn checkout_dir_name(&self, name: &str) -> String {
+        if self.advanced.mirror_mode {
+            format!("{}.git", name)
+        } else {
+            name.to_string()
+        }
+    }
+
     /// Convert age filter string to chrono Duration for comparison
-    pub fn age_filter_duration(&self) -> Option<Duration> {
-        self.filters
-            .age
-            .max_age
-            .as_ref()
-            .and_then(|age_str| match age_str.as_str() {
-                "1month" => Some(Duration::days(30)),
-                "3month" => Some(Duration::days(90)),
-                "6month" => Some(Duration::days(180)),
-                _ => None,
-            })
+    ///
+    /// None when unset or "never"; an error when malformed (see `parse_age`).
+    pub fn age_filter_duration(&self) -> Result<Option<Duration>> {
+        match self.filters.age.max_age.as_deref() {
+            None => Ok(None),
+            Some(age) if age.trim().eq_ignore_ascii_case("never") => Ok(None),
+            Some(age) => parse_age(age)
+                .map(Some)
+                .context("Invalid filters.age.max_age"),
+        }
     }
 
     /// Convert size filter string to bytes for comparison
-    pub fn size_filter_bytes(&self) -> Option<u64> {
-        self.filters
-            .size
-            .max_size
-            .as_ref()
-            .and_then(|size_str| match size_str.as_str() {
-                "100MB" => Some(100 * 1024 * 1024),
-                "1GB" => Some(1024 * 1024 * 1024),
-                _ => None,
-            })
+    ///
+    /// None when unset or "unlimited"; an error when malformed (see `parse_size`).
+    pub fn size_filter_bytes(&self) -> Result<Option<u64>> {
+        match self.filters.size.max_size.as_deref() {
+            None => Ok(None),
+            Some(size) if size.trim().eq_ignore_ascii_case("unlimited") => Ok(None),
+            Some(size) => parse_size(size)
+                .map(Some)
+                .context("Invalid filters.size.max_size"),
+        }
     }
 
     /// Check if a repository should be filtered based on age
+    ///
+    /// A malformed `max_age` is rejected when the configuration is loaded.
     pub fn should_filter_by_age(&self, last_activity: &chrono::DateTime<Utc>) -> bool {
-        if let Some(max_age) = self.age_filter_duration() {
+        if let Ok(Some(max_age)) = self.age_filter_duration() {
             let cutoff_date = Utc::now() - max_age;
             last_activity < &cutoff_date
         } else {
@@ -522,8 +1584,10 @@ impl Config {
     }
 
     /// Check if a repository should be filtered based on size
+    ///
+    /// A malformed `max_size` is rejected when the configuration is loaded.
     pub fn should_filter_by_size(&self, size_bytes: u64) -> bool {
-        if let Some(max_size) = self.size_filter_bytes() {
+        if let Ok(Some(max_size)) = self.size_filter_bytes() {
             size_bytes > max_size
         } else {
             false
@@ -537,12 +1601,20 @@ impl Default for Config {
             base_directory: "${HOME}/dev".to_string(),
             filters: FilterConfig::default(),
             github: GitHubConfig::default(),
+            providers: default_providers(),
+            gitlab: GitLabConfig::default(),
             sync: SyncConfig::default(),
             branches: BranchConfig::default(),
             daemon: DaemonConfig::default(),
             logging: LoggingConfig::default(),
+            events: EventsConfig::default(),
+            notifications: NotificationsConfig::default(),
+            tui: TuiConfig::default(),
             organization: OrganizationConfig::default(),
             advanced: AdvancedConfig::default(),
+            unknown_keys: Vec::new(),
+            load_warnings: Vec::new(),
+            source_path: None,
         }
     }
 }
@@ -568,8 +1640,13 @@ mod tests {
         assert_eq!(config.base_directory, "${HOME}/dev");
         assert!(config.github.include_organizations);
         assert!(!config.github.include_forks);
+        assert!(!config.github.include_wikis);
         assert_eq!(config.sync.max_parallel, 4);
+        assert_eq!(config.sync.max_parallel_analyze, 16);
         assert_eq!(config.sync.timeout, 300);
+        assert_eq!(config.sync.timeout_strike_limit, 3);
+        assert!(!config.sync.auto_extend_timeout);
+        assert_eq!(config.sync.max_retries, 2);
         assert!(!config.sync.auto_stash);
         assert!(config.sync.fast_forward_only);
         assert!(!config.daemon.enabled);
@@ -581,46 +1658,96 @@ mod tests {
     fn test_age_filter_duration() {
         let mut config = Config::default();
 
+        let days = |config: &Config| config.age_filter_duration().unwrap().unwrap().num_days();
+
         // Test 1 month
         config.filters.age.max_age = Some("1month".to_string());
-        assert_eq!(config.age_filter_duration().unwrap().num_days(), 30);
+        assert_eq!(days(&config), 30);
 
         // Test 3 months
         config.filters.age.max_age = Some("3month".to_string());
-        assert_eq!(config.age_filter_duration().unwrap().num_days(), 90);
+        assert_eq!(days(&config), 90);
 
         // Test 6 months
         config.filters.age.max_age = Some("6month".to_string());
-        assert_eq!(config.age_filter_duration().unwrap().num_days(), 180);
+        assert_eq!(days(&config), 180);
+
+        // Test never
+        config.filters.age.max_age = Some("never".to_string());
+        assert!(config.age_filter_duration().unwrap().is_none());
 
         // Test invalid
-        config.filters.age.max_age = Some("invalid".to_string());
-        assert!(config.age_filter_duration().is_none());
+        config.filters.age.max_age = Some("banana".to_string());
+        assert!(config.age_filter_duration().is_err());
 
         // Test none
         config.filters.age.max_age = None;
-        assert!(config.age_filter_duration().is_none());
+        assert!(config.age_filter_duration().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("10d").unwrap().num_days(), 10);
+        assert_eq!(parse_age("45day").unwrap().num_days(), 45);
+        assert_eq!(parse_age("2week").unwrap().num_days(), 14);
+        assert_eq!(parse_age("3w").unwrap().num_days(), 21);
+        assert_eq!(parse_age("2months").unwrap().num_days(), 60);
+        assert_eq!(parse_age("1y").unwrap().num_days(), 365);
+        assert_eq!(parse_age(" 2 Years ").unwrap().num_days(), 730);
+
+        assert!(parse_age("banana").is_err());
+        assert!(parse_age("10").is_err());
+        assert!(parse_age("week").is_err());
+        assert!(parse_age("-1d").is_err());
+        assert!(parse_age("10fortnight").is_err());
+        assert!(parse_age("99999999999999y").is_err());
     }
 
     #[test]
     fn test_size_filter_bytes() {
         let mut config = Config::default();
 
+        let bytes = |config: &Config| config.size_filter_bytes().unwrap().unwrap();
+
         // Test 100MB
         config.filters.size.max_size = Some("100MB".to_string());
-        assert_eq!(config.size_filter_bytes().unwrap(), 100 * 1024 * 1024);
+        assert_eq!(bytes(&config), 100 * 1024 * 1024);
 
         // Test 1GB
         config.filters.size.max_size = Some("1GB".to_string());
-        assert_eq!(config.size_filter_bytes().unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(bytes(&config), 1024 * 1024 * 1024);
+
+        // Test 500MB
+        config.filters.size.max_size = Some("500MB".to_string());
+        assert_eq!(bytes(&config), 500 * 1024 * 1024);
+
+        // Test 2.5GB
+        config.filters.size.max_size = Some("2.5gb".to_string());
+        assert_eq!(bytes(&config), 2560 * 1024 * 1024);
+
+        // Test unlimited
+        config.filters.size.max_size = Some("unlimited".to_string());
+        assert!(config.size_filter_bytes().unwrap().is_none());
 
         // Test invalid
         config.filters.size.max_size = Some("invalid".to_string());
-        assert!(config.size_filter_bytes().is_none());
+        assert!(config.size_filter_bytes().is_err());
 
         // Test none
         config.filters.size.max_size = None;
-        assert!(config.size_filter_bytes().is_none());
+        assert!(config.size_filter_bytes().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2TB").unwrap(), 2 << 40);
+        assert_eq!(parse_size(" 64 kb ").unwrap(), 64 * 1024);
+        assert_eq!(parse_size("10B").unwrap(), 10);
+
+        assert!(parse_size("big").is_err());
+        assert!(parse_size("500").is_err());
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("-1GB").is_err());
     }
 
     #[test]
@@ -639,6 +1766,28 @@ mod tests {
         env::remove_var("TEST_REPOSENTRY_HOME");
     }
 
+    #[test]
+    fn test_relative_base_directory() {
+        let home = dirs::home_dir().unwrap();
+        let mut config = Config {
+            base_directory: "dev".to_string(),
+            ..Config::default()
+        };
+
+        // Resolved the same way before and after expansion, whatever the cwd
+        let before = config.repo_local_path("owner", "repo", &[]);
+        config.expand_paths().unwrap();
+        assert_eq!(PathBuf::from(&config.base_directory), home.join("dev"));
+        assert_eq!(config.repo_local_path("owner", "repo", &[]), before);
+        assert_eq!(before, home.join("dev").join("owner").join("repo"));
+        assert_eq!(config.load_warnings.len(), 1);
+        assert!(config.load_warnings[0].contains("relative"));
+
+        // Already absolute: nothing to report
+        config.expand_paths().unwrap();
+        assert_eq!(config.load_warnings.len(), 1);
+    }
+
     #[test]
     fn test_config_load_nonexistent_file() {
         let nonexistent_path = Path::new("/nonexistent/path/config.yml");
@@ -688,10 +1837,16 @@ mod tests {
         config.filters.size.max_size = Some("1GB".to_string());
 
         // Test age filtering duration
-        assert_eq!(config.age_filter_duration().unwrap().num_days(), 90);
+        assert_eq!(
+            config.age_filter_duration().unwrap().unwrap().num_days(),
+            90
+        );
 
         // Test size filtering bytes
-        assert_eq!(config.size_filter_bytes().unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(
+            config.size_filter_bytes().unwrap(),
+            Some(1024 * 1024 * 1024)
+        );
 
         // Test should_filter_by_age
         let old_timestamp = Utc::now() - Duration::days(100);
@@ -725,6 +1880,8 @@ github:
 sync:
   max_parallel: 8
   timeout: 600
+  timeout_overrides:
+    acme/monorepo: 1800
   auto_stash: true
   fast_forward_only: false
 daemon:
@@ -750,6 +1907,8 @@ advanced:
         assert!(config.github.include_forks);
         assert_eq!(config.sync.max_parallel, 8);
         assert_eq!(config.sync.timeout, 600);
+        assert_eq!(config.sync.timeout_for("acme/monorepo"), 1800);
+        assert_eq!(config.sync.timeout_for("acme/api"), 600);
         assert!(config.sync.auto_stash);
         assert!(!config.sync.fast_forward_only);
         assert!(config.daemon.enabled);
@@ -759,6 +1918,362 @@ advanced:
         assert!(!config.advanced.preserve_timestamps);
     }
 
+    #[test]
+    fn test_repo_local_path() {
+        let mut config = Config {
+            base_directory: "/base".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &[]),
+            PathBuf::from("/base/owner/repo")
+        );
+
+        // GitLab subgroups nest
+        assert_eq!(
+            config.repo_local_path("acme/backend", "api", &[]),
+            PathBuf::from("/base/acme/backend/api")
+        );
+
+        config.organization.separate_org_dirs = false;
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &[]),
+            PathBuf::from("/base/repo")
+        );
+
+        assert_eq!(
+            config.gist_local_path("aa5a315d"),
+            PathBuf::from("/base/gists/aa5a315d")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_dir_name() {
+        // Valid GitHub names are left alone
+        for name in ["repo", "my.repo", ".github", "repo_name-2"] {
+            assert_eq!(sanitize_dir_name(name), name);
+        }
+
+        let spaced = sanitize_dir_name("my repo");
+        assert!(spaced.starts_with("my-repo-"));
+        assert_eq!(spaced, sanitize_dir_name("my repo"));
+        let unicode = sanitize_dir_name("café");
+        assert!(unicode.starts_with("caf-"));
+        assert!(unicode.is_ascii());
+        assert_eq!(sanitize_dir_name("..").len(), 8);
+
+        // No collisions between names that clean up the same way
+        let names = ["a b", "a-b", "a/b", "a\tb", "a  b"];
+        let dirs: std::collections::HashSet<String> =
+            names.iter().map(|name| sanitize_dir_name(name)).collect();
+        assert_eq!(dirs.len(), names.len());
+
+        let mut config = Config {
+            base_directory: "/base".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.repo_local_path("My Org", "my.repo", &[]),
+            PathBuf::from("/base")
+                .join(sanitize_dir_name("My Org"))
+                .join("my.repo")
+        );
+        config.organization.sanitize = false;
+        assert_eq!(
+            config.repo_local_path("My Org", "my.repo", &[]),
+            PathBuf::from("/base/My Org/my.repo")
+        );
+    }
+
+    #[test]
+    fn test_repo_local_path_layout() {
+        let mut config = Config {
+            base_directory: "/base".to_string(),
+            ..Default::default()
+        };
+        let topics = vec!["infra".to_string(), "rust".to_string()];
+
+        config.organization.separate_org_dirs = false;
+        config.organization.layout = Some("org".to_string());
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &topics),
+            PathBuf::from("/base/owner/repo")
+        );
+
+        config.organization.layout = Some("flat".to_string());
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &topics),
+            PathBuf::from("/base/repo")
+        );
+
+        config.organization.layout = Some("topic:infra".to_string());
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &topics),
+            PathBuf::from("/base/infra/repo")
+        );
+        assert_eq!(
+            config.repo_local_path("owner", "repo", &[]),
+            PathBuf::from("/base/misc/repo")
+        );
+    }
+
+    #[test]
+    fn test_config_save_preserves_comments() {
+        let (_temp_dir, config_dir) = setup_test_config_dir();
+        let config_path = config_dir.join("config.yml");
+        std::fs::write(
+            &config_path,
+            "# My config\nbase_directory: \"${HOME}/dev\"  # repos here\n\nsync:\n  # Be careful\n  max_parallel: 4\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load(&config_path).unwrap();
+        config.sync.max_parallel = 8;
+        config.save(&config_path).unwrap();
+
+        let content = std::fs::read_to_string(&config_path).unwrap();
+        assert_eq!(
+            content,
+            "# My config\nbase_directory: \"${HOME}/dev\"  # repos here\n\nsync:\n  # Be careful\n  max_parallel: 8\n"
+        );
+    }
+
+    #[test]
+    fn test_config_save_new_file_uses_template() {
+        let (_temp_dir, config_dir) = setup_test_config_dir();
+        let config_path = config_dir.join("config.yml");
+
+        let config = Config::default();
+        config.save(&config_path).unwrap();
+
+        let content = std::fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("# RepoSentry Configuration File"));
+
+        let loaded = Config::load(&config_path).unwrap();
+        assert_eq!(loaded.filters.age.max_age, config.filters.age.max_age);
+        assert_eq!(loaded.filters.size.max_size, config.filters.size.max_size);
+        assert_eq!(loaded.sync.max_parallel, config.sync.max_parallel);
+    }
+
+    #[test]
+    fn test_add_ignored_repo() {
+        let (_temp_dir, config_dir) = setup_test_config_dir();
+        let config_path = config_dir.join("config.yml");
+        std::fs::write(
+            &config_path,
+            "base_directory: \"${HOME}/dev\"\ngithub:\n  include_forks: true\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&config_path).unwrap();
+        assert!(config.add_ignored_repo(&config_path, "owner/repo").unwrap());
+        assert!(!config.add_ignored_repo(&config_path, "owner/repo").unwrap());
+
+        let content = std::fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains("${HOME}/dev"));
+
+        let reloaded = Config::load(&config_path).unwrap();
+        assert_eq!(
+            reloaded.github.ignored_repos,
+            vec!["owner/repo".to_string()]
+        );
+        assert!(reloaded.github.include_forks);
+    }
+
+    #[test]
+    fn test_add_ignored_repo_creates_config() {
+        let (_temp_dir, config_dir) = setup_test_config_dir();
+        let config_path = config_dir.join("config.yml");
+
+        let config = Config::default();
+        assert!(config.add_ignored_repo(&config_path, "owner/repo").unwrap());
+
+        let reloaded = Config::load(&config_path).unwrap();
+        assert_eq!(
+            reloaded.github.ignored_repos,
+            vec!["owner/repo".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_owner_included() {
+        let mut config = GitHubConfig::default();
+        assert!(config.is_owner_included("anyone"));
+
+        config.only_owners = vec!["acme".to_string(), "Widgets".to_string()];
+        assert!(config.is_owner_included("acme"));
+        assert!(config.is_owner_included("widgets"));
+        assert!(!config.is_owner_included("other"));
+    }
+
+    #[test]
+    fn test_clone_depth_warning() {
+        let mut config = Config::default();
+        config.branches.strategy = "most-recent".to_string();
+        assert!(config.clone_depth_warning().is_none());
+
+        config.sync.clone_depth = Some(1);
+        assert!(config.clone_depth_warning().unwrap().contains("--depth 1"));
+
+        config.branches.strategy = "current".to_string();
+        assert!(config.clone_depth_warning().is_none());
+    }
+
+    #[test]
+    fn test_validate_providers() {
+        let mut config = Config::default();
+        assert!(config.validate_providers().is_ok());
+        assert!(config.uses_provider("github"));
+        assert!(!config.uses_provider("gitlab"));
+
+        config.providers = vec!["gitlab".to_string()];
+        assert!(config.validate_providers().is_ok());
+
+        config.providers = vec!["github".to_string(), "bitbucket".to_string()];
+        assert!(config.validate_providers().is_err());
+
+        config.providers.clear();
+        assert!(config.validate_providers().is_err());
+    }
+
+    #[test]
+    fn test_is_git_config_key() {
+        assert!(is_git_config_key("protocol.version"));
+        assert!(is_git_config_key("http.postBuffer"));
+        assert!(is_git_config_key(
+            "url.https://mirror.example.com/.insteadOf"
+        ));
+        assert!(!is_git_config_key("version"));
+        assert!(!is_git_config_key("protocol."));
+        assert!(!is_git_config_key(".version"));
+        assert!(!is_git_config_key("core.2bad"));
+        assert!(!is_git_config_key("core.name=value"));
+
+        let mut advanced = AdvancedConfig::default();
+        advanced
+            .git_config
+            .insert("protocol.version".to_string(), "2".to_string());
+        assert!(advanced.validate_git_config().is_ok());
+        advanced
+            .git_config
+            .insert("longpaths".to_string(), "true".to_string());
+        assert!(advanced.validate_git_config().is_err());
+    }
+
+    #[test]
+    fn test_validate_lfs() {
+        let mut advanced = AdvancedConfig::default();
+        assert!(advanced.validate_lfs().is_ok());
+        advanced.lfs = "skip-smudge".to_string();
+        assert!(advanced.validate_lfs().is_ok());
+        assert!(advanced.is_lfs_skip_smudge());
+        advanced.lfs = "partial".to_string();
+        assert!(advanced.validate_lfs().is_err());
+    }
+
+    #[test]
+    fn test_active_hours() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        let day = ActiveHours::parse("09:00-17:30").unwrap();
+        assert!(day.contains(time(9, 0)));
+        assert!(day.contains(time(17, 29)));
+        assert!(!day.contains(time(17, 30)));
+        assert!(!day.contains(time(3, 0)));
+
+        let night = ActiveHours::parse("22:00 - 06:00").unwrap();
+        assert_eq!(night.to_string(), "22:00-06:00");
+        assert!(night.contains(time(23, 15)));
+        assert!(night.contains(time(0, 0)));
+        assert!(night.contains(time(5, 59)));
+        assert!(!night.contains(time(6, 0)));
+        assert!(!night.contains(time(12, 0)));
+
+        assert!(ActiveHours::parse("22:00").is_err());
+        assert!(ActiveHours::parse("25:00-06:00").is_err());
+        assert!(ActiveHours::parse("06:00-06:00").is_err());
+
+        let mut daemon = DaemonConfig::default();
+        assert_eq!(daemon.active_window().unwrap(), None);
+        daemon.active_hours = Some("22:00-06:00".to_string());
+        assert_eq!(daemon.active_window().unwrap(), Some(night));
+    }
+
+    #[test]
+    fn test_first_run_mode() {
+        let mut config = SyncConfig::default();
+        assert_eq!(config.first_run_mode, "sync");
+        assert!(!config.is_first_run_fetch_only());
+
+        config.first_run_mode = "fetch-only".to_string();
+        assert!(config.is_first_run_fetch_only());
+    }
+
+    #[test]
+    fn test_fetch_tags_arg() {
+        let mut config = SyncConfig::default();
+        assert_eq!(config.fetch_tags, "follow");
+        assert_eq!(config.fetch_tags_arg(), None);
+
+        config.fetch_tags = "all".to_string();
+        assert_eq!(config.fetch_tags_arg(), Some("--tags"));
+
+        config.fetch_tags = "none".to_string();
+        assert_eq!(config.fetch_tags_arg(), Some("--no-tags"));
+    }
+
+    #[test]
+    fn test_rate_limit_bytes() {
+        let mut config = SyncConfig::default();
+        assert_eq!(config.rate_limit_bytes(), None);
+        assert_eq!(config.effective_rate_limit(), None);
+
+        config.rate_limit = Some("5MB/s".to_string());
+        assert_eq!(config.rate_limit_bytes(), Some(5 * 1024 * 1024));
+        assert_eq!(config.effective_rate_limit(), Some("5MB/s"));
+
+        config.rate_limit = Some("512kb".to_string());
+        assert_eq!(config.rate_limit_bytes(), Some(512 * 1024));
+
+        config.rate_limit = Some("1.5 GB/s".to_string());
+        assert_eq!(config.rate_limit_bytes(), Some(1536 * 1024 * 1024));
+
+        config.rate_limit = Some("fast".to_string());
+        assert_eq!(config.rate_limit_bytes(), None);
+        assert_eq!(config.effective_rate_limit(), None);
+
+        config.rate_limit = Some("0MB/s".to_string());
+        assert_eq!(config.rate_limit_bytes(), None);
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let content = "\
+base_directory: \"~/dev\"
+filterz:
+  exclude: []
+sync:
+  strategy: \"safe-pull\"
+  max_paralel: 8
+advanced:
+  git_config:
+    core.autocrlf: \"input\"
+";
+        let config: Config = serde_yaml::from_str(content).unwrap();
+        assert_eq!(
+            find_unknown_keys(content, &config),
+            vec![
+                "unknown config key 'filterz' at line 2",
+                "unknown config key 'sync.max_paralel' at line 6",
+            ]
+        );
+
+        // The shipped example only uses known settings
+        let example: Config = serde_yaml::from_str(CONFIG_TEMPLATE).unwrap();
+        assert!(find_unknown_keys(CONFIG_TEMPLATE, &example).is_empty());
+    }
+
     #[test]
     fn test_branch_config_defaults() {
         let config = BranchConfig::default();
@@ -774,6 +2289,16 @@ advanced:
         assert!(config.is_most_recent_strategy());
     }
 
+    #[test]
+    fn test_branch_config_fetch_mode() {
+        let mut config = BranchConfig::default();
+        assert_eq!(config.fetch_mode, "all");
+        assert!(!config.is_default_only_fetch());
+
+        config.fetch_mode = "default-only".to_string();
+        assert!(config.is_default_only_fetch());
+    }
+
     #[test]
     fn test_branch_exclude_patterns() {
         let config = BranchConfig::default();
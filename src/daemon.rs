@@ -3,57 +3,105 @@
 //! This module provides the daemon/service infrastructure for running RepoSentry
 //! in the background with configurable sync intervals, PID file management,
 //! and graceful shutdown handling.
-
-use crate::config::{get_log_file_path, get_pid_file_path};
-use crate::discovery::{Discovery, GitHubDiscovery};
+//!
+//! SIGINT and SIGTERM stop the daemon after the current sync; SIGHUP reloads
+//! the configuration file, so `ExecReload=/bin/kill -HUP $MAINPID` works
+//! under systemd.
+
+use crate::config::{get_log_file_path, get_pid_file_path, parse_duration_secs};
+use crate::discovery::{configured_sources, discover_from, Discovery};
+use crate::git::{SyncResult, DRY_RUN_ENV};
+use crate::health::{check_connectivity, on_battery, on_metered_connection, provider_hosts};
+use crate::state::{EventType, StateDb};
 use crate::sync::{SyncEngine, SyncSummary};
 use crate::Config;
 use anyhow::{Context, Result};
-// Helper function to parse duration strings like "30m", "1h", etc.
-fn parse_daemon_duration(duration_str: &str) -> Result<u64> {
-    let duration_str = duration_str.trim().to_lowercase();
-
-    if let Some(value) = duration_str.strip_suffix('s') {
-        value.parse::<u64>().context("Invalid seconds value")
-    } else if let Some(value) = duration_str.strip_suffix('m') {
-        value
-            .parse::<u64>()
-            .map(|v| v * 60)
-            .context("Invalid minutes value")
-    } else if let Some(value) = duration_str.strip_suffix('h') {
-        value
-            .parse::<u64>()
-            .map(|v| v * 3600)
-            .context("Invalid hours value")
-    } else if let Some(value) = duration_str.strip_suffix('d') {
-        value
-            .parse::<u64>()
-            .map(|v| v * 86400)
-            .context("Invalid days value")
-    } else {
-        // Try to parse as raw seconds
-        duration_str
-            .parse::<u64>()
-            .context("Invalid duration format. Use format like '30m', '1h', '2d'")
-    }
-}
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
-use tokio::time::interval;
+use tokio::time::{interval, interval_at};
 use tracing::{debug, error, info, warn};
 
+/// How often `daemon.auto_cleanup_days` pruning runs
+const AUTO_CLEANUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// Daemon state and control
 pub struct Daemon {
     config: Arc<Config>,
-    discovery: GitHubDiscovery,
+    discovery: Vec<Box<dyn Discovery>>,
     sync_engine: SyncEngine,
     shutdown_sender: broadcast::Sender<()>,
     is_running: Arc<AtomicBool>,
     pid_file_path: Option<PathBuf>,
+    total_syncs: AtomicU64,
+    successful_syncs: AtomicU64,
+    failed_syncs: AtomicU64,
+    last_sync: Mutex<Option<Instant>>,
+}
+
+/// A signal the daemon listens for (never arrives when not listening)
+#[derive(Default)]
+struct SignalListener {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl SignalListener {
+    /// Start listening for a Unix signal
+    #[cfg(unix)]
+    fn listen(kind: tokio::signal::unix::SignalKind) -> Result<Self> {
+        let signal =
+            tokio::signal::unix::signal(kind).context("Failed to install daemon signal handler")?;
+        Ok(Self {
+            signal: Some(signal),
+        })
+    }
+
+    /// Wait for the next delivery of the signal
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+            // The signal driver is gone, so nothing will arrive any more
+            self.signal = None;
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+/// Signals that stop the daemon (SIGINT, SIGTERM) or reload its configuration (SIGHUP)
+#[derive(Default)]
+struct DaemonSignals {
+    interrupt: SignalListener,
+    terminate: SignalListener,
+    hangup: SignalListener,
+}
+
+impl DaemonSignals {
+    /// Start listening for SIGINT, SIGTERM and SIGHUP
+    #[cfg(unix)]
+    fn install() -> Result<Self> {
+        use tokio::signal::unix::SignalKind;
+
+        Ok(Self {
+            interrupt: SignalListener::listen(SignalKind::interrupt())?,
+            terminate: SignalListener::listen(SignalKind::terminate())?,
+            hangup: SignalListener::listen(SignalKind::hangup())?,
+        })
+    }
+
+    /// Signals aren't handled on this platform; Ctrl+C ends the process
+    #[cfg(not(unix))]
+    fn install() -> Result<Self> {
+        Ok(Self::default())
+    }
 }
 
 /// Daemon statistics and status
@@ -68,20 +116,41 @@ pub struct DaemonStatus {
     pub next_sync_in: Option<Duration>,
 }
 
+/// Sync statistics of a running daemon, persisted next to its PID file so
+/// `daemon status` (a separate process) can report them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DaemonStats {
+    pub total_syncs: u64,
+    pub successful_syncs: u64,
+    pub failed_syncs: u64,
+    pub last_sync_at: Option<DateTime<Utc>>,
+}
+
 impl Daemon {
     /// Create a new daemon instance
     pub async fn new(config: Config) -> Result<Self> {
         let config = Arc::new(config);
 
-        // Create discovery provider
-        let discovery = GitHubDiscovery::new(config.as_ref().clone())
+        // Create discovery providers
+        let discovery = configured_sources(&config)
             .await
-            .context("Failed to create GitHub discovery for daemon")?;
+            .context("Failed to create repository discovery for daemon")?;
 
         // Create sync engine with state database for event tracking
         let sync_engine = SyncEngine::with_state_db(config.as_ref().clone())
-            .context("Failed to create sync engine with state database")?;
+            .context("Failed to create sync engine with state database")?
+            .with_token_auth()
+            .await;
+
+        Ok(Self::from_parts(config, discovery, sync_engine))
+    }
 
+    /// Assemble a daemon from its discovery sources and sync engine
+    fn from_parts(
+        config: Arc<Config>,
+        discovery: Vec<Box<dyn Discovery>>,
+        sync_engine: SyncEngine,
+    ) -> Self {
         let (shutdown_sender, _) = broadcast::channel(1);
         let is_running = Arc::new(AtomicBool::new(false));
 
@@ -95,41 +164,43 @@ impl Daemon {
             }
         };
 
-        Ok(Self {
+        Self {
             config,
             discovery,
             sync_engine,
             shutdown_sender,
             is_running,
             pid_file_path,
-        })
+            total_syncs: AtomicU64::new(0),
+            successful_syncs: AtomicU64::new(0),
+            failed_syncs: AtomicU64::new(0),
+            last_sync: Mutex::new(None),
+        }
     }
 
     /// Start the daemon in the foreground
     pub async fn run(&mut self) -> Result<()> {
         info!("Starting RepoSentry daemon");
 
+        if self.sync_engine.git_client().is_dry_run() {
+            warn!(
+                "{} is set: scheduled syncs will not modify any repository",
+                DRY_RUN_ENV
+            );
+        }
+
         // Write PID file if configured
         self.write_pid_file().context("Failed to write PID file")?;
 
         // Set running state
         self.is_running.store(true, Ordering::SeqCst);
 
-        // Setup graceful shutdown handling
+        // Setup graceful shutdown and reload handling
         let shutdown_receiver = self.shutdown_sender.subscribe();
-        let is_running = self.is_running.clone();
-
-        // Spawn shutdown signal handler
-        let shutdown_sender = self.shutdown_sender.clone();
-        tokio::spawn(async move {
-            Self::wait_for_shutdown_signal().await;
-            info!("Shutdown signal received, stopping daemon...");
-            is_running.store(false, Ordering::SeqCst);
-            let _ = shutdown_sender.send(());
-        });
+        let signals = DaemonSignals::install()?;
 
         // Run the main daemon loop
-        let result = self.daemon_loop(shutdown_receiver).await;
+        let result = self.daemon_loop(shutdown_receiver, signals).await;
 
         // Cleanup on exit
         self.cleanup().context("Failed to cleanup daemon")?;
@@ -215,15 +286,14 @@ impl Daemon {
         let is_running = self.is_running.load(Ordering::SeqCst);
         let uptime = start_time.elapsed();
 
-        // TODO: Track these statistics in the daemon loop
-        let last_sync = None;
-        let total_syncs = 0;
-        let successful_syncs = 0;
-        let failed_syncs = 0;
+        let last_sync = self.last_sync.lock().ok().and_then(|last| *last);
+        let total_syncs = self.total_syncs.load(Ordering::SeqCst);
+        let successful_syncs = self.successful_syncs.load(Ordering::SeqCst);
+        let failed_syncs = self.failed_syncs.load(Ordering::SeqCst);
 
         // Calculate next sync time
         let next_sync_in = if is_running {
-            let interval_secs = parse_daemon_duration(&self.config.daemon.interval).unwrap_or(1800); // Default 30 minutes
+            let interval_secs = parse_duration_secs(&self.config.daemon.interval).unwrap_or(1800); // Default 30 minutes
             Some(Duration::from_secs(interval_secs))
         } else {
             None
@@ -241,17 +311,25 @@ impl Daemon {
     }
 
     /// Main daemon loop - runs periodic sync operations
-    async fn daemon_loop(&self, mut shutdown_receiver: broadcast::Receiver<()>) -> Result<()> {
-        let sync_interval_secs = parse_daemon_duration(&self.config.daemon.interval)
-            .context("Failed to parse daemon sync interval")?;
-        let sync_interval = Duration::from_secs(sync_interval_secs);
+    async fn daemon_loop(
+        &mut self,
+        mut shutdown_receiver: broadcast::Receiver<()>,
+        mut signals: DaemonSignals,
+    ) -> Result<()> {
+        let mut sync_interval = self.sync_interval()?;
         let mut interval_timer = interval(sync_interval);
+        let mut active_hours = self.config.daemon.active_window()?;
 
         info!("Daemon loop started with interval: {:?}", sync_interval);
 
         // Skip the first immediate tick
         interval_timer.tick().await;
 
+        // Event pruning runs at startup too, so a daemon restarted more often
+        // than daily still gets cleaned up
+        let mut auto_cleanup_days = self.config.daemon.auto_cleanup_days;
+        let mut cleanup_timer = interval(AUTO_CLEANUP_INTERVAL);
+
         loop {
             tokio::select! {
                 // Shutdown signal received
@@ -260,33 +338,83 @@ impl Daemon {
                     break;
                 }
 
+                _ = signals.interrupt.recv() => {
+                    info!("SIGINT received, stopping daemon...");
+                    self.is_running.store(false, Ordering::SeqCst);
+                    break;
+                }
+
+                _ = signals.terminate.recv() => {
+                    info!("SIGTERM received, stopping daemon...");
+                    self.is_running.store(false, Ordering::SeqCst);
+                    break;
+                }
+
+                // Configuration reload (`systemctl reload`)
+                _ = signals.hangup.recv() => {
+                    info!("SIGHUP received, reloading configuration");
+                    if let Err(e) = self.reload_config().await {
+                        warn!("Keeping the current configuration: {:#}", e);
+                        continue;
+                    }
+
+                    // Validated by the reload; the next sync is a full interval away
+                    let new_interval = self.sync_interval()?;
+                    if new_interval != sync_interval {
+                        sync_interval = new_interval;
+                        interval_timer =
+                            interval_at(tokio::time::Instant::now() + sync_interval, sync_interval);
+                        info!("Sync interval is now {:?}", sync_interval);
+                    }
+                    active_hours = self.config.daemon.active_window()?;
+                    auto_cleanup_days = self.config.daemon.auto_cleanup_days;
+                }
+
+                // Daily event history cleanup (`daemon.auto_cleanup_days`)
+                _ = cleanup_timer.tick(), if auto_cleanup_days.is_some() => {
+                    if let Some(days) = auto_cleanup_days {
+                        if let Err(e) = auto_cleanup(days) {
+                            warn!("Automatic event cleanup failed: {:#}", e);
+                        }
+                    }
+                }
+
                 // Sync interval elapsed
                 _ = interval_timer.tick() => {
                     if !self.is_running.load(Ordering::SeqCst) {
                         break;
                     }
 
-                    debug!("Starting scheduled sync operation");
-                    let sync_start = Instant::now();
-
-                    // Discover repositories first
-                    match self.discovery.discover().await {
-                        Ok(repos) => {
-                            // Then sync them
-                            match self.sync_engine.sync_repos(repos).await {
-                                Ok(summary) => {
-                                    let sync_duration = sync_start.elapsed();
-                                    self.log_sync_success(&summary, sync_duration);
-                                }
-                                Err(e) => {
-                                    self.log_sync_failure(&e);
-                                }
-                            }
+                    if let Some(window) = active_hours {
+                        if !window.contains(Local::now().time()) {
+                            info!(
+                                "Skipping scheduled sync: outside active hours {} (daemon.active_hours)",
+                                window
+                            );
+                            continue;
                         }
-                        Err(e) => {
-                            error!("Repository discovery failed: {:?}", e);
+                    }
+
+                    if let Some(reason) = self.laptop_skip_reason().await {
+                        info!("Skipping scheduled sync: {}", reason);
+                        continue;
+                    }
+
+                    // Skip this cycle rather than fail deep inside discovery
+                    let mut offline = None;
+                    for host in provider_hosts(&self.config) {
+                        if let Err(e) = check_connectivity(&host).await {
+                            offline = Some(e);
+                            break;
                         }
                     }
+                    if let Some(e) = offline {
+                        warn!("Skipping scheduled sync: {}", e);
+                        continue;
+                    }
+
+                    let success = self.run_sync().await;
+                    self.record_sync(success);
                 }
             }
         }
@@ -295,14 +423,190 @@ impl Daemon {
         Ok(())
     }
 
-    /// Wait for shutdown signals (SIGTERM, SIGINT, Ctrl+C)
-    async fn wait_for_shutdown_signal() {
-        // For now, just handle Ctrl+C. More sophisticated signal handling
-        // can be added later with proper tokio signal features
-        tokio::signal::ctrl_c()
+    /// Interval between scheduled syncs (`daemon.interval`)
+    fn sync_interval(&self) -> Result<Duration> {
+        let secs = parse_duration_secs(&self.config.daemon.interval)
+            .context("Failed to parse daemon sync interval")?;
+        Ok(Duration::from_secs(secs))
+    }
+
+    /// Re-read the configuration file and rebuild discovery and the sync
+    /// engine from it
+    ///
+    /// The current configuration stays in effect if the file can't be loaded
+    /// or its schedule is invalid. The PID and log files aren't moved.
+    async fn reload_config(&mut self) -> Result<()> {
+        let path = self
+            .config
+            .source_path
+            .clone()
+            .context("The configuration wasn't loaded from a file")?;
+        let config = Config::load(&path)?;
+        parse_duration_secs(&config.daemon.interval)
+            .context("Failed to parse daemon sync interval")?;
+        config.daemon.active_window()?;
+        for warning in &config.load_warnings {
+            warn!("{}", warning);
+        }
+
+        let discovery = configured_sources(&config)
             .await
-            .expect("Failed to listen for ctrl-c");
-        debug!("Ctrl+C received");
+            .context("Failed to create repository discovery for daemon")?;
+        let sync_engine = SyncEngine::with_state_db(config.clone())
+            .context("Failed to create sync engine with state database")?
+            .with_token_auth()
+            .await;
+
+        self.config = Arc::new(config);
+        self.discovery = discovery;
+        self.sync_engine = sync_engine;
+        info!("Configuration reloaded from {}", path.display());
+        Ok(())
+    }
+
+    /// Run one scheduled sync: discover repositories, then sync them
+    ///
+    /// Returns whether both succeeded.
+    async fn run_sync(&self) -> bool {
+        debug!("Starting scheduled sync operation");
+        let sync_start = Instant::now();
+        let first_event_id = self.latest_event_id();
+
+        // Discover repositories first
+        match discover_from(&self.discovery).await {
+            Ok(repos) => {
+                // Then sync them
+                match self.sync_engine.sync_repos(repos).await {
+                    Ok(summary) => {
+                        let sync_duration = sync_start.elapsed();
+                        self.log_sync_success(&summary, sync_duration);
+                        self.notify_failures(first_event_id, &summary).await;
+                        true
+                    }
+                    Err(e) => {
+                        self.log_sync_failure(&e);
+                        false
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Repository discovery failed: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Count a completed sync in the statistics and persist them
+    fn record_sync(&self, success: bool) {
+        self.total_syncs.fetch_add(1, Ordering::SeqCst);
+        if success {
+            self.successful_syncs.fetch_add(1, Ordering::SeqCst);
+        } else {
+            self.failed_syncs.fetch_add(1, Ordering::SeqCst);
+        }
+        if let Ok(mut last_sync) = self.last_sync.lock() {
+            *last_sync = Some(Instant::now());
+        }
+        self.save_stats(Some(Utc::now()));
+    }
+
+    /// Write the sync statistics next to the PID file (if one is configured)
+    fn save_stats(&self, last_sync_at: Option<DateTime<Utc>>) {
+        let Some(path) = self.pid_file_path.as_deref().map(stats_file_path) else {
+            return;
+        };
+        let stats = DaemonStats {
+            total_syncs: self.total_syncs.load(Ordering::SeqCst),
+            successful_syncs: self.successful_syncs.load(Ordering::SeqCst),
+            failed_syncs: self.failed_syncs.load(Ordering::SeqCst),
+            last_sync_at,
+        };
+        let result = serde_json::to_string(&stats)
+            .context("Failed to serialize daemon statistics")
+            .and_then(|json| fs::write(&path, json).context("Failed to write daemon statistics"));
+        if let Err(e) = result {
+            warn!("{:#}: {}", e, path.display());
+        }
+    }
+
+    /// ID of the latest recorded event (None without a state database)
+    fn latest_event_id(&self) -> Option<i64> {
+        let db = self.sync_engine.state_db()?.lock().ok()?;
+        db.latest_event_id().ok()
+    }
+
+    /// Desktop notifications for repositories that became chronically failing
+    /// since `first_event_id`, and for a cycle with at least
+    /// `notifications.failure_threshold` failures (`notifications.desktop`)
+    async fn notify_failures(&self, first_event_id: Option<i64>, summary: &SyncSummary) {
+        let settings = &self.config.notifications;
+        if !settings.desktop {
+            return;
+        }
+
+        let chronic = first_event_id
+            .and_then(|id| {
+                let db = self.sync_engine.state_db()?.lock().ok()?;
+                db.get_events_after(id).ok()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|event| event.event_type == EventType::ChronicFailure);
+
+        let mut notifications = Vec::new();
+        for event in chronic {
+            let repo = event.repo_full_name.unwrap_or_default();
+            let title = format!("{} keeps failing", repo);
+            notifications.push((repo, title, event.summary));
+        }
+        let threshold = settings.failure_threshold;
+        if threshold > 0 && summary.failed_operations >= threshold {
+            notifications.push((
+                "sync cycle".to_string(),
+                "Repository sync failures".to_string(),
+                format!(
+                    "{} of {} repositories failed to sync",
+                    summary.failed_operations, summary.total_repositories
+                ),
+            ));
+        }
+
+        // Each subject is notified about at most once per day, across restarts
+        let now = Local::now();
+        let due: Vec<_> = {
+            let Some(Ok(db)) = self.sync_engine.state_db().map(|db| db.lock()) else {
+                debug!("No state database; skipping desktop notifications");
+                return;
+            };
+            notifications
+                .into_iter()
+                .filter(
+                    |(subject, _, _)| match db.claim_notification(subject, now) {
+                        Ok(due) => due,
+                        Err(e) => {
+                            warn!("Failed to record desktop notification: {:#}", e);
+                            false
+                        }
+                    },
+                )
+                .collect()
+        };
+        for (_, title, body) in due {
+            crate::notify::show(&title, &body).await;
+        }
+    }
+
+    /// Why this cycle should be skipped to spare the battery or a metered
+    /// connection (None to sync as usual, including when the state is unknown)
+    async fn laptop_skip_reason(&self) -> Option<&'static str> {
+        let daemon = &self.config.daemon;
+        if daemon.skip_on_battery && on_battery().await == Some(true) {
+            return Some("running on battery power (daemon.skip_on_battery)");
+        }
+        if daemon.skip_on_metered && on_metered_connection().await == Some(true) {
+            return Some("network connection is metered (daemon.skip_on_metered)");
+        }
+        None
     }
 
     /// Write PID file for daemon process management
@@ -320,6 +624,9 @@ impl Daemon {
             info!("PID file written: {} (PID: {})", pid_file.display(), pid);
         }
 
+        // Statistics start from zero with each daemon run
+        self.save_stats(None);
+
         Ok(())
     }
 
@@ -330,6 +637,7 @@ impl Daemon {
                 fs::remove_file(pid_file).context("Failed to remove PID file")?;
                 info!("PID file removed: {}", pid_file.display());
             }
+            let _ = fs::remove_file(stats_file_path(pid_file));
         }
 
         self.is_running.store(false, Ordering::SeqCst);
@@ -339,6 +647,11 @@ impl Daemon {
 
     /// Log successful sync operation
     fn log_sync_success(&self, summary: &SyncSummary, duration: Duration) {
+        if self.config.daemon.is_compact_log_style() {
+            info!("{}", format_compact_summary(summary, duration, Utc::now()));
+            return;
+        }
+
         info!(
             "Sync completed successfully in {:.2}s: {} repos, {} successful, {} failed, {} skipped",
             duration.as_secs_f64(),
@@ -351,10 +664,70 @@ impl Daemon {
 
     /// Log failed sync operation
     fn log_sync_failure(&self, error: &anyhow::Error) {
+        if self.config.daemon.is_compact_log_style() {
+            error!("{}", format_compact_failure(error, Utc::now()));
+            return;
+        }
+
         error!("Sync operation failed: {:?}", error);
     }
 }
 
+/// Format a sync cycle as a single terse line for the "compact" log style
+///
+/// Example: `2024-01-02T10:00Z sync ok=120 new=3 pull=5 skip=2 fail=0 12.3s`
+pub fn format_compact_summary(
+    summary: &SyncSummary,
+    duration: Duration,
+    timestamp: DateTime<Utc>,
+) -> String {
+    let mut cloned = 0;
+    let mut pulled = 0;
+
+    for result in &summary.results {
+        match result {
+            SyncResult::Cloned { .. } => cloned += 1,
+            SyncResult::Pulled { .. }
+            | SyncResult::Rebased { .. }
+            | SyncResult::Merged { .. }
+            | SyncResult::BranchSwitched { .. } => pulled += 1,
+            _ => {}
+        }
+    }
+
+    format!(
+        "{} sync ok={} new={} pull={} skip={} fail={} {:.1}s",
+        timestamp.format("%Y-%m-%dT%H:%MZ"),
+        summary.successful_operations,
+        cloned,
+        pulled,
+        summary.skipped_operations,
+        summary.failed_operations,
+        duration.as_secs_f64()
+    )
+}
+
+/// Format a failed sync cycle as a single line for the "compact" log style
+pub fn format_compact_failure(error: &anyhow::Error, timestamp: DateTime<Utc>) -> String {
+    format!(
+        "{} sync error: {}",
+        timestamp.format("%Y-%m-%dT%H:%MZ"),
+        error
+    )
+}
+
+/// Prune acknowledged events older than `days` and compact the state database
+fn auto_cleanup(days: u32) -> Result<()> {
+    let db = StateDb::open()?;
+    let count = db.cleanup_old_events(days)?;
+    db.vacuum()?;
+    info!(
+        "Pruned {} acknowledged events older than {} days",
+        count, days
+    );
+    Ok(())
+}
+
 /// Helper to create daemon from default config
 pub async fn create_daemon_from_config() -> Result<Daemon> {
     let config = Config::load_or_default().context("Failed to load configuration for daemon")?;
@@ -362,6 +735,46 @@ pub async fn create_daemon_from_config() -> Result<Daemon> {
     Daemon::new(config).await.context("Failed to create daemon")
 }
 
+/// PID recorded in the daemon's PID file (None if missing or invalid)
+pub fn daemon_pid(config: &Config) -> Option<u32> {
+    let resolved_path = get_pid_file_path(&config.daemon.pid_file);
+    fs::read_to_string(resolved_path).ok()?.trim().parse().ok()
+}
+
+/// How long the daemon has been running, judging by when it wrote its PID file
+pub fn daemon_uptime(config: &Config) -> Option<Duration> {
+    let resolved_path = get_pid_file_path(&config.daemon.pid_file);
+    let modified = fs::metadata(resolved_path).ok()?.modified().ok()?;
+    modified.elapsed().ok()
+}
+
+/// File the daemon's sync statistics are kept in, next to its PID file
+/// (`reposentry.pid` -> `reposentry.stats.json`)
+fn stats_file_path(pid_file: &Path) -> PathBuf {
+    pid_file.with_extension("stats.json")
+}
+
+/// Sync statistics of the running daemon (None if unavailable)
+pub fn daemon_stats(config: &Config) -> Option<DaemonStats> {
+    let resolved_path = get_pid_file_path(&config.daemon.pid_file);
+    if resolved_path.is_empty() {
+        return None;
+    }
+    let json = fs::read_to_string(stats_file_path(Path::new(&resolved_path))).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Time until the next scheduled sync of a daemon that has been up for `uptime`
+///
+/// Syncs run every `interval` from startup (the first one an interval in).
+pub fn next_sync_in(uptime: Duration, interval: Duration) -> Option<Duration> {
+    if interval.is_zero() {
+        return None;
+    }
+    let since_last = Duration::from_secs(uptime.as_secs() % interval.as_secs());
+    Some(interval - since_last)
+}
+
 /// Check if daemon is currently running by checking PID file
 pub fn is_daemon_running(config: &Config) -> Result<bool> {
     let resolved_path = get_pid_file_path(&config.daemon.pid_file);
@@ -409,6 +822,20 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_next_sync_in() {
+        let interval = Duration::from_secs(1800);
+        assert_eq!(
+            next_sync_in(Duration::from_secs(0), interval),
+            Some(Duration::from_secs(1800))
+        );
+        assert_eq!(
+            next_sync_in(Duration::from_secs(2000), interval),
+            Some(Duration::from_secs(1600))
+        );
+        assert_eq!(next_sync_in(Duration::from_secs(10), Duration::ZERO), None);
+    }
+
     #[tokio::test]
     async fn test_daemon_creation() {
         let config = Config::default();
@@ -421,13 +848,82 @@ mod tests {
             }
             Err(e) => {
                 // Expected if authentication is not available
-                assert!(
-                    e.to_string().contains("authentication") || e.to_string().contains("GitHub")
-                );
+                let e = format!("{:#}", e);
+                assert!(e.contains("authentication") || e.contains("GitHub"));
             }
         }
     }
 
+    /// Discovery that always fails, so a sync cycle needs no network or sync lock
+    struct FailingDiscovery;
+
+    #[async_trait::async_trait]
+    impl Discovery for FailingDiscovery {
+        async fn discover(&self) -> Result<Vec<crate::RepoSpec>> {
+            Err(anyhow::anyhow!("offline"))
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "test"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_statistics() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = Config::default();
+        config.daemon.interval = "1s".to_string();
+        // No provider hosts, so cycles don't check connectivity
+        config.providers = Vec::new();
+        config.daemon.pid_file = temp_dir
+            .path()
+            .join("reposentry.pid")
+            .to_string_lossy()
+            .to_string();
+
+        let sync_engine =
+            SyncEngine::with_custom_state_db(config.clone(), StateDb::open_in_memory().unwrap());
+        let mut daemon = Daemon::from_parts(
+            Arc::new(config.clone()),
+            vec![Box::new(FailingDiscovery)],
+            sync_engine,
+        );
+        daemon.is_running.store(true, Ordering::SeqCst);
+        daemon.write_pid_file().unwrap();
+        assert_eq!(daemon_stats(&config), Some(DaemonStats::default()));
+
+        // Step the paused clock to the scheduled syncs at 1s and 2s, letting
+        // the loop run until it waits for the next tick each time
+        let shutdown = daemon.shutdown_sender.subscribe();
+        {
+            let cycles = daemon.daemon_loop(shutdown, DaemonSignals::default());
+            tokio::pin!(cycles);
+            let _ = tokio::time::timeout(Duration::from_millis(1), &mut cycles).await;
+            for _ in 0..2 {
+                tokio::time::advance(Duration::from_secs(1)).await;
+                let _ = tokio::time::timeout(Duration::from_millis(1), &mut cycles).await;
+            }
+        }
+
+        let status = daemon.status(Instant::now());
+        assert_eq!(status.total_syncs, 2);
+        assert_eq!(status.failed_syncs, status.total_syncs);
+        assert_eq!(status.successful_syncs, 0);
+        assert!(status.last_sync.is_some());
+
+        let stats = daemon_stats(&config).unwrap();
+        assert_eq!(stats.total_syncs, status.total_syncs);
+        assert_eq!(stats.failed_syncs, status.failed_syncs);
+        assert!(stats.last_sync_at.is_some());
+
+        daemon.cleanup().unwrap();
+        assert!(daemon_stats(&config).is_none());
+    }
+
     #[test]
     fn test_pid_file_operations() {
         let temp_dir = tempdir().unwrap();
@@ -469,4 +965,62 @@ mod tests {
         assert!(status.next_sync_in.is_some());
         assert_eq!(status.next_sync_in.unwrap().as_secs(), interval_seconds);
     }
+
+    #[test]
+    fn test_format_compact_summary() {
+        let summary = SyncSummary {
+            total_repositories: 4,
+            successful_operations: 3,
+            failed_operations: 0,
+            skipped_operations: 1,
+            rebased_operations: 0,
+            merged_operations: 0,
+            duration: Duration::from_millis(12_300),
+            rate_limit: None,
+            new_repositories: Vec::new(),
+            durations: std::collections::HashMap::new(),
+            results: vec![
+                SyncResult::Cloned {
+                    path: PathBuf::from("/test/repo1"),
+                    branch: Some("main".to_string()),
+                },
+                SyncResult::Pulled {
+                    path: PathBuf::from("/test/repo2"),
+                    commits_updated: 2,
+                    branch: Some("main".to_string()),
+                    remote: "origin".to_string(),
+                    stashed: false,
+                    stash_conflict: false,
+                },
+                SyncResult::FetchedOnly {
+                    path: PathBuf::from("/test/repo3"),
+                    reason: "Repository is up to date".to_string(),
+                },
+                SyncResult::Skipped {
+                    path: PathBuf::from("/test/repo4"),
+                    reason: "Has changes".to_string(),
+                },
+            ],
+        };
+        let timestamp = DateTime::parse_from_rfc3339("2024-01-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let line = format_compact_summary(&summary, Duration::from_millis(12_300), timestamp);
+        assert_eq!(
+            line,
+            "2024-01-02T10:00Z sync ok=3 new=1 pull=1 skip=1 fail=0 12.3s"
+        );
+    }
+
+    #[test]
+    fn test_format_compact_failure() {
+        let timestamp = DateTime::parse_from_rfc3339("2024-01-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let error = anyhow::anyhow!("discovery failed");
+
+        let line = format_compact_failure(&error, timestamp);
+        assert_eq!(line, "2024-01-02T10:00Z sync error: discovery failed");
+    }
 }
@@ -3,12 +3,26 @@
 //! This module provides a provider-agnostic interface for discovering repositories
 //! from various sources (GitHub, GitLab, Codeberg, local directories, etc.)
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
+use crate::state::StateDb;
+
+pub mod gitlab;
+
+pub use gitlab::GitLabDiscovery;
+
 /// Clone method preference for a repository
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CloneMethod {
     /// Use SSH (git@github.com:user/repo.git)
     #[default]
@@ -17,11 +31,80 @@ pub enum CloneMethod {
     Https,
 }
 
+/// Why a repository was included during discovery
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RepoSource {
+    /// Owned by the authenticated user
+    #[default]
+    Owned,
+    /// Belongs to an organization the user is a member of
+    Org(String),
+    /// Starred by the authenticated user
+    Starred,
+    /// A fork (included via `include_forks`)
+    Fork,
+    /// A gist of the authenticated user (included via `include_gists`)
+    Gist,
+}
+
+impl RepoSource {
+    /// Parse a source from its stored string form (see `Display`)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "owned" => Some(RepoSource::Owned),
+            "starred" => Some(RepoSource::Starred),
+            "fork" => Some(RepoSource::Fork),
+            "gist" => Some(RepoSource::Gist),
+            _ => s
+                .strip_prefix("org:")
+                .map(|org| RepoSource::Org(org.to_string())),
+        }
+    }
+}
+
+/// Serialized in its stored string form ("owned", "org:acme", ...)
+impl Serialize for RepoSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RepoSource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        RepoSource::parse(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown repo source: {}", s)))
+    }
+}
+
+impl fmt::Display for RepoSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoSource::Owned => write!(f, "owned"),
+            RepoSource::Org(org) => write!(f, "org:{}", org),
+            RepoSource::Starred => write!(f, "starred"),
+            RepoSource::Fork => write!(f, "fork"),
+            RepoSource::Gist => write!(f, "gist"),
+        }
+    }
+}
+
+/// How recently a repository was pushed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    /// Pushed within the last week
+    Week,
+    /// Pushed within the last month
+    Month,
+    /// Not pushed for over a month
+    Dormant,
+}
+
 /// Provider-agnostic repository specification
 ///
 /// This struct contains all information needed to clone and manage a repository,
 /// regardless of where it was discovered from.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoSpec {
     /// Repository name (e.g., "reposentry")
     pub name: String,
@@ -39,7 +122,7 @@ pub struct RepoSpec {
     pub clone_method: CloneMethod,
 
     /// Local path where this repo should be cloned
-    /// Computed based on config (base_dir, separate_org_dirs, etc.)
+    /// Computed based on config (base_dir, organization layout, etc.)
     pub local_path: PathBuf,
 
     /// Whether the repository is a fork
@@ -56,6 +139,36 @@ pub struct RepoSpec {
 
     /// Source provider (for logging/display)
     pub provider: String,
+
+    /// Why this repository was discovered (owned, org, starred, fork)
+    pub source: RepoSource,
+
+    /// Whether this spec points at a repository's wiki rather than its code
+    pub is_wiki: bool,
+
+    /// GitHub topics the repository is tagged with
+    pub topics: Vec<String>,
+
+    /// When the repository was last pushed to (if known)
+    pub pushed_at: Option<DateTime<Utc>>,
+
+    /// Clone URL of the repository this fork was created from (if known)
+    pub upstream_url: Option<String>,
+
+    /// Default branch of the fork's parent repository
+    pub upstream_branch: Option<String>,
+
+    /// Web page of the repository (if known)
+    pub html_url: Option<String>,
+
+    /// Short description of the repository (if any)
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// ID of the repository on its provider (GitHub repository or GitLab
+    /// project ID), which survives transfers and renames
+    #[serde(default)]
+    pub provider_id: Option<u64>,
 }
 
 impl RepoSpec {
@@ -68,6 +181,176 @@ impl RepoSpec {
     pub fn full_name(&self) -> String {
         format!("{}/{}", self.owner, self.name)
     }
+
+    /// How recently the repository was pushed to, as of `now` (None if unknown)
+    pub fn activity(&self, now: DateTime<Utc>) -> Option<Activity> {
+        let age = now - self.pushed_at?;
+        Some(if age <= Duration::days(7) {
+            Activity::Week
+        } else if age <= Duration::days(30) {
+            Activity::Month
+        } else {
+            Activity::Dormant
+        })
+    }
+
+    /// Build the spec for this repository's wiki (`<repo>.wiki.git`)
+    ///
+    /// The wiki is cloned into a `<repo>.wiki` sibling directory.
+    pub fn wiki_spec(&self) -> RepoSpec {
+        let name = format!("{}.wiki", self.name);
+        // Keep the `.git` suffix of mirror clones
+        let local_path = match self.local_path.extension() {
+            Some(ext) if ext == "git" => self.local_path.with_file_name(format!("{}.git", name)),
+            _ => self.local_path.with_file_name(&name),
+        };
+
+        RepoSpec {
+            name,
+            owner: self.owner.clone(),
+            clone_url: wiki_url(&self.clone_url),
+            clone_url_alt: self.clone_url_alt.as_deref().map(wiki_url),
+            clone_method: self.clone_method.clone(),
+            local_path,
+            is_fork: self.is_fork,
+            is_archived: self.is_archived,
+            size_bytes: None,
+            default_branch: None,
+            provider: self.provider.clone(),
+            source: self.source.clone(),
+            is_wiki: true,
+            topics: self.topics.clone(),
+            pushed_at: self.pushed_at,
+            upstream_url: None,
+            upstream_branch: None,
+            html_url: self.html_url.as_deref().map(|url| format!("{}/wiki", url)),
+            description: None,
+            provider_id: None,
+        }
+    }
+}
+
+/// Sort specs by full name (case-insensitively), each wiki right after its
+/// repository
+pub fn sort_specs(specs: &mut [RepoSpec]) {
+    specs.sort_by_cached_key(|spec| {
+        let name = match spec.name.strip_suffix(".wiki") {
+            Some(name) if spec.is_wiki => name,
+            _ => &spec.name,
+        };
+        (spec.owner.to_lowercase(), name.to_lowercase(), spec.is_wiki)
+    });
+}
+
+/// Keep the `n` most recently pushed repositories, newest first
+///
+/// Repositories without a push date sort last. Wikis don't count towards `n`
+/// and are kept along with their repository.
+pub fn newest_specs(mut specs: Vec<RepoSpec>, n: usize) -> Vec<RepoSpec> {
+    // Stable, so each wiki stays right after its repository
+    specs.sort_by_key(|spec| Reverse(spec.pushed_at));
+
+    let repo_name = |spec: &RepoSpec| match spec.name.strip_suffix(".wiki") {
+        Some(name) if spec.is_wiki => format!("{}/{}", spec.owner, name),
+        _ => spec.full_name(),
+    };
+    let kept: HashSet<String> = specs
+        .iter()
+        .filter(|spec| !spec.is_wiki)
+        .take(n)
+        .map(RepoSpec::full_name)
+        .collect();
+
+    specs.retain(|spec| kept.contains(&repo_name(spec)));
+    specs
+}
+
+/// Discovery results loaded from the state database cache
+pub struct CachedSpecs {
+    /// The repositories as they were discovered
+    pub specs: Vec<RepoSpec>,
+
+    /// When they were discovered
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Load the cached discovery results, if they are fresh and were discovered
+/// with the same configuration (see `advanced.cache_duration`)
+pub fn load_cached_specs(db: &StateDb, config: &Config) -> Option<CachedSpecs> {
+    let ttl = config.advanced.cache_ttl()?;
+    let (fetched_at, specs) = db.load_discovery_cache(&cache_key(config)?).ok()??;
+    if Utc::now() - fetched_at > ttl {
+        return None;
+    }
+
+    let specs = serde_json::from_str(&specs).ok()?;
+    Some(CachedSpecs { specs, fetched_at })
+}
+
+/// Cache discovery results for `load_cached_specs`
+pub fn store_cached_specs(db: &StateDb, config: &Config, specs: &[RepoSpec]) -> Result<()> {
+    let Some(key) = cache_key(config) else {
+        return Ok(());
+    };
+    let specs = serde_json::to_string(specs).context("Failed to serialize repo specs")?;
+    db.save_discovery_cache(&key, &specs)
+}
+
+/// Fingerprint of the configuration, so any change invalidates the cache
+fn cache_key(config: &Config) -> Option<String> {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(config).ok()?.hash(&mut hasher);
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+/// Parse a list of repository names, one `owner/repo` per line
+///
+/// Blank lines and `#` comments are ignored. Only the first field of each
+/// line is used, so tabular output such as `gh repo list` can be piped in.
+pub fn parse_repo_list(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Find the spec for a repository given as `owner/repo` or just `repo`
+///
+/// Names are matched case-insensitively. A bare name must identify a single
+/// repository; wikis are only matched by their own name (`repo.wiki`).
+pub fn find_spec<'a>(specs: &'a [RepoSpec], name: &str) -> Result<&'a RepoSpec> {
+    let matches: Vec<&RepoSpec> = if name.contains('/') {
+        specs
+            .iter()
+            .filter(|spec| spec.full_name().eq_ignore_ascii_case(name))
+            .collect()
+    } else {
+        specs
+            .iter()
+            .filter(|spec| spec.name.eq_ignore_ascii_case(name))
+            .collect()
+    };
+
+    match matches.as_slice() {
+        [spec] => Ok(spec),
+        [] => Err(anyhow!("Repository '{}' not found", name)),
+        _ => Err(anyhow!(
+            "Repository name '{}' is ambiguous: {}",
+            name,
+            matches
+                .iter()
+                .map(|spec| spec.full_name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Convert a repository clone URL into its wiki clone URL
+fn wiki_url(url: &str) -> String {
+    format!("{}.wiki.git", url.strip_suffix(".git").unwrap_or(url))
 }
 
 /// Trait for repository discovery from various providers
@@ -126,6 +409,7 @@ impl MultiDiscovery {
             }
         }
 
+        sort_specs(&mut all_repos);
         Ok(all_repos)
     }
 }
@@ -136,6 +420,38 @@ impl Default for MultiDiscovery {
     }
 }
 
+/// Create the discovery source of each provider in `providers`
+pub async fn configured_sources(config: &Config) -> Result<Vec<Box<dyn Discovery>>> {
+    let mut sources: Vec<Box<dyn Discovery>> = Vec::new();
+    for provider in &config.providers {
+        match provider.as_str() {
+            "github" => sources.push(Box::new(GitHubDiscovery::new(config.clone()).await?)),
+            "gitlab" => sources.push(Box::new(GitLabDiscovery::new(config.clone())?)),
+            other => return Err(anyhow!("Unknown provider '{}'", other)),
+        }
+    }
+    Ok(sources)
+}
+
+/// Discover from each source in turn, failing on the first error
+pub async fn discover_from(sources: &[Box<dyn Discovery>]) -> Result<Vec<RepoSpec>> {
+    let mut specs = Vec::new();
+    for source in sources {
+        let discovered = source
+            .discover()
+            .await
+            .with_context(|| format!("{} discovery failed", source.provider_name()))?;
+        specs.extend(discovered);
+    }
+    sort_specs(&mut specs);
+    Ok(specs)
+}
+
+/// Discover repositories from every configured provider
+pub async fn discover_configured(config: &Config) -> Result<Vec<RepoSpec>> {
+    discover_from(&configured_sources(config).await?).await
+}
+
 // =============================================================================
 // GitHub Discovery Implementation
 // =============================================================================
@@ -167,6 +483,11 @@ impl GitHubDiscovery {
         }
     }
 
+    /// The underlying GitHub client
+    pub fn client(&self) -> &GitHubClient {
+        &self.client
+    }
+
     /// Convert octocrab Repository to our RepoSpec
     fn repo_to_spec(&self, repo: &octocrab::models::Repository) -> RepoSpec {
         let owner = repo
@@ -175,17 +496,10 @@ impl GitHubDiscovery {
             .map(|o| o.login.clone())
             .unwrap_or_else(|| "unknown".to_string());
 
-        // Compute local path based on config
-        let base_dir = shellexpand::full(&self.config.base_directory)
-            .unwrap_or_else(|_| std::borrow::Cow::Borrowed(&self.config.base_directory));
+        let topics = repo.topics.clone().unwrap_or_default();
 
-        let local_path = if self.config.organization.separate_org_dirs {
-            PathBuf::from(base_dir.as_ref())
-                .join(&owner)
-                .join(&repo.name)
-        } else {
-            PathBuf::from(base_dir.as_ref()).join(&repo.name)
-        };
+        // Compute local path based on config
+        let local_path = self.config.repo_local_path(&owner, &repo.name, &topics);
 
         // Prefer SSH URL, fall back to clone_url (HTTPS)
         let ssh_url = repo.ssh_url.clone();
@@ -201,6 +515,22 @@ impl GitHubDiscovery {
             ),
         };
 
+        // The fork's parent, cloned with the same protocol as the fork itself
+        let parent = repo.parent.as_deref();
+        let upstream_url = parent.and_then(|parent| match clone_method {
+            CloneMethod::Ssh => parent.ssh_url.clone(),
+            CloneMethod::Https => parent.clone_url.as_ref().map(|u| u.to_string()),
+        });
+        let upstream_branch = parent.and_then(|parent| parent.default_branch.clone());
+
+        let source = if repo.fork.unwrap_or(false) {
+            RepoSource::Fork
+        } else if owner == self.client.username() {
+            RepoSource::Owned
+        } else {
+            RepoSource::Org(owner.clone())
+        };
+
         RepoSpec {
             name: repo.name.clone(),
             owner,
@@ -213,6 +543,43 @@ impl GitHubDiscovery {
             size_bytes: repo.size.map(|kb| kb as u64 * 1024),
             default_branch: repo.default_branch.clone(),
             provider: "github".to_string(),
+            source,
+            is_wiki: false,
+            topics,
+            pushed_at: repo.pushed_at,
+            upstream_url,
+            upstream_branch,
+            html_url: repo.html_url.as_ref().map(|u| u.to_string()),
+            description: repo.description.clone(),
+            provider_id: Some(repo.id.0),
+        }
+    }
+
+    /// Convert an octocrab Gist to our RepoSpec (gists are git repositories too)
+    fn gist_to_spec(&self, gist: &octocrab::models::gists::Gist) -> RepoSpec {
+        let owner = self.client.username().to_string();
+
+        RepoSpec {
+            name: gist.id.clone(),
+            owner,
+            clone_url: format!("git@gist.github.com:{}.git", gist.id),
+            clone_url_alt: Some(gist.git_pull_url.to_string()),
+            clone_method: CloneMethod::Ssh,
+            local_path: self.config.gist_local_path(&gist.id),
+            is_fork: false,
+            is_archived: false,
+            size_bytes: None,
+            default_branch: None,
+            provider: "github".to_string(),
+            source: RepoSource::Gist,
+            is_wiki: false,
+            topics: Vec::new(),
+            pushed_at: Some(gist.updated_at),
+            upstream_url: None,
+            upstream_branch: None,
+            html_url: Some(gist.html_url.to_string()),
+            description: gist.description.clone(),
+            provider_id: None,
         }
     }
 
@@ -220,6 +587,38 @@ impl GitHubDiscovery {
     pub fn username(&self) -> &str {
         self.client.username()
     }
+
+    /// Build specs for the named repositories (`owner/repo`), bypassing discovery
+    ///
+    /// Repository filters don't apply: the names are synced as given. Returns
+    /// the specs and the names that couldn't be resolved, with the reason.
+    pub async fn resolve(&self, full_names: &[String]) -> (Vec<RepoSpec>, Vec<(String, String)>) {
+        let mut specs = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for full_name in full_names {
+            let Some((owner, name)) = full_name
+                .split_once('/')
+                .filter(|(owner, name)| !owner.is_empty() && !name.is_empty())
+            else {
+                unresolved.push((full_name.clone(), "expected owner/repo".to_string()));
+                continue;
+            };
+
+            match self.client.get_repository(owner, name).await {
+                Ok(repo) => {
+                    let spec = self.repo_to_spec(&repo);
+                    if self.config.github.include_wikis && repo.has_wiki == Some(true) {
+                        specs.push(spec.wiki_spec());
+                    }
+                    specs.push(spec);
+                }
+                Err(e) => unresolved.push((full_name.clone(), format!("{:#}", e))),
+            }
+        }
+
+        (specs, unresolved)
+    }
 }
 
 #[async_trait]
@@ -227,11 +626,21 @@ impl Discovery for GitHubDiscovery {
     async fn discover(&self) -> Result<Vec<RepoSpec>> {
         let repositories = self.client.get_all_repositories(&self.config).await?;
 
-        let specs: Vec<RepoSpec> = repositories
-            .iter()
-            .map(|repo| self.repo_to_spec(repo))
-            .collect();
+        let mut specs = Vec::new();
+        for repo in &repositories {
+            let spec = self.repo_to_spec(repo);
+            if self.config.github.include_wikis && repo.has_wiki == Some(true) {
+                specs.push(spec.wiki_spec());
+            }
+            specs.push(spec);
+        }
 
+        if self.config.github.include_gists {
+            let gists = self.client.list_user_gists().await?;
+            specs.extend(gists.iter().map(|gist| self.gist_to_spec(gist)));
+        }
+
+        sort_specs(&mut specs);
         Ok(specs)
     }
 
@@ -245,24 +654,57 @@ impl Discovery for GitHubDiscovery {
     }
 }
 
+/// Fixtures shared by tests across modules
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// Spec for `test/<name>` cloned over SSH under `/base`, with every
+    /// optional field empty
+    ///
+    /// Tests override what they need with struct update syntax.
+    pub(crate) fn test_spec(name: &str) -> RepoSpec {
+        RepoSpec {
+            name: name.to_string(),
+            owner: "test".to_string(),
+            clone_url: format!("git@github.com:test/{}.git", name),
+            clone_url_alt: None,
+            clone_method: CloneMethod::Ssh,
+            local_path: PathBuf::from("/base/test").join(name),
+            is_fork: false,
+            is_archived: false,
+            size_bytes: None,
+            default_branch: None,
+            provider: "test".to_string(),
+            source: RepoSource::Owned,
+            is_wiki: false,
+            topics: Vec::new(),
+            pushed_at: None,
+            upstream_url: None,
+            upstream_branch: None,
+            html_url: None,
+            description: None,
+            provider_id: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::test_support::test_spec;
     use super::*;
 
     #[test]
     fn test_repo_spec_full_name() {
         let spec = RepoSpec {
-            name: "reposentry".to_string(),
             owner: "MKSG".to_string(),
             clone_url: "git@github.com:MKSG/reposentry.git".to_string(),
             clone_url_alt: Some("https://github.com/MKSG/reposentry.git".to_string()),
-            clone_method: CloneMethod::Ssh,
             local_path: PathBuf::from("/home/user/dev/MKSG/reposentry"),
-            is_fork: false,
-            is_archived: false,
             size_bytes: Some(1024 * 1024),
             default_branch: Some("main".to_string()),
             provider: "github".to_string(),
+            ..test_spec("reposentry")
         };
 
         assert_eq!(spec.full_name(), "MKSG/reposentry");
@@ -271,22 +713,241 @@ mod tests {
     #[test]
     fn test_repo_spec_exists_locally() {
         let spec = RepoSpec {
-            name: "nonexistent".to_string(),
-            owner: "test".to_string(),
-            clone_url: "git@github.com:test/nonexistent.git".to_string(),
-            clone_url_alt: None,
-            clone_method: CloneMethod::Ssh,
             local_path: PathBuf::from("/nonexistent/path/repo"),
-            is_fork: false,
-            is_archived: false,
-            size_bytes: None,
-            default_branch: None,
-            provider: "test".to_string(),
+            ..test_spec("nonexistent")
         };
 
         assert!(!spec.exists_locally());
     }
 
+    #[test]
+    fn test_wiki_spec() {
+        let spec = RepoSpec {
+            owner: "MKSG".to_string(),
+            clone_url: "git@github.com:MKSG/reposentry.git".to_string(),
+            clone_url_alt: Some("https://github.com/MKSG/reposentry.git".to_string()),
+            local_path: PathBuf::from("/home/user/dev/MKSG/reposentry"),
+            size_bytes: Some(1024 * 1024),
+            default_branch: Some("main".to_string()),
+            provider: "github".to_string(),
+            ..test_spec("reposentry")
+        };
+
+        let wiki = spec.wiki_spec();
+        assert!(wiki.is_wiki);
+        assert_eq!(wiki.full_name(), "MKSG/reposentry.wiki");
+        assert_eq!(wiki.clone_url, "git@github.com:MKSG/reposentry.wiki.git");
+        assert_eq!(
+            wiki.clone_url_alt,
+            Some("https://github.com/MKSG/reposentry.wiki.git".to_string())
+        );
+        assert_eq!(
+            wiki.local_path,
+            PathBuf::from("/home/user/dev/MKSG/reposentry.wiki")
+        );
+    }
+
+    #[test]
+    fn test_activity() {
+        let now = Utc::now();
+        let mut spec = RepoSpec {
+            local_path: PathBuf::from("/tmp/repo"),
+            provider: "github".to_string(),
+            ..test_spec("repo")
+        };
+        assert_eq!(spec.activity(now), None);
+
+        spec.pushed_at = Some(now - Duration::days(2));
+        assert_eq!(spec.activity(now), Some(Activity::Week));
+
+        spec.pushed_at = Some(now - Duration::days(20));
+        assert_eq!(spec.activity(now), Some(Activity::Month));
+
+        spec.pushed_at = Some(now - Duration::days(90));
+        assert_eq!(spec.activity(now), Some(Activity::Dormant));
+    }
+
+    #[test]
+    fn test_newest_specs() {
+        let now = Utc::now();
+        let spec = |name: &str, days_ago: Option<i64>| RepoSpec {
+            local_path: PathBuf::from("/tmp").join(name),
+            provider: "github".to_string(),
+            pushed_at: days_ago.map(|days| now - Duration::days(days)),
+            ..test_spec(name)
+        };
+
+        let recent = spec("recent", Some(1));
+        let specs = vec![
+            spec("unknown", None),
+            spec("old", Some(300)),
+            recent.clone(),
+            recent.wiki_spec(),
+            spec("middle", Some(30)),
+        ];
+
+        let names: Vec<String> = newest_specs(specs.clone(), 2)
+            .into_iter()
+            .map(|spec| spec.name)
+            .collect();
+        assert_eq!(names, vec!["recent", "recent.wiki", "middle"]);
+
+        assert_eq!(newest_specs(specs.clone(), 10).len(), specs.len());
+        assert!(newest_specs(specs, 0).is_empty());
+    }
+
+    #[test]
+    fn test_sort_specs() {
+        let spec = |owner: &str, name: &str| RepoSpec {
+            owner: owner.to_string(),
+            clone_url: format!("git@github.com:{}/{}.git", owner, name),
+            local_path: PathBuf::from("/tmp").join(owner).join(name),
+            provider: "github".to_string(),
+            ..test_spec(name)
+        };
+
+        let api = spec("acme", "api");
+        let mut specs = vec![
+            spec("zeta", "api"),
+            api.wiki_spec(),
+            spec("acme", "api-docs"),
+            spec("Acme", "Web"),
+            api,
+        ];
+        sort_specs(&mut specs);
+
+        let names: Vec<String> = specs.iter().map(RepoSpec::full_name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "acme/api",
+                "acme/api.wiki",
+                "acme/api-docs",
+                "Acme/Web",
+                "zeta/api"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cached_specs() {
+        let spec = RepoSpec {
+            owner: "MKSG".to_string(),
+            clone_url: "git@github.com:MKSG/reposentry.git".to_string(),
+            local_path: PathBuf::from("/home/user/dev/MKSG/reposentry"),
+            default_branch: Some("main".to_string()),
+            provider: "github".to_string(),
+            source: RepoSource::Org("MKSG".to_string()),
+            topics: vec!["rust".to_string()],
+            pushed_at: Some(Utc::now()),
+            ..test_spec("reposentry")
+        };
+        let db = StateDb::open_in_memory().unwrap();
+        let mut config = Config::default();
+
+        assert!(load_cached_specs(&db, &config).is_none());
+        store_cached_specs(&db, &config, std::slice::from_ref(&spec)).unwrap();
+
+        let cached = load_cached_specs(&db, &config).unwrap();
+        assert_eq!(cached.specs.len(), 1);
+        assert_eq!(cached.specs[0].full_name(), spec.full_name());
+        assert_eq!(cached.specs[0].source, spec.source);
+        assert_eq!(cached.specs[0].pushed_at, spec.pushed_at);
+
+        // Any configuration change invalidates the cache
+        config.github.include_forks = !config.github.include_forks;
+        assert!(load_cached_specs(&db, &config).is_none());
+
+        // As does disabling it
+        let mut config = Config::default();
+        config.advanced.cache_duration = "0".to_string();
+        assert!(load_cached_specs(&db, &config).is_none());
+    }
+
+    #[test]
+    fn test_parse_repo_list() {
+        let input = "\
+# repositories to sync
+MKSG/reposentry
+
+  acme/api\tAPI server\tpublic\t2024-01-01
+acme/web  # frontend
+#acme/old
+";
+        assert_eq!(
+            parse_repo_list(input),
+            vec!["MKSG/reposentry", "acme/api", "acme/web"]
+        );
+    }
+
+    #[test]
+    fn test_find_spec() {
+        let spec = |owner: &str, name: &str| RepoSpec {
+            owner: owner.to_string(),
+            clone_url: format!("git@github.com:{}/{}.git", owner, name),
+            local_path: PathBuf::from("/tmp").join(owner).join(name),
+            provider: "github".to_string(),
+            html_url: Some(format!("https://github.com/{}/{}", owner, name)),
+            ..test_spec(name)
+        };
+        let reposentry = spec("MKSG", "reposentry");
+        let specs = vec![
+            reposentry.clone(),
+            reposentry.wiki_spec(),
+            spec("acme", "api"),
+            spec("other", "api"),
+        ];
+
+        assert_eq!(find_spec(&specs, "reposentry").unwrap().owner, "MKSG");
+        assert_eq!(find_spec(&specs, "mksg/REPOSENTRY").unwrap().owner, "MKSG");
+        assert_eq!(find_spec(&specs, "acme/api").unwrap().owner, "acme");
+
+        let wiki = find_spec(&specs, "reposentry.wiki").unwrap();
+        assert!(wiki.is_wiki);
+        assert_eq!(
+            wiki.html_url.as_deref(),
+            Some("https://github.com/MKSG/reposentry/wiki")
+        );
+
+        let err = find_spec(&specs, "api").unwrap_err().to_string();
+        assert!(err.contains("acme/api, other/api"));
+        assert!(find_spec(&specs, "missing").is_err());
+    }
+
+    #[test]
+    fn test_repo_source_roundtrip() {
+        for source in [
+            RepoSource::Owned,
+            RepoSource::Org("acme".to_string()),
+            RepoSource::Starred,
+            RepoSource::Fork,
+            RepoSource::Gist,
+        ] {
+            assert_eq!(RepoSource::parse(&source.to_string()), Some(source));
+        }
+        assert_eq!(RepoSource::parse("bogus"), None);
+    }
+
+    #[test]
+    fn test_repo_spec_serialize() {
+        let spec = RepoSpec {
+            owner: "acme".to_string(),
+            clone_url: "git@github.com:acme/repo.git".to_string(),
+            local_path: PathBuf::from("/dev/acme/repo"),
+            default_branch: Some("main".to_string()),
+            provider: "github".to_string(),
+            source: RepoSource::Org("acme".to_string()),
+            topics: vec!["infra".to_string()],
+            ..test_spec("repo")
+        };
+
+        let json = serde_json::to_value(&spec).unwrap();
+        assert_eq!(json["local_path"], "/dev/acme/repo");
+        assert_eq!(json["clone_method"], "ssh");
+        assert_eq!(json["source"], "org:acme");
+        assert_eq!(json["topics"][0], "infra");
+    }
+
     #[test]
     fn test_clone_method_default() {
         assert_eq!(CloneMethod::default(), CloneMethod::Ssh);
@@ -0,0 +1,461 @@
+//! GitLab repository discovery (`providers: [gitlab]`)
+//!
+//! Projects are listed from the REST API (`/api/v4/projects`) of gitlab.com or
+//! a self-hosted instance (`gitlab.url`), authenticated with a personal access
+//! token from `GITLAB_TOKEN`. Every project the token's user is a member of is
+//! discovered, subject to the same ignore, owner, exclude-pattern and fork
+//! filters as GitHub repositories.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::{sort_specs, CloneMethod, Discovery, RepoSource, RepoSpec};
+use crate::github::{matches_exclusion_pattern, FilterRejection};
+use crate::Config;
+
+/// Environment variable holding the GitLab personal access token
+pub const GITLAB_TOKEN_ENV: &str = "GITLAB_TOKEN";
+
+/// Projects requested per page (the API maximum)
+const PER_PAGE: u32 = 100;
+
+/// A project as returned by `/api/v4/projects`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Project {
+    pub id: u64,
+    /// URL slug of the project (used as the repository name)
+    pub path: String,
+    /// `group/subgroup/project`
+    pub path_with_namespace: String,
+    pub namespace: Namespace,
+    pub ssh_url_to_repo: Option<String>,
+    pub http_url_to_repo: Option<String>,
+    pub web_url: Option<String>,
+    pub description: Option<String>,
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    /// Set only for forks
+    pub forked_from_project: Option<ForkedFrom>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Only returned to members with at least Reporter access
+    pub statistics: Option<Statistics>,
+}
+
+/// The user or group a project belongs to
+#[derive(Debug, Clone, Deserialize)]
+pub struct Namespace {
+    /// Full path, including parent groups (`group/subgroup`)
+    pub full_path: String,
+    /// "user" or "group"
+    pub kind: String,
+}
+
+/// The project a fork was created from
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForkedFrom {
+    pub ssh_url_to_repo: Option<String>,
+    pub http_url_to_repo: Option<String>,
+    pub default_branch: Option<String>,
+}
+
+/// Project storage statistics
+#[derive(Debug, Clone, Deserialize)]
+pub struct Statistics {
+    /// Git repository size in bytes
+    pub repository_size: u64,
+}
+
+/// Explanation of an empty GitLab discovery result
+#[derive(Debug, Clone)]
+pub struct GitLabDiagnosis {
+    /// Instance the projects were listed from (`gitlab.url`)
+    pub url: String,
+    /// Projects listed for the included owners, before filtering
+    pub fetched: usize,
+    /// Number of projects excluded by each filter (non-zero only)
+    pub excluded: Vec<(FilterRejection, usize)>,
+    /// Owner filter in effect (`github.only_owners`)
+    pub only_owners: Vec<String>,
+}
+
+impl GitLabDiagnosis {
+    /// The most likely reason no projects were found
+    pub fn likely_cause(&self) -> String {
+        if self.fetched > 0 {
+            format!("Filters excluded all {} projects", self.fetched)
+        } else if !self.only_owners.is_empty() {
+            format!(
+                "No projects are visible for the owner filter ({}); check the group names",
+                self.only_owners.join(", ")
+            )
+        } else {
+            format!(
+                "The {} user isn't a member of any project on {}; check the token and gitlab.url",
+                GITLAB_TOKEN_ENV, self.url
+            )
+        }
+    }
+}
+
+/// GitLab repository discovery implementation
+pub struct GitLabDiscovery {
+    http: reqwest::Client,
+    token: Option<String>,
+    config: Arc<Config>,
+}
+
+impl GitLabDiscovery {
+    /// Create a new GitLab discovery instance, reading the token from `GITLAB_TOKEN`
+    pub fn new(config: Config) -> Result<Self> {
+        let token = std::env::var(GITLAB_TOKEN_ENV)
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("reposentry/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create GitLab HTTP client")?;
+
+        Ok(Self {
+            http,
+            token,
+            config: Arc::new(config),
+        })
+    }
+
+    /// The personal access token, or an error explaining how to set one
+    fn token(&self) -> Result<&str> {
+        self.token.as_deref().ok_or_else(|| {
+            anyhow!(
+                "No GitLab token: set {} to a personal access token with read_api scope",
+                GITLAB_TOKEN_ENV
+            )
+        })
+    }
+
+    /// URL of a REST API endpoint (`path` without the leading slash)
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/v4/{}",
+            self.config.gitlab.url.trim_end_matches('/'),
+            path
+        )
+    }
+
+    /// Username of the token's user, which also proves the token works
+    pub async fn username(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct User {
+            username: String,
+        }
+
+        let url = self.api_url("user");
+        let user: User = self
+            .http
+            .get(url.as_str())
+            .header("PRIVATE-TOKEN", self.token()?)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach GitLab at {}", url))?
+            .error_for_status()
+            .context("GitLab API request failed")?
+            .json()
+            .await
+            .context("Failed to parse GitLab user")?;
+        Ok(user.username)
+    }
+
+    /// Fetch every project the token's user is a member of
+    async fn list_projects(&self) -> Result<Vec<Project>> {
+        let token = self.token()?;
+        let url = self.api_url("projects");
+
+        let mut projects = Vec::new();
+        let mut page = 1;
+        loop {
+            let response = self
+                .http
+                .get(url.as_str())
+                .header("PRIVATE-TOKEN", token)
+                .query(&[
+                    ("membership", "true"),
+                    ("statistics", "true"),
+                    ("order_by", "id"),
+                    ("sort", "asc"),
+                ])
+                .query(&[("per_page", PER_PAGE), ("page", page)])
+                .send()
+                .await
+                .with_context(|| format!("Failed to reach GitLab at {}", url))?
+                .error_for_status()
+                .context("GitLab API request failed")?;
+
+            // An empty or missing header marks the last page
+            let next_page = response
+                .headers()
+                .get("x-next-page")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u32>().ok());
+
+            let batch: Vec<Project> = response
+                .json()
+                .await
+                .context("Failed to parse GitLab projects")?;
+            tracing::debug!("Fetched {} GitLab projects (page {})", batch.len(), page);
+            projects.extend(batch);
+
+            match next_page {
+                Some(next) => page = next,
+                None => break,
+            }
+        }
+
+        Ok(projects)
+    }
+
+    /// Check whether a project passes the owner, ignore, exclude-pattern and fork filters
+    fn is_included(&self, project: &Project) -> bool {
+        self.is_owner_included(project) && self.rejection(project).is_none()
+    }
+
+    /// Check a project against `github.only_owners`
+    ///
+    /// The filter matches the top-level group, so nested groups come along.
+    fn is_owner_included(&self, project: &Project) -> bool {
+        let top_level = project
+            .namespace
+            .full_path
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        self.config.github.is_owner_included(top_level)
+    }
+
+    /// The first filter that excludes a project, if any
+    fn rejection(&self, project: &Project) -> Option<FilterRejection> {
+        let github = &self.config.github;
+        if github.ignored_repos.contains(&project.path_with_namespace) {
+            Some(FilterRejection::Ignored)
+        } else if matches_exclusion_pattern(&project.path, &github.exclude_patterns) {
+            Some(FilterRejection::Pattern)
+        } else if project.forked_from_project.is_some() && !github.include_forks {
+            Some(FilterRejection::Fork)
+        } else {
+            None
+        }
+    }
+
+    /// Every project of the included owners, with the filter excluding it (if any)
+    pub async fn filter_projects(&self) -> Result<Vec<(Project, Option<FilterRejection>)>> {
+        let projects = self.list_projects().await?;
+        Ok(projects
+            .into_iter()
+            .filter(|project| self.is_owner_included(project))
+            .map(|project| {
+                let rejection = self.rejection(&project);
+                (project, rejection)
+            })
+            .collect())
+    }
+
+    /// Explain why discovery found no projects
+    pub async fn diagnose(&self) -> Result<GitLabDiagnosis> {
+        let projects = self.filter_projects().await?;
+
+        Ok(GitLabDiagnosis {
+            url: self.config.gitlab.url.clone(),
+            fetched: projects.len(),
+            excluded: FilterRejection::ALL
+                .into_iter()
+                .map(|rejection| {
+                    let count = projects
+                        .iter()
+                        .filter(|(_, r)| *r == Some(rejection))
+                        .count();
+                    (rejection, count)
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            only_owners: self.config.github.only_owners.clone(),
+        })
+    }
+
+    /// Convert a GitLab project to our RepoSpec
+    fn project_to_spec(&self, project: &Project) -> RepoSpec {
+        let owner = project.namespace.full_path.clone();
+        let local_path = self
+            .config
+            .repo_local_path(&owner, &project.path, &project.topics);
+
+        let (clone_url, clone_url_alt, clone_method) =
+            match (&project.ssh_url_to_repo, &project.http_url_to_repo) {
+                (Some(ssh), https) => (ssh.clone(), https.clone(), CloneMethod::Ssh),
+                (None, Some(https)) => (https.clone(), None, CloneMethod::Https),
+                (None, None) => (
+                    format!(
+                        "{}/{}.git",
+                        self.config.gitlab.url, project.path_with_namespace
+                    ),
+                    None,
+                    CloneMethod::Https,
+                ),
+            };
+
+        let parent = project.forked_from_project.as_ref();
+        let upstream_url = parent.and_then(|parent| match clone_method {
+            CloneMethod::Ssh => parent.ssh_url_to_repo.clone(),
+            CloneMethod::Https => parent.http_url_to_repo.clone(),
+        });
+
+        let source = if parent.is_some() {
+            RepoSource::Fork
+        } else if project.namespace.kind == "user" {
+            RepoSource::Owned
+        } else {
+            RepoSource::Org(owner.clone())
+        };
+
+        RepoSpec {
+            name: project.path.clone(),
+            owner,
+            clone_url,
+            clone_url_alt,
+            clone_method,
+            local_path,
+            is_fork: parent.is_some(),
+            is_archived: project.archived,
+            size_bytes: project.statistics.as_ref().map(|s| s.repository_size),
+            default_branch: project.default_branch.clone(),
+            provider: "gitlab".to_string(),
+            source,
+            is_wiki: false,
+            topics: project.topics.clone(),
+            pushed_at: project.last_activity_at,
+            upstream_url,
+            upstream_branch: parent.and_then(|parent| parent.default_branch.clone()),
+            html_url: project.web_url.clone(),
+            description: project.description.clone(),
+            provider_id: Some(project.id),
+        }
+    }
+}
+
+#[async_trait]
+impl Discovery for GitLabDiscovery {
+    async fn discover(&self) -> Result<Vec<RepoSpec>> {
+        let projects = self.list_projects().await?;
+
+        let mut specs: Vec<RepoSpec> = projects
+            .iter()
+            .filter(|project| self.is_included(project))
+            .map(|project| self.project_to_spec(project))
+            .collect();
+
+        sort_specs(&mut specs);
+        Ok(specs)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    async fn is_available(&self) -> bool {
+        self.token.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT_JSON: &str = r#"{
+        "id": 42,
+        "path": "api",
+        "path_with_namespace": "acme/backend/api",
+        "namespace": {"full_path": "acme/backend", "kind": "group"},
+        "ssh_url_to_repo": "git@gitlab.example.com:acme/backend/api.git",
+        "http_url_to_repo": "https://gitlab.example.com/acme/backend/api.git",
+        "web_url": "https://gitlab.example.com/acme/backend/api",
+        "default_branch": "main",
+        "archived": false,
+        "last_activity_at": "2026-03-01T12:00:00.000Z",
+        "topics": ["rust"],
+        "statistics": {"repository_size": 2048}
+    }"#;
+
+    fn discovery(config: Config) -> GitLabDiscovery {
+        GitLabDiscovery {
+            http: reqwest::Client::new(),
+            token: None,
+            config: Arc::new(config),
+        }
+    }
+
+    #[test]
+    fn test_project_to_spec() {
+        let project: Project = serde_json::from_str(PROJECT_JSON).unwrap();
+
+        let spec = discovery(Config::default()).project_to_spec(&project);
+        assert_eq!(spec.full_name(), "acme/backend/api");
+        assert_eq!(
+            spec.clone_url,
+            "git@gitlab.example.com:acme/backend/api.git"
+        );
+        assert_eq!(
+            spec.clone_url_alt.as_deref(),
+            Some("https://gitlab.example.com/acme/backend/api.git")
+        );
+        assert_eq!(spec.default_branch.as_deref(), Some("main"));
+        assert_eq!(spec.size_bytes, Some(2048));
+        assert_eq!(spec.provider, "gitlab");
+        assert_eq!(spec.source, RepoSource::Org("acme/backend".to_string()));
+        assert!(!spec.is_fork);
+        assert!(spec.pushed_at.is_some());
+        assert_eq!(spec.provider_id, Some(42));
+    }
+
+    #[test]
+    fn test_project_filters() {
+        let mut config = Config::default();
+        config.github.exclude_patterns = vec!["test-*".to_string()];
+        config.github.include_forks = false;
+        let discovery = discovery(config);
+
+        let project: Project = serde_json::from_str(PROJECT_JSON).unwrap();
+        assert!(discovery.is_included(&project));
+
+        let mut excluded = project.clone();
+        excluded.path = "test-api".to_string();
+        assert!(!discovery.is_included(&excluded));
+        assert_eq!(
+            discovery.rejection(&excluded),
+            Some(FilterRejection::Pattern)
+        );
+
+        let mut fork = project.clone();
+        fork.forked_from_project = Some(ForkedFrom {
+            ssh_url_to_repo: None,
+            http_url_to_repo: None,
+            default_branch: None,
+        });
+        assert!(!discovery.is_included(&fork));
+        assert_eq!(discovery.rejection(&fork), Some(FilterRejection::Fork));
+    }
+
+    #[test]
+    fn test_project_only_owners() {
+        let mut config = Config::default();
+        config.github.only_owners = vec!["acme".to_string()];
+        let project: Project = serde_json::from_str(PROJECT_JSON).unwrap();
+        assert!(discovery(config.clone()).is_included(&project));
+
+        config.github.only_owners = vec!["other".to_string()];
+        assert!(!discovery(config).is_included(&project));
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Context, Result};
+use octocrab::models::gists::Gist;
 use octocrab::models::Repository;
 use octocrab::Octocrab;
 use std::env;
@@ -12,26 +13,45 @@ use crate::config::Config;
 pub struct GitHubClient {
     client: Octocrab,
     username: String,
+    token_scopes: Option<String>,
+    scope_warnings: Vec<String>,
 }
 
+/// Keyring service the GitHub token is stored under
+pub const KEYRING_SERVICE: &str = "reposentry";
+
+/// Keyring user (account) the GitHub token is stored under
+pub const KEYRING_USER: &str = "github";
+
 /// GitHub authentication strategies
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthStrategy {
     /// Use GitHub CLI authentication
     GitHubCLI,
     /// Use environment variable token
     EnvironmentToken,
+    /// Use a token read from `github.token_file` or `GITHUB_TOKEN_FILE`
+    TokenFile,
+    /// Use a token stored in the system keyring
+    Keyring,
 }
 
+/// Strategies `auth_method: auto` tries after the token file, in order
+const AUTO_STRATEGIES: [AuthStrategy; 3] = [
+    AuthStrategy::Keyring,
+    AuthStrategy::EnvironmentToken,
+    AuthStrategy::GitHubCLI,
+];
+
 impl GitHubClient {
     /// Create a new GitHub client with automatic authentication
     pub async fn new(config: &Config) -> Result<Self> {
-        let (auth_strategy, token) = Self::detect_authentication(config)?;
+        let (auth_strategy, token) = Self::authenticate(config).await?;
 
         info!("Using authentication strategy: {:?}", auth_strategy);
 
         let client = Octocrab::builder()
-            .personal_token(token)
+            .personal_token(token.clone())
             .build()
             .context("Failed to create GitHub client")?;
 
@@ -50,26 +70,93 @@ impl GitHubClient {
 
         info!("Authenticated as GitHub user: {}", username);
 
-        Ok(Self { client, username })
+        let (token_scopes, scope_warnings) =
+            Self::check_token_scopes(&client, &token, config).await;
+        for warning in &scope_warnings {
+            warn!("{}", warning);
+        }
+
+        Ok(Self {
+            client,
+            username,
+            token_scopes,
+            scope_warnings,
+        })
+    }
+
+    /// Inspect the token's granted scopes and return them with any permission
+    /// warnings
+    ///
+    /// A token without the right scopes still authenticates, but listing
+    /// repositories silently returns fewer results.
+    async fn check_token_scopes(
+        client: &Octocrab,
+        token: &str,
+        config: &Config,
+    ) -> (Option<String>, Vec<String>) {
+        let scopes_header = match client._get("/user").await {
+            Ok(response) => response
+                .headers()
+                .get("x-oauth-scopes")
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string()),
+            Err(e) => {
+                debug!("Could not inspect token scopes: {}", e);
+                return (None, Vec::new());
+            }
+        };
+
+        let warnings = token_scope_warnings(
+            token,
+            scopes_header.as_deref(),
+            config.github.include_organizations,
+        );
+        (scopes_header, warnings)
+    }
+
+    /// Obtain the GitHub token per `github.auth_method`, without contacting GitHub
+    pub async fn resolve_token(config: &Config) -> Result<String> {
+        Self::authenticate(config).await.map(|(_, token)| token)
+    }
+
+    /// Detect authentication on a blocking thread (it may run `gh` or query the keyring)
+    async fn authenticate(config: &Config) -> Result<(AuthStrategy, String)> {
+        let config = config.clone();
+        tokio::task::spawn_blocking(move || Self::detect_authentication(&config))
+            .await
+            .context("GitHub authentication lookup panicked")?
     }
 
     /// Detect and obtain GitHub authentication
     fn detect_authentication(config: &Config) -> Result<(AuthStrategy, String)> {
+        // An explicitly configured token file wins, and must be readable
+        if matches!(config.github.auth_method.as_str(), "auto" | "token") {
+            if let Some(path) = Self::token_file_path(config) {
+                let token = Self::try_token_file(&path)?;
+                return Ok((AuthStrategy::TokenFile, token));
+            }
+        }
+
         match config.github.auth_method.as_str() {
-            "auto" => {
-                // Try GitHub CLI first, then environment token
-                if let Ok(token) = Self::try_github_cli() {
-                    Ok((AuthStrategy::GitHubCLI, token))
-                } else if let Ok(token) = Self::try_environment_token() {
-                    Ok((AuthStrategy::EnvironmentToken, token))
-                } else {
-                    Err(anyhow!(
-                        "No GitHub authentication found. Please either:\n\
-                         1. Install and authenticate GitHub CLI: gh auth login\n\
-                         2. Set GITHUB_TOKEN environment variable\n\
-                         3. Run: reposentry auth setup"
-                    ))
-                }
+            "auto" => first_available_token(|strategy| match strategy {
+                AuthStrategy::Keyring => Self::try_keyring(),
+                AuthStrategy::EnvironmentToken => Self::try_environment_token(),
+                AuthStrategy::GitHubCLI => Self::try_github_cli(),
+                AuthStrategy::TokenFile => Err(anyhow!("No token file configured")),
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No GitHub authentication found. Please either:\n\
+                     1. Set GITHUB_TOKEN environment variable\n\
+                     2. Install and authenticate GitHub CLI: gh auth login\n\
+                     3. Run: reposentry auth setup"
+                )
+            }),
+            "keyring" => {
+                let token = Self::try_keyring().context(
+                    "No GitHub token in the system keyring (service \"reposentry\", user \"github\")",
+                )?;
+                Ok((AuthStrategy::Keyring, token))
             }
             "gh_cli" => {
                 let token = Self::try_github_cli()
@@ -132,6 +219,53 @@ impl GitHubClient {
         Ok(token)
     }
 
+    /// Token file from `github.token_file`, or else `GITHUB_TOKEN_FILE`
+    fn token_file_path(config: &Config) -> Option<String> {
+        config
+            .github
+            .token_file
+            .clone()
+            .or_else(|| env::var("GITHUB_TOKEN_FILE").ok())
+            .filter(|path| !path.trim().is_empty())
+    }
+
+    /// Read a token from a file (`~` and variables expanded, whitespace trimmed)
+    fn try_token_file(path: &str) -> Result<String> {
+        debug!("Attempting token file authentication");
+
+        let path = shellexpand::full(path)
+            .with_context(|| format!("Failed to expand token file path: {}", path))?;
+        let token = std::fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read GitHub token file: {}", path))?
+            .trim()
+            .to_string();
+
+        if token.is_empty() {
+            return Err(anyhow!("GitHub token file is empty: {}", path));
+        }
+
+        debug!("Successfully read token from {}", path);
+        Ok(token)
+    }
+
+    /// Read the token stored in the system keyring
+    fn try_keyring() -> Result<String> {
+        debug!("Attempting keyring authentication");
+
+        let token = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .and_then(|entry| entry.get_password())
+            .context("Failed to read the GitHub token from the system keyring")?
+            .trim()
+            .to_string();
+
+        if token.is_empty() {
+            return Err(anyhow!("GitHub token in the system keyring is empty"));
+        }
+
+        debug!("Successfully read token from the system keyring");
+        Ok(token)
+    }
+
     /// Try to get token from environment variable
     fn try_environment_token() -> Result<String> {
         debug!("Attempting environment variable authentication");
@@ -143,8 +277,12 @@ impl GitHubClient {
             return Err(anyhow!("GITHUB_TOKEN is empty"));
         }
 
-        if !token.starts_with("ghp_") && !token.starts_with("gho_") && !token.starts_with("ghs_") {
-            warn!("GITHUB_TOKEN doesn't look like a valid GitHub token (should start with ghp_, gho_, or ghs_)");
+        if !token.starts_with("ghp_")
+            && !token.starts_with("gho_")
+            && !token.starts_with("ghs_")
+            && !token.starts_with(FINE_GRAINED_TOKEN_PREFIX)
+        {
+            warn!("GITHUB_TOKEN doesn't look like a valid GitHub token (should start with ghp_, gho_, ghs_, or github_pat_)");
         }
 
         debug!("Successfully found GITHUB_TOKEN environment variable");
@@ -165,6 +303,26 @@ impl GitHubClient {
         &self.username
     }
 
+    /// Scopes granted to the token (None if GitHub doesn't report them,
+    /// as for fine-grained tokens)
+    pub fn token_scopes(&self) -> Option<&str> {
+        self.token_scopes.as_deref()
+    }
+
+    /// Warnings about missing token permissions detected at authentication
+    pub fn scope_warnings(&self) -> &[String] {
+        &self.scope_warnings
+    }
+
+    /// Fetch a single repository by owner and name
+    pub async fn get_repository(&self, owner: &str, name: &str) -> Result<Repository> {
+        self.client
+            .repos(owner, name)
+            .get()
+            .await
+            .with_context(|| format!("Failed to fetch repository {}/{}", owner, name))
+    }
+
     /// List all repositories for the authenticated user
     pub async fn list_user_repositories(&self) -> Result<Vec<Repository>> {
         debug!("Fetching user repositories for: {}", self.username);
@@ -202,6 +360,37 @@ impl GitHubClient {
         Ok(repositories)
     }
 
+    /// List the authenticated user's gists
+    pub async fn list_user_gists(&self) -> Result<Vec<Gist>> {
+        debug!("Fetching gists for user: {}", self.username);
+
+        let mut gists = Vec::new();
+        let mut page = 1u32;
+
+        loop {
+            let page_gists = self
+                .client
+                .gists()
+                .list_all_gists()
+                .per_page(100)
+                .page(page)
+                .send()
+                .await
+                .with_context(|| format!("Failed to fetch gists page {}", page))?;
+
+            let items = page_gists.items;
+            if items.is_empty() {
+                break;
+            }
+
+            gists.extend(items);
+            page += 1;
+        }
+
+        info!("Found {} gists", gists.len());
+        Ok(gists)
+    }
+
     /// List all organizations the user is a member of
     pub async fn list_user_organizations(&self) -> Result<Vec<String>> {
         debug!("Fetching organizations for user: {}", self.username);
@@ -276,17 +465,47 @@ impl GitHubClient {
 
     /// Get all repositories (user + organizations) with filtering applied
     pub async fn get_all_repositories(&self, config: &Config) -> Result<Vec<Repository>> {
+        let all_repositories = self.list_all_repositories(config).await?;
+
+        info!(
+            "Total repositories before filtering: {}",
+            all_repositories.len()
+        );
+
+        // Apply filters
+        let mut filtered_repositories = self.apply_filters(all_repositories, config);
+        sort_repositories(&mut filtered_repositories);
+
+        if config.github.tracks_upstream() {
+            self.load_fork_parents(&mut filtered_repositories).await;
+        }
+
+        info!(
+            "Repositories after filtering: {}",
+            filtered_repositories.len()
+        );
+
+        Ok(filtered_repositories)
+    }
+
+    /// Get all repositories of the included owners, before the configured
+    /// filters (ignored names, patterns, forks, age, size) are applied
+    pub async fn list_all_repositories(&self, config: &Config) -> Result<Vec<Repository>> {
         let mut all_repositories = Vec::new();
 
         // Get user repositories
         let user_repos = self.list_user_repositories().await?;
         all_repositories.extend(user_repos);
 
-        // Get organization repositories if enabled
-        if config.github.include_organizations {
+        // Get organization repositories if enabled (or explicitly requested),
+        // enumerating only the organizations that pass the owner filter
+        if config.github.include_organizations || !config.github.only_owners.is_empty() {
             let organizations = self.list_user_organizations().await?;
 
-            for org in organizations {
+            for org in organizations
+                .into_iter()
+                .filter(|org| config.github.is_owner_included(org))
+            {
                 match self.list_organization_repositories(&org).await {
                     Ok(org_repos) => {
                         all_repositories.extend(org_repos);
@@ -302,84 +521,318 @@ impl GitHubClient {
             }
         }
 
-        info!(
-            "Total repositories before filtering: {}",
-            all_repositories.len()
-        );
-
-        // Apply filters
-        let filtered_repositories = self.apply_filters(all_repositories, config).await?;
+        // User repositories can include ones owned by others
+        all_repositories.retain(|repo| {
+            let owner = repo.owner.as_ref().map(|owner| owner.login.as_str());
+            config.github.is_owner_included(owner.unwrap_or_default())
+        });
 
-        info!(
-            "Repositories after filtering: {}",
-            filtered_repositories.len()
-        );
+        Ok(all_repositories)
+    }
 
-        Ok(filtered_repositories)
+    /// Gather what is needed to explain why discovery found no repositories
+    pub async fn diagnose_discovery(&self, config: &Config) -> Result<DiscoveryDiagnosis> {
+        let repositories = self.list_all_repositories(config).await?;
+        let fetched = repositories.len();
+        let (_, excluded) = partition_by_filters(repositories, config);
+
+        Ok(DiscoveryDiagnosis {
+            fetched,
+            excluded: FilterRejection::ALL
+                .into_iter()
+                .map(|rejection| {
+                    let count = excluded.iter().filter(|(_, r)| *r == rejection).count();
+                    (rejection, count)
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            include_organizations: config.github.include_organizations,
+            only_owners: config.github.only_owners.clone(),
+            token_scopes: self.token_scopes.clone(),
+            scope_warnings: self.scope_warnings.clone(),
+        })
     }
 
     /// Apply configuration filters to repositories
-    async fn apply_filters(
-        &self,
-        repositories: Vec<Repository>,
-        config: &Config,
-    ) -> Result<Vec<Repository>> {
-        let mut filtered = Vec::new();
+    fn apply_filters(&self, repositories: Vec<Repository>, config: &Config) -> Vec<Repository> {
+        let (included, excluded) = partition_by_filters(repositories, config);
+        for (repo, rejection) in &excluded {
+            debug!(
+                "Excluding repository ({}): {}",
+                rejection.description(),
+                repo.name
+            );
+        }
+        included
+    }
 
-        for repo in repositories {
-            // Skip if matches exclusion patterns
-            if self.matches_exclusion_pattern(&repo.name, &config.github.exclude_patterns) {
-                debug!("Excluding repository due to pattern match: {}", repo.name);
+    /// Fill in the parent repository of each fork
+    ///
+    /// Repository listings don't include `parent`, so each fork is fetched
+    /// individually. Forks whose parent can't be loaded keep syncing from origin.
+    async fn load_fork_parents(&self, repositories: &mut [Repository]) {
+        for repo in repositories
+            .iter_mut()
+            .filter(|repo| repo.fork == Some(true) && repo.parent.is_none())
+        {
+            let Some(owner) = repo.owner.as_ref().map(|owner| owner.login.clone()) else {
                 continue;
-            }
+            };
 
-            // Skip forks if not included
-            if repo.fork == Some(true) && !config.github.include_forks {
-                debug!("Excluding fork repository: {}", repo.name);
-                continue;
+            match self.client.repos(&owner, &repo.name).get().await {
+                Ok(full) => repo.parent = full.parent,
+                Err(e) => warn!(
+                    "Failed to fetch parent of fork {}/{}: {}",
+                    owner, repo.name, e
+                ),
             }
+        }
+    }
+}
 
-            // Check age filter
-            if let Some(updated_at) = repo.updated_at {
-                if config.should_filter_by_age(&updated_at) {
-                    debug!("Excluding repository due to age: {}", repo.name);
-                    continue;
-                }
-            }
+/// Configuration filter that excludes a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterRejection {
+    /// Listed in `github.ignored_repos`
+    Ignored,
+    /// Name matches one of `github.exclude_patterns`
+    Pattern,
+    /// Tagged with `filters.drop_topic`
+    Topic,
+    /// A fork, with `github.include_forks` disabled
+    Fork,
+    /// Not updated within `filters.age.max_age`
+    Age,
+    /// Larger than `filters.size.max_size`
+    Size,
+}
 
-            // Check size filter
-            if let Some(size_kb) = repo.size {
-                let size_bytes = size_kb * 1024; // GitHub API returns size in KB
-                if config.should_filter_by_size(size_bytes as u64) {
-                    debug!(
-                        "Excluding repository due to size ({} KB): {}",
-                        size_kb, repo.name
-                    );
-                    continue;
-                }
+impl FilterRejection {
+    /// Every rejection, in the order filters are applied
+    pub const ALL: [FilterRejection; 6] = [
+        FilterRejection::Ignored,
+        FilterRejection::Pattern,
+        FilterRejection::Topic,
+        FilterRejection::Fork,
+        FilterRejection::Age,
+        FilterRejection::Size,
+    ];
+
+    /// Short description for listings and logs
+    pub fn description(&self) -> &'static str {
+        match self {
+            FilterRejection::Ignored => "ignored (github.ignored_repos)",
+            FilterRejection::Pattern => "name pattern (github.exclude_patterns)",
+            FilterRejection::Topic => "topic (filters.drop_topic)",
+            FilterRejection::Fork => "fork (github.include_forks)",
+            FilterRejection::Age => "age (filters.age.max_age)",
+            FilterRejection::Size => "size (filters.size.max_size)",
+        }
+    }
+}
+
+/// First token `lookup` finds, trying each of `AUTO_STRATEGIES` in order
+fn first_available_token(
+    lookup: impl Fn(AuthStrategy) -> Result<String>,
+) -> Option<(AuthStrategy, String)> {
+    AUTO_STRATEGIES
+        .into_iter()
+        .find_map(|strategy| match lookup(strategy) {
+            Ok(token) => Some((strategy, token)),
+            Err(e) => {
+                debug!("{:?} authentication unavailable: {:#}", strategy, e);
+                None
             }
+        })
+}
+
+/// Explanation of an empty discovery result
+#[derive(Debug, Clone)]
+pub struct DiscoveryDiagnosis {
+    /// Repositories listed for the included owners, before filtering
+    pub fetched: usize,
+    /// Number of repositories excluded by each filter (non-zero only)
+    pub excluded: Vec<(FilterRejection, usize)>,
+    /// Whether organization repositories are listed (`github.include_organizations`)
+    pub include_organizations: bool,
+    /// Owner filter in effect (`github.only_owners` or `--org`)
+    pub only_owners: Vec<String>,
+    /// Scopes the token reports (None if it doesn't report them)
+    pub token_scopes: Option<String>,
+    /// Warnings about missing token permissions
+    pub scope_warnings: Vec<String>,
+}
+
+impl DiscoveryDiagnosis {
+    /// The most likely reason no repositories were found
+    pub fn likely_cause(&self) -> String {
+        if self.fetched > 0 {
+            return format!(
+                "Filters excluded all {} repositories; run 'reposentry filters test' to see why",
+                self.fetched
+            );
+        }
+        if let Some(warning) = self.scope_warnings.first() {
+            return format!("Token permissions: {}", warning);
+        }
+        if !self.only_owners.is_empty() {
+            return format!(
+                "No repositories are visible for the owner filter ({}); check the names",
+                self.only_owners.join(", ")
+            );
+        }
+        if !self.include_organizations {
+            return "The account has no repositories of its own; set \
+                    github.include_organizations to sync organization repositories"
+                .to_string();
+        }
+        "The token can't see any repositories; check its scopes or repository access \
+         (reposentry auth status)"
+            .to_string()
+    }
+}
 
-            filtered.push(repo);
+/// Sort repositories by full name (case-insensitively) and drop duplicates
+///
+/// The API returns repositories in no stable order, and an organization's
+/// repositories can appear in both the user and the organization listing.
+pub fn sort_repositories(repositories: &mut Vec<Repository>) {
+    let key = |repo: &Repository| {
+        repo.full_name
+            .as_deref()
+            .unwrap_or(&repo.name)
+            .to_lowercase()
+    };
+    repositories.sort_by_cached_key(key);
+    repositories.dedup_by(|a, b| key(a) == key(b));
+}
+
+/// Split repositories into those passing the configuration filters and
+/// the excluded ones, each with the first filter that rejected it
+pub fn partition_by_filters(
+    repositories: Vec<Repository>,
+    config: &Config,
+) -> (Vec<Repository>, Vec<(Repository, FilterRejection)>) {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+
+    for repo in repositories {
+        match filter_rejection(&repo, config) {
+            Some(rejection) => excluded.push((repo, rejection)),
+            None => included.push(repo),
         }
+    }
 
-        Ok(filtered)
+    (included, excluded)
+}
+
+/// The filter that excludes a repository (None if it is included)
+fn filter_rejection(repo: &Repository, config: &Config) -> Option<FilterRejection> {
+    // Repositories ignored by full name
+    if let Some(full_name) = &repo.full_name {
+        if config.github.ignored_repos.contains(full_name) {
+            return Some(FilterRejection::Ignored);
+        }
     }
 
-    /// Check if repository name matches any exclusion pattern
-    fn matches_exclusion_pattern(&self, name: &str, patterns: &[String]) -> bool {
-        patterns.iter().any(|pattern| {
-            // Simple glob pattern matching
-            if pattern.contains('*') {
-                let pattern_regex = pattern.replace('.', r"\.").replace('*', ".*");
+    if matches_exclusion_pattern(&repo.name, &config.github.exclude_patterns) {
+        return Some(FilterRejection::Pattern);
+    }
 
-                regex::Regex::new(&format!("^{}$", pattern_regex))
-                    .map(|re| re.is_match(name))
-                    .unwrap_or(false)
-            } else {
-                name == pattern
-            }
-        })
+    // Topics set on GitHub override the remaining filters
+    let topics = repo.topics.as_deref().unwrap_or_default();
+    if config.filters.is_dropped(topics) {
+        return Some(FilterRejection::Topic);
     }
+    if config.filters.is_kept(topics) {
+        return None;
+    }
+
+    if repo.fork == Some(true) && !config.github.include_forks {
+        return Some(FilterRejection::Fork);
+    }
+
+    if let Some(updated_at) = repo.updated_at {
+        if config.should_filter_by_age(&updated_at) {
+            return Some(FilterRejection::Age);
+        }
+    }
+
+    if let Some(size_kb) = repo.size {
+        let size_bytes = size_kb * 1024; // GitHub API returns size in KB
+        if config.should_filter_by_size(size_bytes as u64) {
+            return Some(FilterRejection::Size);
+        }
+    }
+
+    None
+}
+
+/// Check if repository name matches any exclusion pattern
+pub(crate) fn matches_exclusion_pattern(name: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        // Simple glob pattern matching
+        if pattern.contains('*') {
+            let pattern_regex = pattern.replace('.', r"\.").replace('*', ".*");
+
+            regex::Regex::new(&format!("^{}$", pattern_regex))
+                .map(|re| re.is_match(name))
+                .unwrap_or(false)
+        } else {
+            name == pattern
+        }
+    })
+}
+
+/// Prefix of GitHub fine-grained personal access tokens
+const FINE_GRAINED_TOKEN_PREFIX: &str = "github_pat_";
+
+/// Determine permission warnings for a token
+///
+/// `scopes_header` is the `X-OAuth-Scopes` response header, which GitHub only
+/// sends for classic tokens. Fine-grained tokens don't expose their
+/// permissions, so only a general reminder can be given for them.
+fn token_scope_warnings(token: &str, scopes_header: Option<&str>, need_org: bool) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if token.starts_with(FINE_GRAINED_TOKEN_PREFIX) {
+        let mut message = "Using a fine-grained token: repositories it cannot access are silently omitted. \
+             Ensure it has access to all repositories with 'Contents' and 'Metadata' read permissions"
+            .to_string();
+        if need_org {
+            message.push_str(", and is approved by each organization you want to sync");
+        }
+        warnings.push(message);
+        return warnings;
+    }
+
+    let Some(header) = scopes_header else {
+        return warnings;
+    };
+
+    let scopes: Vec<&str> = header
+        .split(',')
+        .map(|scope| scope.trim())
+        .filter(|scope| !scope.is_empty())
+        .collect();
+
+    if !scopes.contains(&"repo") {
+        warnings.push(
+            "Token lacks the 'repo' scope: private repositories will not be listed".to_string(),
+        );
+    }
+
+    let has_org_scope = ["read:org", "write:org", "admin:org"]
+        .iter()
+        .any(|scope| scopes.contains(scope));
+    if need_org && !has_org_scope {
+        warnings.push(
+            "Token lacks the 'read:org' scope: organization repositories may be missing"
+                .to_string(),
+        );
+    }
+
+    warnings
 }
 
 /// Utility functions for GitHub authentication setup
@@ -445,6 +898,10 @@ pub mod auth_setup {
                 println!("✅ Authentication successful");
                 println!("   Username: {}", client.username());
 
+                for warning in client.scope_warnings() {
+                    println!("⚠️  {}", warning);
+                }
+
                 // Test basic API access
                 match client.list_user_organizations().await {
                     Ok(orgs) => {
@@ -486,7 +943,10 @@ mod tests {
                 // Either strategy is valid
                 assert!(matches!(
                     strategy,
-                    AuthStrategy::GitHubCLI | AuthStrategy::EnvironmentToken
+                    AuthStrategy::GitHubCLI
+                        | AuthStrategy::EnvironmentToken
+                        | AuthStrategy::TokenFile
+                        | AuthStrategy::Keyring
                 ));
             }
             Err(e) => {
@@ -501,6 +961,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_auth_from_token_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let token_file = temp_dir.path().join("github_token");
+        std::fs::write(&token_file, "ghp_fromfile\n").unwrap();
+
+        let mut config = Config::default();
+        config.github.token_file = Some(token_file.display().to_string());
+        let (strategy, token) = GitHubClient::detect_authentication(&config).unwrap();
+        assert!(matches!(strategy, AuthStrategy::TokenFile));
+        assert_eq!(token, "ghp_fromfile");
+
+        // A configured file that can't be read is an error, not a fallback
+        config.github.token_file = Some(temp_dir.path().join("missing").display().to_string());
+        assert!(GitHubClient::detect_authentication(&config).is_err());
+
+        std::fs::write(&token_file, "  \n").unwrap();
+        assert!(GitHubClient::try_token_file(&token_file.display().to_string()).is_err());
+    }
+
+    #[test]
+    fn test_auto_auth_precedence() {
+        let available = |strategies: &'static [AuthStrategy]| {
+            move |strategy| {
+                if strategies.contains(&strategy) {
+                    Ok(format!("{:?}", strategy))
+                } else {
+                    Err(anyhow!("unavailable"))
+                }
+            }
+        };
+
+        // GITHUB_TOKEN wins over the gh CLI
+        let (strategy, token) = first_available_token(available(&[
+            AuthStrategy::EnvironmentToken,
+            AuthStrategy::GitHubCLI,
+        ]))
+        .unwrap();
+        assert_eq!(strategy, AuthStrategy::EnvironmentToken);
+        assert_eq!(token, "EnvironmentToken");
+
+        // The keyring wins over both
+        let (strategy, _) = first_available_token(available(&AUTO_STRATEGIES)).unwrap();
+        assert_eq!(strategy, AuthStrategy::Keyring);
+
+        let (strategy, _) = first_available_token(available(&[AuthStrategy::GitHubCLI])).unwrap();
+        assert_eq!(strategy, AuthStrategy::GitHubCLI);
+        assert!(first_available_token(available(&[])).is_none());
+    }
+
     #[test]
     fn test_auth_strategy_env_token() {
         env::set_var("TEST_GITHUB_TOKEN", "test_token");
@@ -513,6 +1023,35 @@ mod tests {
         env::remove_var("TEST_GITHUB_TOKEN");
     }
 
+    #[test]
+    fn test_token_scope_warnings_classic() {
+        // Full scopes: no warnings
+        let warnings = token_scope_warnings("ghp_abc", Some("repo, read:org, gist"), true);
+        assert!(warnings.is_empty());
+
+        // admin:org implies read:org
+        let warnings = token_scope_warnings("ghp_abc", Some("repo, admin:org"), true);
+        assert!(warnings.is_empty());
+
+        // Missing both scopes
+        let warnings = token_scope_warnings("ghp_abc", Some("public_repo"), true);
+        assert_eq!(warnings.len(), 2);
+
+        // read:org not needed when organizations are disabled
+        let warnings = token_scope_warnings("ghp_abc", Some("repo"), false);
+        assert!(warnings.is_empty());
+
+        // Header absent: nothing to check
+        assert!(token_scope_warnings("gho_abc", None, true).is_empty());
+    }
+
+    #[test]
+    fn test_token_scope_warnings_fine_grained() {
+        let warnings = token_scope_warnings("github_pat_abc", None, true);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("fine-grained"));
+    }
+
     #[test]
     fn test_pattern_matching() {
         let patterns = vec!["test-*".to_string(), "archived-*".to_string()];
@@ -541,6 +1080,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sort_repositories() {
+        let repo = |full_name: &str| -> Repository {
+            serde_json::from_value(serde_json::json!({
+                "id": 1,
+                "name": full_name.split('/').nth(1).unwrap(),
+                "full_name": full_name,
+                "url": format!("https://api.github.com/repos/{}", full_name),
+            }))
+            .unwrap()
+        };
+
+        let mut repositories = vec![
+            repo("zeta/api"),
+            repo("acme/Web"),
+            repo("acme/api"),
+            repo("zeta/api"),
+        ];
+        sort_repositories(&mut repositories);
+
+        let names: Vec<_> = repositories
+            .iter()
+            .map(|r| r.full_name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, vec!["acme/api", "acme/Web", "zeta/api"]);
+    }
+
+    #[test]
+    fn test_partition_by_filters() {
+        let tagged = |name: &str, fork: bool, size_kb: u32, topics: &[&str]| -> Repository {
+            serde_json::from_value(serde_json::json!({
+                "id": 1,
+                "name": name,
+                "full_name": format!("owner/{}", name),
+                "url": format!("https://api.github.com/repos/owner/{}", name),
+                "fork": fork,
+                "size": size_kb,
+                "updated_at": chrono::Utc::now().to_rfc3339(),
+                "topics": topics,
+            }))
+            .unwrap()
+        };
+        let repo = |name: &str, fork: bool, size_kb: u32| tagged(name, fork, size_kb, &[]);
+
+        let mut config = Config::default();
+        config.github.exclude_patterns = vec!["test-*".to_string()];
+        config.github.ignored_repos = vec!["owner/ignored".to_string()];
+        config.github.include_forks = false;
+        config.filters.size.max_size = Some("100MB".to_string());
+        config.filters.keep_topic = Some("reposentry-keep".to_string());
+        config.filters.drop_topic = Some("reposentry-drop".to_string());
+
+        let (included, excluded) = partition_by_filters(
+            vec![
+                repo("kept", false, 10),
+                repo("ignored", false, 10),
+                repo("test-repo", false, 10),
+                repo("forked", true, 10),
+                repo("huge", false, 200 * 1024),
+                tagged("kept-fork", true, 200 * 1024, &["reposentry-keep"]),
+                tagged("dropped", false, 10, &["reposentry-drop"]),
+            ],
+            &config,
+        );
+
+        let names: Vec<_> = included.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["kept", "kept-fork"]);
+        let rejections: Vec<_> = excluded
+            .iter()
+            .map(|(r, rejection)| (r.name.as_str(), *rejection))
+            .collect();
+        assert_eq!(
+            rejections,
+            vec![
+                ("ignored", FilterRejection::Ignored),
+                ("test-repo", FilterRejection::Pattern),
+                ("forked", FilterRejection::Fork),
+                ("huge", FilterRejection::Size),
+                ("dropped", FilterRejection::Topic),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discovery_diagnosis_likely_cause() {
+        let diagnosis = DiscoveryDiagnosis {
+            fetched: 3,
+            excluded: vec![(FilterRejection::Age, 3)],
+            include_organizations: false,
+            only_owners: Vec::new(),
+            token_scopes: Some("repo".to_string()),
+            scope_warnings: Vec::new(),
+        };
+        assert!(diagnosis.likely_cause().contains("filters test"));
+
+        let empty = DiscoveryDiagnosis {
+            fetched: 0,
+            excluded: Vec::new(),
+            ..diagnosis
+        };
+        assert!(empty.likely_cause().contains("include_organizations"));
+
+        let owners = DiscoveryDiagnosis {
+            only_owners: vec!["acme".to_string()],
+            ..empty.clone()
+        };
+        assert!(owners.likely_cause().contains("acme"));
+
+        let scopes = DiscoveryDiagnosis {
+            scope_warnings: vec!["Token lacks the 'repo' scope".to_string()],
+            ..owners
+        };
+        assert!(scopes.likely_cause().starts_with("Token permissions"));
+    }
+
     #[test]
     fn test_size_conversion() {
         // Test basic size conversion logic
@@ -3,16 +3,218 @@
 //! This module provides preflight checks to verify the system is properly
 //! configured before running operations.
 
-use crate::{Config, GitHubClient};
-use std::path::Path;
+use crate::discovery::gitlab::GITLAB_TOKEN_ENV;
+use crate::git::GitVersion;
+use crate::{Config, GitHubClient, GitLabDiscovery};
+use anyhow::{anyhow, Result};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// Host contacted by the network connectivity preflight
+pub const GITHUB_HOST: &str = "github.com";
+
+/// How long the connectivity preflight waits before giving up
+const CONNECTIVITY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Free space below which the base directory check warns (1 GB)
+const LOW_DISK_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Where Linux exposes batteries and AC adapters
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/// A provider's host and the port its API is served on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderHost {
+    pub host: String,
+    pub port: u16,
+}
+
+impl std::fmt::Display for ProviderHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// Hosts of the configured providers (GitHub and/or the GitLab instance)
+pub fn provider_hosts(config: &Config) -> Vec<ProviderHost> {
+    let mut hosts = Vec::new();
+    if config.uses_provider("github") {
+        hosts.push(ProviderHost {
+            host: GITHUB_HOST.to_string(),
+            port: 443,
+        });
+    }
+    if config.uses_provider("gitlab") {
+        let url = reqwest::Url::parse(&config.gitlab.url).ok();
+        let host = url.as_ref().and_then(|url| {
+            Some(ProviderHost {
+                host: url.host_str()?.to_string(),
+                port: url.port_or_known_default()?,
+            })
+        });
+        match host {
+            Some(host) => hosts.push(host),
+            None => tracing::warn!("Invalid gitlab.url: {}", config.gitlab.url),
+        }
+    }
+    hosts
+}
+
+/// Check that a provider's host can be resolved and reached on its port
+///
+/// Used before syncing so an offline machine gets a clear message instead of
+/// an API error from deep inside discovery.
+pub async fn check_connectivity(host: &ProviderHost) -> Result<()> {
+    let connect = TcpStream::connect((host.host.as_str(), host.port));
+    match tokio::time::timeout(CONNECTIVITY_TIMEOUT, connect).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(anyhow!("No network connectivity to {}: {}", host, e)),
+        Err(_) => Err(anyhow!(
+            "No network connectivity to {}: timed out after {}s",
+            host,
+            CONNECTIVITY_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// Whether the machine is running on battery power (None if unknown)
+///
+/// Linux reads the kernel's power supply information and macOS asks `pmset`;
+/// on other platforms the power state can't be determined.
+pub async fn on_battery() -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        let output = command_output("pmset", &["-g", "batt"]).await?;
+        parse_pmset_on_battery(&output)
+    } else {
+        on_battery_sysfs(Path::new(POWER_SUPPLY_DIR))
+    }
+}
+
+/// Whether the active network connection is metered (None if unknown)
+///
+/// Asks NetworkManager (`nmcli`), so this is only known on Linux desktops.
+pub async fn on_metered_connection() -> Option<bool> {
+    let output =
+        command_output("nmcli", &["-t", "-f", "GENERAL.METERED", "device", "show"]).await?;
+    parse_nmcli_metered(&output)
+}
+
+/// Run a command and capture its stdout (None if it can't run or fails)
+async fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Power state from a `/sys/class/power_supply`-style directory
+///
+/// On battery if no AC adapter is online and a system battery is
+/// discharging. Device batteries (mice, headsets) are ignored.
+fn on_battery_sysfs(dir: &Path) -> Option<bool> {
+    let read = |path: &Path, name: &str| {
+        std::fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut discharging = false;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let supply = entry.path();
+        match read(&supply, "type").as_str() {
+            "Mains" | "USB" if read(&supply, "online") == "1" => return Some(false),
+            "Battery" if read(&supply, "scope") != "Device" => {
+                discharging |= read(&supply, "status") == "Discharging";
+            }
+            _ => {}
+        }
+    }
+
+    Some(discharging)
+}
+
+/// Power state from `pmset -g batt` output ("Now drawing from 'Battery Power'")
+fn parse_pmset_on_battery(output: &str) -> Option<bool> {
+    let source = output.lines().next()?.split('\'').nth(1)?;
+    Some(source == "Battery Power")
+}
+
+/// Metered state from `nmcli -t -f GENERAL.METERED device show` output
+///
+/// Metered if any device reports it (including NetworkManager's guess);
+/// unknown if no device reports either way.
+fn parse_nmcli_metered(output: &str) -> Option<bool> {
+    let mut known = false;
+    for line in output.lines() {
+        let Some(value) = line.strip_prefix("GENERAL.METERED:") else {
+            continue;
+        };
+        if value.starts_with("yes") {
+            return Some(true);
+        }
+        known |= value.starts_with("no");
+    }
+
+    known.then_some(false)
+}
+
+/// Every distinct executable named `program` on `path` (a PATH-style list),
+/// in lookup order, so the first one is the one that runs
+///
+/// Symlinks to the same binary (e.g. `/bin` and `/usr/bin` on merged-usr
+/// systems) are listed once.
+fn find_on_path(program: &str, path: &OsStr) -> Vec<PathBuf> {
+    let file_name = format!("{}{}", program, std::env::consts::EXE_SUFFIX);
+    let mut seen = Vec::new();
+    let mut found = Vec::new();
+
+    for dir in std::env::split_paths(path) {
+        let candidate = dir.join(&file_name);
+        if !is_executable(&candidate) {
+            continue;
+        }
+        let canonical = candidate
+            .canonicalize()
+            .unwrap_or_else(|_| candidate.clone());
+        if !seen.contains(&canonical) {
+            seen.push(canonical);
+            found.push(candidate);
+        }
+    }
+
+    found
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
 
 /// Result of system health checks
 #[derive(Debug, Clone)]
 pub struct HealthCheck {
     /// Git installation status
     pub git: CheckResult,
+    /// Network connectivity to the configured providers
+    pub network: CheckResult,
     /// GitHub authentication status
     pub github_auth: CheckResult,
+    /// GitLab authentication status
+    pub gitlab_auth: CheckResult,
     /// Base directory status
     pub base_dir: CheckResult,
     /// SSH configuration status (warning only, not required)
@@ -89,8 +291,10 @@ impl HealthCheck {
     /// Run all health checks
     pub async fn run(config: &Config) -> Self {
         Self {
-            git: Self::check_git(),
+            git: Self::check_git(config),
+            network: Self::check_network(config).await,
             github_auth: Self::check_github_auth(config).await,
+            gitlab_auth: Self::check_gitlab_auth(config).await,
             base_dir: Self::check_base_dir(config),
             ssh: Self::check_ssh(),
         }
@@ -98,32 +302,53 @@ impl HealthCheck {
 
     /// Check if all required checks passed (excludes warnings)
     pub fn all_passed(&self) -> bool {
-        self.git.passed && self.github_auth.passed && self.base_dir.passed
+        self.git.passed
+            && self.network.passed
+            && self.github_auth.passed
+            && self.gitlab_auth.passed
+            && self.base_dir.passed
         // SSH is optional, not included in required checks
     }
 
     /// Get list of failed checks (errors only, not warnings)
     pub fn errors(&self) -> Vec<&CheckResult> {
-        [&self.git, &self.github_auth, &self.base_dir, &self.ssh]
-            .into_iter()
-            .filter(|r| !r.passed && !r.is_warning)
-            .collect()
+        [
+            &self.git,
+            &self.network,
+            &self.github_auth,
+            &self.gitlab_auth,
+            &self.base_dir,
+            &self.ssh,
+        ]
+        .into_iter()
+        .filter(|r| !r.passed && !r.is_warning)
+        .collect()
     }
 
     /// Get list of warnings
     pub fn warnings(&self) -> Vec<&CheckResult> {
-        [&self.git, &self.github_auth, &self.base_dir, &self.ssh]
-            .into_iter()
-            .filter(|r| r.is_warning)
-            .collect()
+        [
+            &self.git,
+            &self.network,
+            &self.github_auth,
+            &self.gitlab_auth,
+            &self.base_dir,
+            &self.ssh,
+        ]
+        .into_iter()
+        .filter(|r| r.is_warning)
+        .collect()
     }
 
-    /// Check git installation
-    fn check_git() -> CheckResult {
+    /// Check git installation: version and which binary is used
+    fn check_git(config: &Config) -> CheckResult {
         match std::process::Command::new("git").arg("--version").output() {
             Ok(output) if output.status.success() => {
-                let version = String::from_utf8_lossy(&output.stdout);
-                CheckResult::ok_with_details("Git installed", version.trim().to_string())
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                let binaries = std::env::var_os("PATH")
+                    .map(|path| find_on_path("git", &path))
+                    .unwrap_or_default();
+                Self::git_version_result(&version, &binaries, &config.advanced.min_git_version)
             }
             Ok(_) => CheckResult::error("Git command failed"),
             Err(_) => CheckResult::error_with_details(
@@ -133,9 +358,95 @@ impl HealthCheck {
         }
     }
 
+    /// Judge the installed git version against `min_version`, and warn when
+    /// several git binaries are on PATH
+    fn git_version_result(version: &str, binaries: &[PathBuf], min_version: &str) -> CheckResult {
+        let mut details = vec![version.to_string()];
+        let mut warnings = Vec::new();
+
+        match GitVersion::parse(version) {
+            Some(installed) => {
+                match GitVersion::parse(min_version) {
+                    Some(minimum) if installed < minimum => warnings.push(format!(
+                        "Git {} is older than {} (advanced.min_git_version)",
+                        installed, minimum
+                    )),
+                    Some(_) => {}
+                    None => warnings.push(format!(
+                        "Invalid advanced.min_git_version '{}'",
+                        min_version
+                    )),
+                }
+                details.extend(
+                    installed
+                        .missing_features()
+                        .into_iter()
+                        .map(|feature| format!("Unavailable: {}", feature)),
+                );
+            }
+            None => warnings.push("Could not parse the git version".to_string()),
+        }
+
+        if let [used, others @ ..] = binaries {
+            details.push(format!("Using {}", used.display()));
+            if !others.is_empty() {
+                warnings.push("Multiple git binaries on PATH".to_string());
+                details.extend(
+                    others
+                        .iter()
+                        .map(|other| format!("Shadowed: {}", other.display())),
+                );
+            }
+        }
+
+        if warnings.is_empty() {
+            CheckResult::ok_with_details("Git installed", details.join("\n"))
+        } else {
+            CheckResult::warning_with_details(warnings.join("; "), details.join("\n"))
+        }
+    }
+
+    /// Check network connectivity to every configured provider
+    async fn check_network(config: &Config) -> CheckResult {
+        let hosts = provider_hosts(config);
+        for host in &hosts {
+            if let Err(e) = check_connectivity(host).await {
+                return CheckResult::error_with_details(
+                    format!("No network connectivity to {}", host),
+                    e.to_string(),
+                );
+            }
+        }
+        let hosts: Vec<String> = hosts.iter().map(ToString::to_string).collect();
+        CheckResult::ok_with_details("Network connectivity OK", hosts.join(", "))
+    }
+
     /// Check GitHub authentication
     async fn check_github_auth(config: &Config) -> CheckResult {
-        match GitHubClient::new(config).await {
+        if !config.uses_provider("github") {
+            return CheckResult::ok_with_details("Not checked", "github is not in providers");
+        }
+
+        let timeout = Duration::from_secs(config.advanced.auth_check_timeout);
+        let Ok(result) = tokio::time::timeout(timeout, GitHubClient::new(config)).await else {
+            return CheckResult::warning_with_details(
+                "GitHub auth check timed out — network may be down",
+                format!(
+                    "No response within {}s (advanced.auth_check_timeout)",
+                    timeout.as_secs()
+                ),
+            );
+        };
+
+        match result {
+            Ok(client) if !client.scope_warnings().is_empty() => CheckResult::warning_with_details(
+                "GitHub authentication successful, but token permissions may be insufficient",
+                format!(
+                    "Username: {}\n{}",
+                    client.username(),
+                    client.scope_warnings().join("\n")
+                ),
+            ),
             Ok(client) => CheckResult::ok_with_details(
                 "GitHub authentication successful",
                 format!("Username: {}", client.username()),
@@ -147,13 +458,71 @@ impl HealthCheck {
         }
     }
 
+    /// Check GitLab authentication (the `GITLAB_TOKEN` personal access token)
+    async fn check_gitlab_auth(config: &Config) -> CheckResult {
+        if !config.uses_provider("gitlab") {
+            return CheckResult::ok_with_details("Not checked", "gitlab is not in providers");
+        }
+
+        let discovery = match GitLabDiscovery::new(config.clone()) {
+            Ok(discovery) => discovery,
+            Err(e) => {
+                return CheckResult::error_with_details("GitLab client failed", e.to_string())
+            }
+        };
+        let timeout = Duration::from_secs(config.advanced.auth_check_timeout);
+        let Ok(result) = tokio::time::timeout(timeout, discovery.username()).await else {
+            return CheckResult::warning_with_details(
+                "GitLab auth check timed out — network may be down",
+                format!(
+                    "No response within {}s (advanced.auth_check_timeout)",
+                    timeout.as_secs()
+                ),
+            );
+        };
+
+        match result {
+            Ok(username) => CheckResult::ok_with_details(
+                "GitLab authentication successful",
+                format!("Username: {}", username),
+            ),
+            Err(e) => CheckResult::error_with_details(
+                "GitLab authentication failed",
+                format!(
+                    "{:#}\nSet {} to a personal access token",
+                    e, GITLAB_TOKEN_ENV
+                ),
+            ),
+        }
+    }
+
     /// Check base directory exists
     fn check_base_dir(config: &Config) -> CheckResult {
         match shellexpand::full(&config.base_directory) {
             Ok(expanded) => {
                 let path = Path::new(expanded.as_ref());
                 if path.exists() {
-                    CheckResult::ok_with_details("Base directory exists", expanded.to_string())
+                    match available_space(path) {
+                        // Syncs abort when the disk fills up, so warn ahead of time
+                        Some(free) if free < LOW_DISK_SPACE_BYTES => {
+                            CheckResult::warning_with_details(
+                                "Low disk space in base directory",
+                                format!(
+                                    "{} free in {}; syncs stop when the disk is full",
+                                    format_gb(free),
+                                    expanded
+                                ),
+                            )
+                        }
+                        Some(free) => CheckResult::ok_with_details(
+                            "Base directory exists",
+                            format!("{} ({} free)", expanded, format_gb(free)),
+                        ),
+                        None => CheckResult::ok_with_details(
+                            "Base directory exists",
+                            expanded.to_string(),
+                        ),
+                    }
                 } else {
                     CheckResult::error_with_details(
                         "Base directory does not exist",
@@ -193,20 +562,93 @@ impl HealthCheck {
     }
 
     /// Get all checks as a slice for iteration
-    pub fn all_checks(&self) -> [(&'static str, &CheckResult); 4] {
+    pub fn all_checks(&self) -> [(&'static str, &CheckResult); 6] {
         [
             ("Git Installation", &self.git),
+            ("Network Connectivity", &self.network),
             ("GitHub Authentication", &self.github_auth),
+            ("GitLab Authentication", &self.gitlab_auth),
             ("Base Directory", &self.base_dir),
             ("SSH Configuration", &self.ssh),
         ]
     }
 }
 
+/// Free space available to unprivileged users on the filesystem holding `path`
+#[cfg(unix)]
+fn available_space(path: &Path) -> Option<u64> {
+    let stat = nix::sys::statvfs::statvfs(path).ok()?;
+    Some(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Format a byte count in gigabytes, e.g. "0.4 GB"
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_on_battery_sysfs() {
+        let dir = tempfile::tempdir().unwrap();
+        let supply = |name: &str, files: &[(&str, &str)]| {
+            let path = dir.path().join(name);
+            std::fs::create_dir_all(&path).unwrap();
+            for (file, value) in files {
+                std::fs::write(path.join(file), format!("{}\n", value)).unwrap();
+            }
+        };
+
+        // Desktop without batteries
+        assert_eq!(on_battery_sysfs(dir.path()), Some(false));
+
+        supply("BAT0", &[("type", "Battery"), ("status", "Discharging")]);
+        supply(
+            "hidpp_battery_0",
+            &[
+                ("type", "Battery"),
+                ("scope", "Device"),
+                ("status", "Charging"),
+            ],
+        );
+        assert_eq!(on_battery_sysfs(dir.path()), Some(true));
+
+        supply("AC", &[("type", "Mains"), ("online", "1")]);
+        assert_eq!(on_battery_sysfs(dir.path()), Some(false));
+
+        assert_eq!(on_battery_sysfs(&dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn test_parse_pmset_on_battery() {
+        let battery =
+            "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t85%; discharging\n";
+        let ac = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=1234)\t100%; charged\n";
+        assert_eq!(parse_pmset_on_battery(battery), Some(true));
+        assert_eq!(parse_pmset_on_battery(ac), Some(false));
+        assert_eq!(parse_pmset_on_battery(""), None);
+    }
+
+    #[test]
+    fn test_parse_nmcli_metered() {
+        assert_eq!(
+            parse_nmcli_metered("GENERAL.METERED:no (guessed)\nGENERAL.METERED:unknown\n"),
+            Some(false)
+        );
+        assert_eq!(
+            parse_nmcli_metered("GENERAL.METERED:unknown\nGENERAL.METERED:yes (guessed)\n"),
+            Some(true)
+        );
+        assert_eq!(parse_nmcli_metered("GENERAL.METERED:unknown\n"), None);
+    }
+
     #[test]
     fn test_check_result_ok() {
         let result = CheckResult::ok("Test passed");
@@ -255,12 +697,70 @@ mod tests {
 
     #[test]
     fn test_git_check() {
-        let result = HealthCheck::check_git();
+        let result = HealthCheck::check_git(&Config::default());
         // Git should be installed in dev environment
         assert!(result.passed);
         assert!(result.details.is_some()); // Should have version info
     }
 
+    #[test]
+    fn test_git_version_result() {
+        let result = HealthCheck::git_version_result(
+            "git version 2.39.5",
+            &[PathBuf::from("/usr/bin/git")],
+            "2.22",
+        );
+        assert!(!result.is_warning);
+
+        let result = HealthCheck::git_version_result("git version 2.20.1", &[], "2.22");
+        assert!(result.is_warning);
+        assert!(result
+            .details
+            .unwrap()
+            .contains("Unavailable: git branch --show-current"));
+
+        let result = HealthCheck::git_version_result(
+            "git version 2.39.5",
+            &[
+                PathBuf::from("/usr/local/bin/git"),
+                PathBuf::from("/usr/bin/git"),
+            ],
+            "2.22",
+        );
+        assert!(result.is_warning);
+        assert_eq!(
+            result.details.unwrap(),
+            "git version 2.39.5\nUsing /usr/local/bin/git\nShadowed: /usr/bin/git"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_on_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bin = |name: &str, mode: u32| {
+            let path = dir.path().join(name);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join("git"), "").unwrap();
+            std::fs::set_permissions(path.join("git"), std::fs::Permissions::from_mode(mode))
+                .unwrap();
+            path
+        };
+        let local = bin("local", 0o755);
+        let system = bin("system", 0o755);
+        let not_executable = bin("data", 0o644);
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&system, &link).unwrap();
+
+        let path = std::env::join_paths([&not_executable, &local, &system, &link]).unwrap();
+        assert_eq!(
+            find_on_path("git", &path),
+            vec![local.join("git"), system.join("git")]
+        );
+    }
+
     #[test]
     fn test_check_base_dir_existing() {
         let mut config = Config::default();
@@ -299,7 +799,9 @@ mod tests {
     fn test_all_passed_with_passing_checks() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::warning("SSH warning"), // Warnings don't fail
         };
@@ -310,7 +812,9 @@ mod tests {
     fn test_all_passed_with_failing_git() {
         let health = HealthCheck {
             git: CheckResult::error("Git missing"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::ok("SSH OK"),
         };
@@ -321,7 +825,9 @@ mod tests {
     fn test_all_passed_with_failing_auth() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::error("Auth failed"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::ok("SSH OK"),
         };
@@ -332,7 +838,9 @@ mod tests {
     fn test_all_passed_with_failing_base_dir() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::error("Dir missing"),
             ssh: CheckResult::ok("SSH OK"),
         };
@@ -344,18 +852,71 @@ mod tests {
         // SSH warnings should NOT cause all_passed to fail
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::warning("No SSH keys"),
         };
         assert!(health.all_passed());
     }
 
+    #[test]
+    fn test_all_passed_with_failing_network() {
+        let health = HealthCheck {
+            git: CheckResult::ok("Git OK"),
+            network: CheckResult::error("No network"),
+            github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
+            base_dir: CheckResult::ok("Dir OK"),
+            ssh: CheckResult::ok("SSH OK"),
+        };
+        assert!(!health.all_passed());
+    }
+
+    #[tokio::test]
+    async fn test_check_connectivity_unresolvable_host() {
+        let host = ProviderHost {
+            host: "nonexistent.invalid".to_string(),
+            port: 443,
+        };
+        let err = check_connectivity(&host).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("No network connectivity to nonexistent.invalid:443"));
+    }
+
+    #[test]
+    fn test_provider_hosts() {
+        let mut config = Config {
+            providers: vec!["github".to_string(), "gitlab".to_string()],
+            ..Config::default()
+        };
+        config.gitlab.url = "http://gitlab.local:8080".to_string();
+        let hosts: Vec<String> = provider_hosts(&config)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(hosts, ["github.com:443", "gitlab.local:8080"]);
+
+        config.providers = vec!["gitlab".to_string()];
+        config.gitlab.url = "https://gitlab.example.com".to_string();
+        assert_eq!(
+            provider_hosts(&config),
+            [ProviderHost {
+                host: "gitlab.example.com".to_string(),
+                port: 443,
+            }]
+        );
+    }
+
     #[test]
     fn test_errors_returns_only_errors() {
         let health = HealthCheck {
             git: CheckResult::error("Git error"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::error("Dir error"),
             ssh: CheckResult::warning("SSH warning"),
         };
@@ -369,7 +930,9 @@ mod tests {
     fn test_errors_excludes_warnings() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::warning("SSH warning"),
         };
@@ -381,7 +944,9 @@ mod tests {
     fn test_warnings_returns_only_warnings() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::error("Auth error"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::warning("SSH warning"),
         };
@@ -391,18 +956,22 @@ mod tests {
     }
 
     #[test]
-    fn test_all_checks_returns_all_four() {
+    fn test_all_checks_returns_all_six() {
         let health = HealthCheck {
             git: CheckResult::ok("Git OK"),
+            network: CheckResult::ok("Network OK"),
             github_auth: CheckResult::ok("Auth OK"),
+            gitlab_auth: CheckResult::ok("Auth OK"),
             base_dir: CheckResult::ok("Dir OK"),
             ssh: CheckResult::ok("SSH OK"),
         };
         let checks = health.all_checks();
-        assert_eq!(checks.len(), 4);
+        assert_eq!(checks.len(), 6);
         assert_eq!(checks[0].0, "Git Installation");
-        assert_eq!(checks[1].0, "GitHub Authentication");
-        assert_eq!(checks[2].0, "Base Directory");
-        assert_eq!(checks[3].0, "SSH Configuration");
+        assert_eq!(checks[1].0, "Network Connectivity");
+        assert_eq!(checks[2].0, "GitHub Authentication");
+        assert_eq!(checks[3].0, "GitLab Authentication");
+        assert_eq!(checks[4].0, "Base Directory");
+        assert_eq!(checks[5].0, "SSH Configuration");
     }
 }
@@ -22,15 +22,24 @@ pub mod discovery;
 pub mod git;
 pub mod github;
 pub mod health;
+pub mod lock;
+pub mod manifest;
+pub mod notify;
+pub mod report;
+pub mod scan;
+pub mod service;
 pub mod state;
 pub mod sync;
 pub mod tui;
+mod yaml_edit;
 
 pub use config::Config;
 pub use daemon::{Daemon, DaemonStatus};
-pub use discovery::{CloneMethod, Discovery, GitHubDiscovery, RepoSpec};
-pub use git::{GitClient, RepoState, SyncResult};
+pub use discovery::{
+    CloneMethod, Discovery, GitHubDiscovery, GitLabDiscovery, RepoSource, RepoSpec,
+};
+pub use git::{GitClient, GitRunner, RepoState, SyncResult, SystemGitRunner};
 pub use github::GitHubClient;
 pub use health::HealthCheck;
 pub use state::{EventType, RepoStatus, Severity, StateDb, SyncEvent, SyncEventBuilder};
-pub use sync::{SyncEngine, SyncSummary};
+pub use sync::{RepoStatusView, SyncEngine, SyncSummary};
@@ -0,0 +1,181 @@
+//! Cross-process sync lock
+//!
+//! The daemon, the CLI and the TUI each hold an advisory lock on a file in the
+//! runtime directory while syncing, so two processes never operate on the same
+//! repositories at once. The lock is released when the holder finishes; the
+//! OS also drops it if the holding process crashes, so it can't go stale.
+
+use crate::config::get_pid_file_path;
+use crate::Config;
+use anyhow::{anyhow, Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+/// Sync lock filename (placed next to the PID file in the runtime directory)
+const SYNC_LOCK_FILENAME: &str = "reposentry-sync.lock";
+
+/// How often to retry when waiting for a running sync to finish
+const LOCK_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Get the full sync lock file path
+pub fn get_sync_lock_path() -> PathBuf {
+    PathBuf::from(get_pid_file_path(SYNC_LOCK_FILENAME))
+}
+
+/// Exclusive lock held for the duration of a sync
+///
+/// The lock file contains the PID of the holder. Dropping the guard releases
+/// the lock.
+pub struct SyncLock {
+    #[cfg(unix)]
+    _file: nix::fcntl::Flock<File>,
+    #[cfg(not(unix))]
+    _file: File,
+}
+
+impl SyncLock {
+    /// Acquire the sync lock
+    ///
+    /// If another process holds it, either wait for it to be released
+    /// (`wait`) or fail with a message naming the holder.
+    pub async fn acquire(config: &Config, wait: bool) -> Result<Self> {
+        Self::acquire_at(&get_sync_lock_path(), config, wait).await
+    }
+
+    async fn acquire_at(path: &Path, config: &Config, wait: bool) -> Result<Self> {
+        let mut announced = false;
+
+        loop {
+            if let Some(lock) = Self::try_acquire(path)? {
+                return Ok(lock);
+            }
+
+            let holder = describe_holder(path, config);
+            if !wait {
+                return Err(anyhow!("A sync is already running ({})", holder));
+            }
+
+            if !announced {
+                info!("Waiting for the running sync ({}) to finish...", holder);
+                announced = true;
+            }
+            tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Try to take the lock without blocking (None if it is held elsewhere)
+    fn try_acquire(path: &Path) -> Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create sync lock directory")?;
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open sync lock file: {}", path.display()))?;
+
+        #[cfg(unix)]
+        {
+            use nix::errno::Errno;
+            use nix::fcntl::{Flock, FlockArg};
+
+            match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+                Ok(mut file) => {
+                    record_holder(&mut file)?;
+                    Ok(Some(Self { _file: file }))
+                }
+                Err((_, Errno::EWOULDBLOCK)) => Ok(None),
+                Err((_, e)) => Err(anyhow!(
+                    "Failed to lock sync lock file {}: {}",
+                    path.display(),
+                    e
+                )),
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            // No advisory locking on non-Unix platforms; never blocks
+            let mut file = file;
+            record_holder(&mut file)?;
+            Ok(Some(Self { _file: file }))
+        }
+    }
+}
+
+/// Write the current PID to the lock file
+fn record_holder(file: &mut File) -> Result<()> {
+    file.set_len(0)
+        .context("Failed to truncate sync lock file")?;
+    write!(file, "{}", std::process::id()).context("Failed to write sync lock file")
+}
+
+/// Describe the process holding the lock ("daemon" or its PID)
+fn describe_holder(path: &Path, config: &Config) -> String {
+    let read_pid = |path: &Path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| content.trim().parse::<u32>().ok())
+    };
+
+    let daemon_pid = read_pid(Path::new(&get_pid_file_path(&config.daemon.pid_file)));
+    match read_pid(path) {
+        Some(pid) if Some(pid) == daemon_pid => "daemon".to_string(),
+        Some(pid) => format!("pid {}", pid),
+        None => "another process".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_sync_lock_is_exclusive() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("sync.lock");
+        let config = Config::default();
+
+        let lock = SyncLock::acquire_at(&path, &config, false).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            std::process::id().to_string()
+        );
+
+        let err = SyncLock::acquire_at(&path, &config, false)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("A sync is already running"));
+
+        // Released on drop
+        drop(lock);
+        assert!(SyncLock::acquire_at(&path, &config, false).await.is_ok());
+    }
+
+    #[test]
+    fn test_describe_holder() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("sync.lock");
+        let pid_file = temp_dir.path().join("reposentry.pid");
+
+        let mut config = Config::default();
+        config.daemon.pid_file = pid_file.to_string_lossy().to_string();
+
+        assert_eq!(describe_holder(&path, &config), "another process");
+
+        fs::write(&path, "4242").unwrap();
+        assert_eq!(describe_holder(&path, &config), "pid 4242");
+
+        fs::write(&pid_file, "4242\n").unwrap();
+        assert_eq!(describe_holder(&path, &config), "daemon");
+    }
+}
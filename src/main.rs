@@ -1,15 +1,34 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use tracing::info;
+use anyhow::{bail, Context, Result};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use tracing::{info, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
-use reposentry::config::{get_log_file_path, get_pid_file_path};
-use reposentry::daemon::is_daemon_running;
-use reposentry::github::auth_setup;
-use reposentry::state::{EventType, RepoStatus, Severity, StateDb};
+use reposentry::config::{
+    get_log_file_path, get_pid_file_path, parse_duration_secs, LoggingConfig,
+};
+use reposentry::daemon::{
+    daemon_pid, daemon_stats, daemon_uptime, is_daemon_running, next_sync_in,
+};
+use reposentry::discovery::gitlab::GitLabDiagnosis;
+use reposentry::discovery::{
+    discover_configured, find_spec, load_cached_specs, newest_specs, parse_repo_list,
+    store_cached_specs,
+};
+use reposentry::git::{dry_run_forced, FailureKind, DRY_RUN_ENV};
+use reposentry::github::{auth_setup, partition_by_filters, DiscoveryDiagnosis, FilterRejection};
+use reposentry::health::{check_connectivity, provider_hosts, GITHUB_HOST};
+use reposentry::manifest::Manifest;
+use reposentry::report::{ReportFormat, SyncReport};
+use reposentry::scan::find_unmanaged_repos;
+use reposentry::service::ServiceDefinition;
+use reposentry::state::{
+    parse_tag_filter, parse_time_bound, write_events_csv, EventType, RepoStatus, Severity, StateDb,
+};
 use reposentry::tui;
 use reposentry::{
-    Config, Daemon, Discovery, GitHubClient, GitHubDiscovery, HealthCheck, SyncEngine,
+    Config, Daemon, GitClient, GitHubClient, GitHubDiscovery, GitLabDiscovery, HealthCheck,
+    RepoSpec, SyncEngine,
 };
 
 #[derive(Parser)]
@@ -24,9 +43,13 @@ struct Cli {
     #[arg(short, long)]
     config: Option<std::path::PathBuf>,
 
-    /// Verbose logging
-    #[arg(short, long)]
-    verbose: bool,
+    /// Verbose logging (-v for debug, -vv for trace, including every git command)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Fail on unknown configuration keys instead of warning about them
+    #[arg(long)]
+    strict_config: bool,
 }
 
 #[derive(Subcommand)]
@@ -58,9 +81,61 @@ enum Commands {
         #[arg(long)]
         force: bool,
 
-        /// Sync only specific organization
+        /// Sync only these users/organizations (repeatable; overrides github.only_owners)
         #[arg(long)]
-        org: Option<String>,
+        org: Vec<String>,
+
+        /// Also clone repository wikis (overrides github.include_wikis)
+        #[arg(long)]
+        include_wikis: bool,
+
+        /// Pull never-synced repositories even if first_run_mode is "fetch-only"
+        #[arg(long)]
+        confirm: bool,
+
+        /// Wait for an already running sync (e.g. the daemon's) instead of aborting
+        #[arg(long)]
+        wait: bool,
+
+        /// With --dry-run, print only the aggregate counts
+        #[arg(long, requires = "dry_run", conflicts_with = "show_all")]
+        summary_only: bool,
+
+        /// With --dry-run, list every repository, including up-to-date ones
+        #[arg(long, requires = "dry_run")]
+        show_all: bool,
+
+        /// Sync only the N most recently pushed repositories (after other filters)
+        #[arg(long, value_name = "N")]
+        newest: Option<usize>,
+
+        /// Sync exactly the repositories named on stdin (owner/repo per line), skipping discovery
+        #[arg(long, conflicts_with = "org")]
+        stdin: bool,
+
+        /// Record each checkout's path and current branch without pulling or cloning
+        #[arg(long, conflicts_with_all = ["dry_run", "force", "confirm"])]
+        analyze_only: bool,
+
+        /// Prune deleted remote branches and local branches whose upstream is gone (sets sync.prune)
+        #[arg(long, conflicts_with = "analyze_only")]
+        prune_refs: bool,
+
+        /// Keep syncing when the first repositories all fail to authenticate (sets sync.continue_on_auth_error)
+        #[arg(long, conflicts_with = "analyze_only")]
+        continue_on_auth_error: bool,
+
+        /// Write a report of the run (JSON or Markdown, by extension) to FILE
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["dry_run", "analyze_only"])]
+        report: Option<std::path::PathBuf>,
+
+        /// Print the sync summary, with every repository's outcome, as JSON
+        #[arg(long, conflicts_with_all = ["dry_run", "analyze_only"])]
+        json: bool,
+
+        /// Print the discovered repository specs as JSON and exit (for debugging)
+        #[arg(long, hide = true)]
+        dump_specs: bool,
     },
 
     /// List repositories that would be synced
@@ -69,9 +144,43 @@ enum Commands {
         #[arg(long)]
         details: bool,
 
-        /// Filter by organization
+        /// Print the repositories as a JSON array
+        #[arg(long, conflicts_with = "details")]
+        json: bool,
+
+        /// Filter by user/organization (repeatable)
         #[arg(long)]
-        org: Option<String>,
+        org: Vec<String>,
+
+        /// List checkouts under base_directory that no discovered repository maps to
+        #[arg(long, conflicts_with = "details")]
+        unmanaged: bool,
+
+        /// Print the discovered repository specs as JSON and exit (for debugging)
+        #[arg(long, hide = true)]
+        dump_specs: bool,
+    },
+
+    /// Inspect the effect of the configured repository filters
+    Filters {
+        #[command(subcommand)]
+        filters_command: FiltersCommands,
+    },
+
+    /// Export the synced repositories to a manifest, or clone from one
+    Manifest {
+        #[command(subcommand)]
+        manifest_command: ManifestCommands,
+    },
+
+    /// Print a repository's local path (or open its web page with --web)
+    Open {
+        /// Repository name (owner/repo, or just repo if unambiguous)
+        name: String,
+
+        /// Open the repository on GitHub in the default browser
+        #[arg(long)]
+        web: bool,
     },
 
     /// Run as daemon
@@ -92,6 +201,13 @@ enum Commands {
         #[command(subcommand)]
         events_command: EventsCommands,
     },
+
+    /// Generate a shell completion script on stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 #[derive(Subcommand)]
@@ -106,6 +222,36 @@ enum AuthCommands {
     Status,
 }
 
+#[derive(Subcommand)]
+enum FiltersCommands {
+    /// Show which repositories the current filters include and exclude, and why
+    Test,
+}
+
+#[derive(Subcommand)]
+enum ManifestCommands {
+    /// Write the synced repositories (name, clone URL, path, default branch) as YAML
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Write JSON instead of YAML
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Clone (or sync) exactly the repositories in a manifest, skipping discovery
+    Apply {
+        /// Manifest file (YAML or JSON)
+        file: std::path::PathBuf,
+
+        /// Wait for an already running sync (e.g. the daemon's) instead of aborting
+        #[arg(long)]
+        wait: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum DaemonCommands {
     /// Start daemon in foreground
@@ -119,13 +265,84 @@ enum DaemonCommands {
     Stop,
 
     /// Show daemon status
-    Status,
+    Status {
+        /// Print the status as JSON (exits non-zero if the daemon isn't running)
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Restart daemon
     Restart,
 
     /// Reload daemon configuration
     Reload,
+
+    /// Install a systemd user unit (launchd agent on macOS) that runs the daemon
+    Install {
+        /// Install a system-wide service (/etc/systemd/system) instead of a user one
+        #[arg(long)]
+        system: bool,
+
+        /// Overwrite an existing unit file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// What `sync` does with the selected repositories
+#[derive(Clone, Copy, PartialEq)]
+enum SyncMode {
+    /// Clone and pull
+    Sync,
+
+    /// Report what a sync would do (`--dry-run`)
+    DryRun(DryRunDetail),
+
+    /// Record each checkout's state without changing it (`--analyze-only`)
+    AnalyzeOnly,
+}
+
+/// How much per-repository detail `sync --dry-run` prints
+#[derive(Clone, Copy, PartialEq)]
+enum DryRunDetail {
+    /// Repositories needing action, plus up-to-date ones for small sets
+    Default,
+
+    /// Aggregate counts only
+    SummaryOnly,
+
+    /// Every repository
+    All,
+}
+
+/// Which repositories `sync` operates on
+struct RepoSelection {
+    /// Names read with `--stdin` (discovery is skipped when set)
+    names: Option<Vec<String>>,
+
+    /// Keep only the N most recently pushed (`--newest`)
+    newest: Option<usize>,
+}
+
+/// Where `sync` reports the outcome of a run
+struct SyncOutput {
+    /// Also write a report file (`--report`)
+    report: Option<std::path::PathBuf>,
+
+    /// Print the summary as JSON instead of the usual progress (`--json`)
+    json: bool,
+}
+
+/// Largest set for which default dry-run output lists up-to-date repositories
+const DRY_RUN_UP_TO_DATE_LIMIT: usize = 10;
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq)]
+enum EventsFormat {
+    /// Human-readable list
+    Text,
+
+    /// CSV with a header row, for spreadsheets
+    Csv,
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -161,6 +378,18 @@ enum EventsCommands {
         /// Filter by event type (cloned, pulled, branch_switch, skipped, error)
         #[arg(short = 't', long)]
         event_type: Option<String>,
+
+        /// Only events of one sync run: "last" or a run ID
+        #[arg(long)]
+        run: Option<String>,
+
+        /// Only events with this tag, as key=value (repeatable, e.g. --tag org=acme)
+        #[arg(long = "tag", value_name = "KEY=VALUE")]
+        tags: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: EventsFormat,
     },
 
     /// Show repository status summary
@@ -203,6 +432,14 @@ enum EventsCommands {
         /// Delete events older than N days
         #[arg(short, long, default_value = "30")]
         days: u32,
+
+        /// Delete events from this time on instead (RFC3339, or a duration ago like "3d")
+        #[arg(long, conflicts_with = "days")]
+        after: Option<String>,
+
+        /// Delete events before this time instead (RFC3339, or a duration ago like "2d")
+        #[arg(long, conflicts_with = "days")]
+        before: Option<String>,
     },
 }
 
@@ -210,17 +447,34 @@ enum EventsCommands {
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Completion scripts go to stdout, so skip logging and config loading
+    if let Some(Commands::Completions { shell }) = cli.command {
+        return cmd_completions(shell);
+    }
+
+    // Load configuration (logging is set up from it)
+    let config = load_config(cli.config).await?;
+    if cli.strict_config && !config.unknown_keys.is_empty() {
+        bail!(
+            "Invalid configuration (--strict-config):\n  {}",
+            config.unknown_keys.join("\n  ")
+        );
+    }
+
     // Only initialize logging for CLI commands, not TUI
     // TUI has its own log viewer and stdout logging breaks raw mode
     let is_tui = cli.command.is_none();
     if !is_tui {
-        init_logging(cli.verbose)?;
+        init_logging(cli.verbose, &config.logging)?;
         info!("Starting RepoSentry v{}", env!("CARGO_PKG_VERSION"));
+        for unknown_key in &config.unknown_keys {
+            warn!("Ignoring {}", unknown_key);
+        }
+        for warning in &config.load_warnings {
+            warn!("{}", warning);
+        }
     }
 
-    // Load configuration
-    let config = load_config(cli.config).await?;
-
     // Execute command (default to TUI if no command specified)
     match cli.command {
         None => cmd_tui(&config).await,
@@ -233,27 +487,125 @@ async fn main() -> Result<()> {
             dry_run,
             force,
             org,
-        }) => cmd_sync(dry_run, force, org, &config).await,
-        Some(Commands::List { details, org }) => cmd_list(details, org, &config).await,
+            include_wikis,
+            confirm,
+            wait,
+            summary_only,
+            show_all,
+            newest,
+            stdin,
+            analyze_only,
+            prune_refs,
+            continue_on_auth_error,
+            report,
+            json,
+            dump_specs,
+        }) => {
+            let mut config = config;
+            config.github.include_wikis |= include_wikis;
+            config.sync.prune |= prune_refs;
+            config.sync.continue_on_auth_error |= continue_on_auth_error;
+            if !org.is_empty() {
+                config.github.only_owners = org.clone();
+            }
+            if confirm {
+                config.sync.first_run_mode = "sync".to_string();
+            }
+            let mode = if analyze_only {
+                SyncMode::AnalyzeOnly
+            } else if summary_only {
+                SyncMode::DryRun(DryRunDetail::SummaryOnly)
+            } else if show_all {
+                SyncMode::DryRun(DryRunDetail::All)
+            } else if dry_run {
+                SyncMode::DryRun(DryRunDetail::Default)
+            } else {
+                SyncMode::Sync
+            };
+            if dump_specs {
+                return cmd_dump_specs(&config).await;
+            }
+            let names = if stdin {
+                let input = std::io::read_to_string(std::io::stdin())
+                    .context("Failed to read repository names from stdin")?;
+                Some(parse_repo_list(&input))
+            } else {
+                None
+            };
+            if let Some(path) = &report {
+                // Reject an unusable file name before syncing rather than after
+                ReportFormat::from_path(path)?;
+            }
+            let selection = RepoSelection { names, newest };
+            let output = SyncOutput { report, json };
+            cmd_sync(mode, force, &org, &selection, wait, &output, &config).await
+        }
+        Some(Commands::List {
+            details,
+            json,
+            org,
+            unmanaged,
+            dump_specs,
+        }) => {
+            let mut config = config;
+            if !org.is_empty() {
+                config.github.only_owners = org;
+            }
+            if dump_specs {
+                return cmd_dump_specs(&config).await;
+            }
+            if unmanaged {
+                return cmd_list_unmanaged(json, &config).await;
+            }
+            cmd_list(details, json, &config).await
+        }
+        Some(Commands::Filters { filters_command }) => cmd_filters(filters_command, &config).await,
+        Some(Commands::Manifest { manifest_command }) => {
+            cmd_manifest(manifest_command, &config).await
+        }
+        Some(Commands::Open { name, web }) => cmd_open(&name, web, &config).await,
         Some(Commands::Daemon { daemon_command }) => cmd_daemon(daemon_command, &config).await,
         Some(Commands::Doctor { component }) => cmd_doctor(component, &config).await,
-        Some(Commands::Events { events_command }) => cmd_events(events_command).await,
+        Some(Commands::Events { events_command }) => cmd_events(events_command, &config).await,
+        Some(Commands::Completions { .. }) => unreachable!("handled before logging setup"),
     }
 }
 
-/// Initialize logging based on verbosity level
-fn init_logging(verbose: bool) -> Result<()> {
-    let filter = if verbose {
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"))
-    } else {
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"))
+/// Initialize logging from the logging configuration
+///
+/// `-v` raises the level to debug and `-vv` to trace; `RUST_LOG` overrides both. Logs go
+/// to stderr so command output (e.g. CSV) can be redirected cleanly; the
+/// daemon logs through this subscriber too (its stderr is the log file).
+fn init_logging(verbose: u8, logging: &LoggingConfig) -> Result<()> {
+    let level = match verbose {
+        0 => logging.level.as_str(),
+        1 => "debug",
+        _ => "trace",
     };
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(level))
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let registry = tracing_subscriber::registry().with(filter);
+    let layer = fmt::layer().with_writer(std::io::stderr);
+    match logging.format.as_str() {
+        "json" => registry.with(layer.json()).init(),
+        "pretty" => registry
+            .with(layer.pretty().with_ansi(logging.color))
+            .init(),
+        _ => registry
+            .with(layer.compact().with_ansi(logging.color))
+            .init(),
+    }
 
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(filter)
-        .init();
+    Ok(())
+}
 
+/// Print a shell completion script for the given shell
+fn cmd_completions(shell: Shell) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
     Ok(())
 }
 
@@ -323,34 +675,148 @@ async fn cmd_auth(auth_command: AuthCommands, config: &Config) -> Result<()> {
 
 /// Sync repositories according to configuration
 async fn cmd_sync(
-    dry_run: bool,
+    mode: SyncMode,
     force: bool,
-    org_filter: Option<String>,
+    org_filter: &[String],
+    selection: &RepoSelection,
+    wait: bool,
+    output: &SyncOutput,
     config: &Config,
 ) -> Result<()> {
     info!("Starting repository synchronization...");
+    // Progress goes to stdout only when it isn't reserved for the JSON summary
+    let verbose = !output.json;
+
+    // Prompts on stdout would corrupt the JSON summary
+    if output.json && config.sync.strategy == "interactive" {
+        bail!("--json can't be used with sync.strategy \"interactive\", whose prompts use stdout");
+    }
+
+    // The environment override wins over any flags
+    let mode = if dry_run_forced() && mode == SyncMode::Sync {
+        if output.json {
+            bail!(
+                "{} is set, so there's no sync to report as JSON",
+                DRY_RUN_ENV
+            );
+        }
+        eprintln!("🔒 {} is set - forcing dry run", DRY_RUN_ENV);
+        SyncMode::DryRun(DryRunDetail::Default)
+    } else {
+        mode
+    };
+
+    // Fail fast with a clear message when offline
+    for host in provider_hosts(config) {
+        check_connectivity(&host).await?;
+    }
+    let sync_engine = SyncEngine::with_state_db(config.clone())
+        .unwrap_or_else(|e| {
+            warn!(
+                "State database unavailable, sync results won't be recorded: {}",
+                e
+            );
+            SyncEngine::new(config.clone())
+        })
+        .wait_for_lock(wait)
+        .with_token_auth()
+        .await;
+
+    let repos = match &selection.names {
+        Some(names) => {
+            if !config.uses_provider("github") {
+                bail!("--stdin resolves names on GitHub, which isn't in providers");
+            }
+            let discovery = GitHubDiscovery::new(config.clone()).await?;
+            if verbose {
+                println!("🔍 Resolving {} repositories from stdin...", names.len());
+            }
+            let (repos, unresolved) = discovery.resolve(names).await;
+            if verbose {
+                println!("   Resolved {} repositories", repos.len());
+                if !unresolved.is_empty() {
+                    println!("\n⚠️  Could not resolve {} repositories:", unresolved.len());
+                    for (name, reason) in &unresolved {
+                        println!("   ❌ {}: {}", name, reason);
+                    }
+                }
+            } else {
+                for (name, reason) in &unresolved {
+                    warn!("Could not resolve {}: {}", name, reason);
+                }
+            }
+            repos
+        }
+        None => {
+            if verbose {
+                println!("🔍 Discovering repositories...");
+            }
+            let repos = discover_configured(config).await?;
+            if verbose {
+                println!("   Found {} repositories", repos.len());
+            }
+            // An empty run still produces a (zero) JSON summary
+            if repos.is_empty() && verbose {
+                print_empty_discovery(config).await?;
+                return Ok(());
+            }
+            repos
+        }
+    };
+
+    let repos = match selection.newest {
+        Some(n) => {
+            let repos = newest_specs(repos, n);
+            if verbose {
+                println!(
+                    "   Keeping the {} most recently pushed (--newest {})",
+                    repos.len(),
+                    n
+                );
+            }
+            repos
+        }
+        None => repos,
+    };
 
-    // Create discovery and sync engine
-    let discovery = GitHubDiscovery::new(config.clone()).await?;
-    let sync_engine = SyncEngine::new(config.clone());
+    if mode == SyncMode::AnalyzeOnly {
+        println!("\n🔍 Analyze-only mode - recording repository states without syncing");
+
+        let repo_states = sync_engine.analyze_repos(&repos).await?;
+        let recorded = sync_engine.record_analysis(&repos, &repo_states)?;
 
-    // Discover repositories
-    println!("🔍 Discovering repositories...");
-    let repos = discovery.discover().await?;
-    println!("   Found {} repositories", repos.len());
+        println!("   📝 Recorded the state of {} repositories", recorded);
+        let not_cloned = repo_states.iter().filter(|state| !state.exists).count();
+        if not_cloned > 0 {
+            println!("   📥 Not cloned yet (left out): {}", not_cloned);
+        }
+        println!("\n💡 Run 'reposentry events status' to review them");
+        return Ok(());
+    }
 
-    if dry_run {
+    if let SyncMode::DryRun(dry_run_detail) = mode {
         println!("\n🔍 Dry run mode - analyzing repository states");
 
         let repo_states = sync_engine.analyze_repos(&repos).await?;
 
-        println!("📊 Repository Analysis Results:");
+        if dry_run_detail != DryRunDetail::SummaryOnly {
+            println!("📊 Repository Analysis Results:");
+        }
 
         let mut needs_clone = 0;
         let mut needs_pull = 0;
         let mut has_conflicts = 0;
+        let mut branch_switches = 0;
         let mut up_to_date = 0;
 
+        let show_repos = dry_run_detail != DryRunDetail::SummaryOnly;
+        let show_up_to_date = match dry_run_detail {
+            DryRunDetail::All => true,
+            DryRunDetail::SummaryOnly => false,
+            // Show details for small sets
+            DryRunDetail::Default => repo_states.len() <= DRY_RUN_UP_TO_DATE_LIMIT,
+        };
+
         for state in &repo_states {
             match (
                 state.exists,
@@ -360,26 +826,42 @@ async fn cmd_sync(
             ) {
                 (false, _, _, _) => {
                     needs_clone += 1;
-                    println!("   📥 Clone needed: {}", state.path.display());
+                    if show_repos {
+                        println!("   📥 Clone needed: {}", state.path.display());
+                    }
                 }
                 (true, true, _, _) => {
                     has_conflicts += 1;
-                    println!(
-                        "   ⚠️  Has conflicts: {} (uncommitted changes)",
-                        state.path.display()
-                    );
+                    if show_repos {
+                        println!(
+                            "   ⚠️  Has conflicts: {} (uncommitted changes)",
+                            state.path.display()
+                        );
+                    }
+                }
+                (true, false, _, _) if state.branch_switch.is_some() => {
+                    branch_switches += 1;
+                    if show_repos {
+                        println!(
+                            "   🔀 Would switch {} → {}: {}",
+                            state.current_branch.as_deref().unwrap_or("unknown"),
+                            state.branch_switch.as_deref().unwrap_or_default(),
+                            state.path.display()
+                        );
+                    }
                 }
                 (true, false, _, true) => {
                     needs_pull += 1;
-                    println!(
-                        "   🔄 Pull needed: {} (behind remote)",
-                        state.path.display()
-                    );
+                    if show_repos {
+                        println!(
+                            "   🔄 Pull needed: {} (behind remote)",
+                            state.path.display()
+                        );
+                    }
                 }
                 _ => {
                     up_to_date += 1;
-                    if repo_states.len() <= 10 {
-                        // Show details for small sets
+                    if show_up_to_date {
                         println!("   ✅ Up to date: {}", state.path.display());
                     }
                 }
@@ -390,8 +872,13 @@ async fn cmd_sync(
         println!("   📥 Repositories to clone: {}", needs_clone);
         println!("   🔄 Repositories to pull: {}", needs_pull);
         println!("   ⚠️  Repositories with conflicts: {}", has_conflicts);
+        if config.branches.is_most_recent_strategy() {
+            println!("   🔀 Repositories to switch branch: {}", branch_switches);
+        }
         println!("   ✅ Up-to-date repositories: {}", up_to_date);
 
+        print_new_repositories(&sync_engine.new_repos(&repos));
+
         if has_conflicts > 0 {
             println!("\n💡 Tip: Resolve conflicts manually before running sync");
         }
@@ -400,14 +887,33 @@ async fn cmd_sync(
     }
 
     // Real sync mode
-    println!("\n🔄 Running full repository synchronization");
-
-    if force {
-        println!("⚡ Force mode enabled");
+    if verbose {
+        println!("\n🔄 Running full repository synchronization");
+        if force {
+            println!("⚡ Force mode enabled");
+        }
     }
 
+    let started_at = chrono::Utc::now();
+    let first_event_id = StateDb::open().and_then(|db| db.latest_event_id()).ok();
     let summary = sync_engine.sync_repos(repos).await?;
 
+    if let Some(path) = &output.report {
+        let warnings = first_event_id
+            .map(run_warnings)
+            .transpose()?
+            .unwrap_or_default();
+        SyncReport::new(started_at, config, &summary, warnings).write(path)?;
+    }
+
+    if output.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&summary).context("Failed to serialize sync summary")?
+        );
+        return Ok(());
+    }
+
     println!("\n🎉 Synchronization Complete!");
     println!("   📊 Total repositories: {}", summary.total_repositories);
     println!(
@@ -416,76 +922,442 @@ async fn cmd_sync(
     );
     println!("   ❌ Failed operations: {}", summary.failed_operations);
     println!("   ⏭️  Skipped operations: {}", summary.skipped_operations);
+    if summary.rebased_operations + summary.merged_operations > 0 {
+        println!(
+            "   🔀 Rebased: {}, merged: {}",
+            summary.rebased_operations, summary.merged_operations
+        );
+    }
     println!("   ⏱️  Duration: {:.2}s", summary.duration.as_secs_f64());
+    if let Some(limit) = &summary.rate_limit {
+        println!(
+            "   🐢 Rate limit: {} (repositories synced one at a time)",
+            limit
+        );
+    }
+
+    print_new_repositories(&summary.new_repositories);
+
+    let skips = summary.skips_by_reason();
+    if !skips.is_empty() {
+        println!("\n⏭️  Skipped:");
+        for (event_type, paths) in skips {
+            println!("   {} {}", paths.len(), skip_description(event_type));
+            for path in paths {
+                println!("      ⏭️  {}", path.display());
+            }
+        }
+    }
 
     if summary.failed_operations > 0 {
         println!("\n🔍 Failed Operations:");
-        for result in &summary.results {
-            if let reposentry::SyncResult::Failed { path, error } = result {
-                println!("   ❌ {}: {}", path.display(), error);
+        for (kind, failures) in summary.failures_by_kind() {
+            println!("   {} {}", failures.len(), kind.description());
+            for (path, error) in failures {
+                // The cause is known for the others; their errors are in `events`
+                if kind == FailureKind::Other {
+                    println!("      ❌ {}: {}", path.display(), error);
+                } else {
+                    println!("      ❌ {}", path.display());
+                }
             }
         }
     }
 
-    if let Some(org) = org_filter {
-        println!("\n📝 Note: Filtered by organization: {}", org);
+    if let Some(path) = &output.report {
+        println!("\n📄 Report written to {}", path.display());
+    }
+
+    if !org_filter.is_empty() {
+        println!(
+            "\n📝 Note: Filtered by organization: {}",
+            org_filter.join(", ")
+        );
         println!("   Use --help to see all filtering options");
     }
 
     Ok(())
 }
 
+/// Warning and error events recorded after `first_event_id`, for a sync report
+fn run_warnings(first_event_id: i64) -> Result<Vec<String>> {
+    let db = StateDb::open()?;
+    Ok(db
+        .get_events_after(first_event_id)?
+        .into_iter()
+        .filter(|event| event.severity != Severity::Info)
+        .map(|event| match &event.repo_full_name {
+            Some(repo) => format!("{}: {}", repo, event.summary),
+            None => event.summary,
+        })
+        .collect())
+}
+
+/// Why repositories recorded with a skip event type were skipped
+fn skip_description(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::SkippedLocalChanges => "with local changes",
+        EventType::SkippedConflicts => "with conflicts or an interrupted merge/rebase",
+        EventType::SkippedAheadOfRemote => "ahead of their remote",
+        EventType::SkippedSentinel => "with a .reposentry-skip file",
+        EventType::RemoteMismatch => "with an unexpected origin",
+        EventType::RemoteInaccessible => "with an inaccessible remote",
+        _ => "for other reasons",
+    }
+}
+
+/// Print repositories seen for the first time since the last sync
+fn print_new_repositories(full_names: &[String]) {
+    if full_names.is_empty() {
+        return;
+    }
+
+    println!("\n🆕 New repositories ({}):", full_names.len());
+    for full_name in full_names {
+        println!("   {}", full_name);
+    }
+}
+
 /// List repositories that would be synced
-async fn cmd_list(details: bool, org_filter: Option<String>, config: &Config) -> Result<()> {
+///
+/// `--org` is applied through `github.only_owners`, so other organizations
+/// aren't enumerated at all.
+async fn cmd_list(details: bool, json: bool, config: &Config) -> Result<()> {
     info!("Listing repositories...");
 
-    // Create GitHub client
-    let github_client = GitHubClient::new(config).await?;
-
-    // Get repositories
-    let repositories = github_client.get_all_repositories(config).await?;
-
-    // Filter by organization if specified
-    let filtered_repos: Vec<_> = if let Some(org) = org_filter {
-        repositories
-            .into_iter()
-            .filter(|repo| {
-                repo.full_name
-                    .as_ref()
-                    .map(|name| name.starts_with(&format!("{}/", org)))
-                    .unwrap_or(false)
+    // Repositories from every configured provider (already filtered)
+    let filtered_repos = discover_configured(config).await?;
+
+    if json {
+        let entries: Vec<_> = filtered_repos
+            .iter()
+            .map(|repo| {
+                serde_json::json!({
+                    "full_name": repo.full_name(),
+                    "provider": repo.provider,
+                    "size": repo.size_bytes.map(|bytes| bytes / 1024),
+                    "pushed_at": repo.pushed_at,
+                    "html_url": repo.html_url,
+                })
             })
-            .collect()
-    } else {
-        repositories
-    };
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).context("Failed to serialize repositories")?
+        );
+        return Ok(());
+    }
 
     println!("Repositories ({}): ", filtered_repos.len());
 
     for repo in filtered_repos {
         if details {
-            println!("📁 {}", repo.full_name.as_ref().unwrap_or(&repo.name));
+            println!("📁 {}", repo.full_name());
             if let Some(description) = &repo.description {
                 println!("   📝 {}", description);
             }
-            if let Some(size) = repo.size {
-                println!("   📊 Size: {} KB", size);
+            if let Some(size) = repo.size_bytes {
+                println!("   📊 Size: {} KB", size / 1024);
             }
-            if let Some(updated) = repo.updated_at {
-                println!("   🕒 Updated: {}", updated.format("%Y-%m-%d"));
+            if let Some(pushed) = repo.pushed_at {
+                println!("   🕒 Pushed: {}", pushed.format("%Y-%m-%d"));
             }
             if let Some(url) = &repo.html_url {
                 println!("   🔗 {}", url);
             }
             println!();
         } else {
-            println!("  📁 {}", repo.full_name.as_ref().unwrap_or(&repo.name));
+            println!("  📁 {}", repo.full_name());
+        }
+    }
+
+    Ok(())
+}
+
+/// List checkouts on disk that no discovered repository is synced to
+async fn cmd_list_unmanaged(json: bool, config: &Config) -> Result<()> {
+    let repos = discover_configured(config).await?;
+    let unmanaged = find_unmanaged_repos(config, &repos);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&unmanaged).context("Failed to serialize checkouts")?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Unmanaged checkouts under {} ({}):",
+        config.expanded_base_directory().display(),
+        unmanaged.len()
+    );
+    for path in unmanaged {
+        println!("  📁 {}", path.display());
+    }
+    println!(
+        "\n💡 Searched {} levels deep (advanced.max_scan_depth)",
+        config.advanced.max_scan_depth
+    );
+
+    Ok(())
+}
+
+/// Explain an empty discovery result for each configured provider
+async fn print_empty_discovery(config: &Config) -> Result<()> {
+    println!("\n⚠️  No repositories to sync");
+    if config.uses_provider("github") {
+        let client = GitHubClient::new(config).await?;
+        print_discovery_diagnosis(&client.diagnose_discovery(config).await?);
+    }
+    if config.uses_provider("gitlab") {
+        let discovery = GitLabDiscovery::new(config.clone())?;
+        print_gitlab_diagnosis(&discovery.diagnose().await?);
+    }
+    Ok(())
+}
+
+/// Explain an empty GitLab discovery result, pointing at the likely cause
+fn print_gitlab_diagnosis(diagnosis: &GitLabDiagnosis) {
+    println!("   GitLab instance: {}", diagnosis.url);
+    println!("   Fetched before filters: {}", diagnosis.fetched);
+    for (rejection, count) in &diagnosis.excluded {
+        println!("   Excluded by {}: {}", rejection.description(), count);
+    }
+    if !diagnosis.only_owners.is_empty() {
+        println!("   Owner filter: {}", diagnosis.only_owners.join(", "));
+    }
+    println!("\n💡 {}", diagnosis.likely_cause());
+}
+
+/// Explain an empty GitHub discovery result, pointing at the likely cause
+fn print_discovery_diagnosis(diagnosis: &DiscoveryDiagnosis) {
+    println!("   Fetched before filters: {}", diagnosis.fetched);
+    for (rejection, count) in &diagnosis.excluded {
+        println!("   Excluded by {}: {}", rejection.description(), count);
+    }
+    println!(
+        "   Organizations included: {}",
+        if diagnosis.include_organizations {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+    if !diagnosis.only_owners.is_empty() {
+        println!("   Owner filter: {}", diagnosis.only_owners.join(", "));
+    }
+    match &diagnosis.token_scopes {
+        Some(scopes) if !scopes.is_empty() => println!("   Token scopes: {}", scopes),
+        Some(_) => println!("   Token scopes: none"),
+        None => println!("   Token scopes: not reported (fine-grained or app token)"),
+    }
+    println!("\n💡 {}", diagnosis.likely_cause());
+}
+
+/// Inspect the configured repository filters
+async fn cmd_filters(command: FiltersCommands, config: &Config) -> Result<()> {
+    match command {
+        FiltersCommands::Test => {
+            // Every repository of each configured provider, with the filter excluding it
+            let mut repositories: Vec<(String, Option<FilterRejection>)> = Vec::new();
+            if config.uses_provider("github") {
+                let github_client = GitHubClient::new(config).await?;
+                let listed = github_client.list_all_repositories(config).await?;
+                let (included, excluded) = partition_by_filters(listed, config);
+                let name = |repo: &octocrab::models::Repository| {
+                    repo.full_name.clone().unwrap_or_else(|| repo.name.clone())
+                };
+                repositories.extend(included.iter().map(|repo| (name(repo), None)));
+                repositories.extend(
+                    excluded
+                        .iter()
+                        .map(|(repo, rejection)| (name(repo), Some(*rejection))),
+                );
+            }
+            if config.uses_provider("gitlab") {
+                let projects = GitLabDiscovery::new(config.clone())?
+                    .filter_projects()
+                    .await?;
+                repositories.extend(
+                    projects
+                        .into_iter()
+                        .map(|(project, rejection)| (project.path_with_namespace, rejection)),
+                );
+            }
+
+            println!(
+                "🔍 Filter simulation over {} repositories",
+                repositories.len()
+            );
+            println!();
+
+            let with_rejection = |rejection: Option<FilterRejection>| {
+                repositories
+                    .iter()
+                    .filter(move |(_, r)| *r == rejection)
+                    .map(|(name, _)| name)
+            };
+
+            println!("✅ Included ({}):", with_rejection(None).count());
+            for name in with_rejection(None) {
+                println!("  📁 {}", name);
+            }
+
+            for rejection in FilterRejection::ALL {
+                println!();
+                println!(
+                    "❌ Excluded by {} ({}):",
+                    rejection.description(),
+                    with_rejection(Some(rejection)).count()
+                );
+                for name in with_rejection(Some(rejection)) {
+                    println!("  📁 {}", name);
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Export or apply a repository manifest
+async fn cmd_manifest(command: ManifestCommands, config: &Config) -> Result<()> {
+    match command {
+        ManifestCommands::Export { output, json } => {
+            let db = StateDb::open()?;
+            let manifest = Manifest::export(config, &db, &GitClient::new(config.clone())).await?;
+            let rendered = manifest.render(json)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write manifest: {}", path.display()))?;
+                    println!(
+                        "📝 Wrote {} repositories to {}",
+                        manifest.repositories.len(),
+                        path.display()
+                    );
+                }
+                None => print!("{}", rendered),
+            }
+        }
+
+        ManifestCommands::Apply { file, wait } => {
+            let specs = Manifest::load(&file)?.to_specs(config)?;
+            println!(
+                "📋 Applying {} repositories from {}",
+                specs.len(),
+                file.display()
+            );
+
+            let sync_engine = SyncEngine::with_state_db(config.clone())
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "State database unavailable, sync results won't be recorded: {}",
+                        e
+                    );
+                    SyncEngine::new(config.clone())
+                })
+                .wait_for_lock(wait)
+                .with_token_auth()
+                .await;
+            let summary = sync_engine.sync_repos(specs).await?;
+
+            println!("\n🎉 Manifest applied!");
+            println!(
+                "   ✅ Successful operations: {}",
+                summary.successful_operations
+            );
+            println!("   ❌ Failed operations: {}", summary.failed_operations);
+            println!("   ⏭️  Skipped operations: {}", summary.skipped_operations);
+            for result in &summary.results {
+                if let reposentry::SyncResult::Failed { path, error } = result {
+                    println!("   ❌ {}: {}", path.display(), error);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the specs discovery produces (after filtering and path computation) as JSON
+async fn cmd_dump_specs(config: &Config) -> Result<()> {
+    use std::io::Write;
+
+    let specs: Vec<RepoSpec> = discover_configured(config).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &specs).context("Failed to serialize repo specs")?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+/// Print a repository's local path, or open its web page
+///
+/// The name is resolved against the cached discovery results, falling back
+/// to a fresh discovery. Only the path goes to stdout, so
+/// `cd "$(reposentry open repo)"` works.
+async fn cmd_open(name: &str, web: bool, config: &Config) -> Result<()> {
+    let state_db = StateDb::open().ok();
+    let cached = state_db
+        .as_ref()
+        .and_then(|db| load_cached_specs(db, config));
+    let specs = match cached {
+        Some(cached) => cached.specs,
+        None => {
+            let specs = discover_configured(config).await?;
+            if let Some(db) = &state_db {
+                let _ = store_cached_specs(db, config, &specs);
+            }
+            specs
+        }
+    };
+
+    let spec = find_spec(&specs, name)?;
+
+    if web {
+        let url = spec
+            .html_url
+            .clone()
+            .unwrap_or_else(|| format!("https://{}/{}", GITHUB_HOST, spec.full_name()));
+        info!("Opening {}", url);
+        return open_url(&url);
+    }
+
+    if !spec.local_path.exists() {
+        warn!(
+            "{} has not been cloned yet (run 'reposentry sync')",
+            spec.full_name()
+        );
+    }
+    println!("{}", spec.local_path.display());
+    Ok(())
+}
+
+/// Open a URL with the platform's default handler
+fn open_url(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+
+    let status = command
+        .arg(url)
+        .status()
+        .context("Failed to launch the browser")?;
+    if !status.success() {
+        bail!("Failed to open {} ({})", url, status);
+    }
+    Ok(())
+}
+
 /// Handle daemon commands
 async fn cmd_daemon(daemon_command: DaemonCommands, config: &Config) -> Result<()> {
     match daemon_command {
@@ -543,27 +1415,49 @@ async fn cmd_daemon(daemon_command: DaemonCommands, config: &Config) -> Result<(
             println!("✅ Daemon stop signal sent");
         }
 
-        DaemonCommands::Status => {
+        DaemonCommands::Status { json: true } => print_daemon_status_json(config)?,
+
+        DaemonCommands::Status { json: false } => {
             println!("📊 RepoSentry Daemon Status");
 
             let is_running = is_daemon_running(config)?;
 
             if is_running {
-                let daemon = Daemon::new((*config).clone()).await?;
-                let status = daemon.status(std::time::Instant::now());
+                // The daemon is another process: read what it recorded on disk
+                let uptime = daemon_uptime(config);
+                let interval = parse_duration_secs(&config.daemon.interval)
+                    .ok()
+                    .map(std::time::Duration::from_secs);
+                let stats = daemon_stats(config).unwrap_or_default();
 
                 println!("   🟢 Status: Running");
-                println!("   ⏱️  Uptime: {:.1}m", status.uptime.as_secs_f64() / 60.0);
+                if let Some(uptime) = uptime {
+                    println!("   ⏱️  Uptime: {:.1}m", uptime.as_secs_f64() / 60.0);
+                }
                 println!("   🔄 Sync interval: {}", config.daemon.interval);
+                if let Some(active_hours) = &config.daemon.active_hours {
+                    println!("   🌙 Active hours: {}", active_hours);
+                }
 
-                if let Some(next_sync) = status.next_sync_in {
+                let next_sync = uptime
+                    .zip(interval)
+                    .and_then(|(uptime, interval)| next_sync_in(uptime, interval));
+                if let Some(next_sync) = next_sync {
                     println!("   ⏰ Next sync in: {:.0}s", next_sync.as_secs_f64());
                 }
 
                 println!("   📊 Sync statistics:");
-                println!("      Total: {}", status.total_syncs);
-                println!("      Successful: {}", status.successful_syncs);
-                println!("      Failed: {}", status.failed_syncs);
+                println!("      Total: {}", stats.total_syncs);
+                println!("      Successful: {}", stats.successful_syncs);
+                println!("      Failed: {}", stats.failed_syncs);
+                if let Some(last_sync) = stats.last_sync_at {
+                    println!(
+                        "      Last sync: {}",
+                        last_sync
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                    );
+                }
 
                 let resolved_log = get_log_file_path(&config.daemon.log_file);
                 if !resolved_log.is_empty() {
@@ -615,6 +1509,18 @@ async fn cmd_daemon(daemon_command: DaemonCommands, config: &Config) -> Result<(
             println!("🚧 Configuration hot-reload coming soon...");
             println!("   For now, use 'daemon restart' to apply new configuration");
         }
+
+        DaemonCommands::Install { system, force } => {
+            let service = ServiceDefinition::for_current_process(config, system)?;
+            let path = service.install_path()?;
+            service.write(&path, force)?;
+
+            println!("✅ Service definition written to {}", path.display());
+            println!("   Binary: {}", service.binary.display());
+            println!("   Config: {}", service.config_path.display());
+            println!("\n💡 Enable and start it with:");
+            println!("   {}", service.enable_command(&path));
+        }
     }
 
     Ok(())
@@ -627,6 +1533,40 @@ async fn cmd_doctor(_component: Option<DoctorComponent>, config: &Config) -> Res
     Ok(())
 }
 
+/// Print the daemon status as a JSON object for monitoring
+///
+/// Exits with status 1 if the daemon isn't running, so a check can alert on it.
+fn print_daemon_status_json(config: &Config) -> Result<()> {
+    let running = is_daemon_running(config)?;
+    let interval_secs = parse_duration_secs(&config.daemon.interval).ok();
+    let uptime = running.then(|| daemon_uptime(config)).flatten();
+    let next_sync = uptime
+        .zip(interval_secs)
+        .and_then(|(uptime, secs)| next_sync_in(uptime, std::time::Duration::from_secs(secs)));
+
+    let status = serde_json::json!({
+        "running": running,
+        "pid": running.then(|| daemon_pid(config)).flatten(),
+        "uptime_secs": uptime.map(|uptime| uptime.as_secs()),
+        "interval": config.daemon.interval,
+        "interval_secs": interval_secs,
+        "active_hours": config.daemon.active_hours,
+        "next_sync_in_secs": next_sync.map(|next| next.as_secs()),
+        "log_file": get_log_file_path(&config.daemon.log_file),
+        "pid_file": get_pid_file_path(&config.daemon.pid_file),
+        "stats": running.then(|| daemon_stats(config)).flatten(),
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&status).context("Failed to serialize daemon status")?
+    );
+
+    if !running {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 /// Launch the Terminal User Interface
 async fn cmd_tui(config: &Config) -> Result<()> {
     // Preflight checks - ensure system is properly configured
@@ -706,20 +1646,49 @@ fn print_health_report(health: &HealthCheck) {
 }
 
 /// Handle events commands for viewing sync events and repository status
-async fn cmd_events(events_command: EventsCommands) -> Result<()> {
+async fn cmd_events(events_command: EventsCommands, config: &Config) -> Result<()> {
     let db = StateDb::open()?;
 
+    let chronic_threshold = config.events.chronic_threshold;
+
     match events_command {
         EventsCommands::List {
             limit,
             unread,
             event_type,
+            run,
+            tags,
+            format,
         } => {
             let event_type_filter = event_type.as_ref().and_then(|s| EventType::parse(s));
             let acknowledged_filter = if unread { Some(false) } else { None };
-
-            let events =
-                db.get_events_with_filter(acknowledged_filter, event_type_filter, Some(limit))?;
+            let run_filter = match run.as_deref() {
+                None => None,
+                Some("last") => match db.latest_run_id()? {
+                    Some(run_id) => Some(run_id),
+                    None => bail!("No sync runs recorded yet"),
+                },
+                Some(id) => Some(id.parse::<i64>().with_context(|| {
+                    format!("Invalid run '{}': expected \"last\" or a run ID", id)
+                })?),
+            };
+
+            let tag_filters = tags
+                .iter()
+                .map(|tag| parse_tag_filter(tag))
+                .collect::<Result<Vec<_>>>()?;
+
+            let events = db.get_events_with_filter(
+                acknowledged_filter,
+                event_type_filter,
+                run_filter,
+                &tag_filters,
+                Some(limit),
+            )?;
+
+            if format == EventsFormat::Csv {
+                return write_events_csv(&events, std::io::stdout());
+            }
 
             if events.is_empty() {
                 println!("📭 No events found");
@@ -756,7 +1725,18 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
                     };
                     println!("      Details: {}", truncated);
                 }
-                println!("      ID: {}", event.id);
+                if !event.tags.is_empty() {
+                    let tags = event
+                        .tags
+                        .iter()
+                        .map(|(key, value)| format!("{}={}", key, value))
+                        .collect::<Vec<_>>();
+                    println!("      Tags: {}", tags.join(" "));
+                }
+                match event.run_id {
+                    Some(run_id) => println!("      ID: {} (run {})", event.id, run_id),
+                    None => println!("      ID: {}", event.id),
+                }
                 println!();
             }
         }
@@ -782,9 +1762,18 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
                     };
 
                     println!("{} {}", status_icon, repo.full_name);
+                    if let Some(source) = &repo.source {
+                        println!("   Source: {}", source);
+                    }
                     if let Some(reason) = &repo.skip_reason {
                         println!("   Reason: {}", reason);
                     }
+                    if chronic_threshold > 0 && repo.consecutive_failures >= chronic_threshold {
+                        println!(
+                            "   🔥 Chronic: failed {} syncs in a row",
+                            repo.consecutive_failures
+                        );
+                    }
                     if let Some(branch) = &repo.current_branch {
                         println!("   Branch: {}", branch);
                     }
@@ -801,7 +1790,10 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
                 println!();
 
                 for repo in repos {
-                    println!("  {}", repo.full_name);
+                    match &repo.source {
+                        Some(source) => println!("  {} [{}]", repo.full_name, source),
+                        None => println!("  {}", repo.full_name),
+                    }
                     if let Some(branch) = &repo.current_branch {
                         println!("     Branch: {}", branch);
                     }
@@ -820,6 +1812,21 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
                 println!("      ⏭️  Skipped: {}", skipped_repos.len());
                 println!("      ❌ Error: {}", error_repos.len());
                 println!();
+
+                if chronic_threshold > 0 {
+                    let chronic_repos = db.get_chronic_repos(chronic_threshold)?;
+                    if !chronic_repos.is_empty() {
+                        println!(
+                            "   🔥 Chronically failing ({}+ failed syncs in a row):",
+                            chronic_threshold
+                        );
+                        for repo in &chronic_repos {
+                            println!("      {} ({})", repo.full_name, repo.consecutive_failures);
+                        }
+                        println!();
+                    }
+                }
+
                 println!("   Unacknowledged Events:");
                 println!("      ℹ️  Info: {}", info);
                 println!("      ⚠️  Warning: {}", warning);
@@ -847,13 +1854,20 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
 
         EventsCommands::Repo { name, limit } => {
             let events = db.get_events_for_repo(&name, Some(limit))?;
+            let last_action = db.get_repo(&name)?.and_then(|repo| repo.last_action);
 
             if events.is_empty() {
                 println!("📭 No events found for {}", name);
+                if let Some(action) = &last_action {
+                    println!("   Last action: {}", action);
+                }
                 return Ok(());
             }
 
             println!("📋 Events for {} ({})", name, events.len());
+            if let Some(action) = &last_action {
+                println!("   Last action: {}", action);
+            }
             println!();
 
             for event in events {
@@ -877,7 +1891,7 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
             let total_unack = info + warning + error;
 
             // Get all events for total count
-            let all_events = db.get_events_with_filter(None, None, None)?;
+            let all_events = db.get_events_with_filter(None, None, None, &[], None)?;
 
             println!("📈 Event Statistics");
             println!();
@@ -893,37 +1907,89 @@ async fn cmd_events(events_command: EventsCommands) -> Result<()> {
             // Count by event type
             let mut cloned = 0;
             let mut pulled = 0;
+            let mut rebased = 0;
+            let mut merged = 0;
             let mut branch_switch = 0;
+            let mut branches_pruned = 0;
             let mut skipped = 0;
+            let mut first_run = 0;
+            let mut new_repos = 0;
             let mut errors = 0;
+            let mut warnings = 0;
+            let mut other = 0;
 
             for event in &all_events {
                 match event.event_type {
                     EventType::Cloned => cloned += 1,
                     EventType::Pulled => pulled += 1,
+                    EventType::Rebased => rebased += 1,
+                    EventType::Merged => merged += 1,
                     EventType::BranchSwitch => branch_switch += 1,
+                    EventType::BranchesPruned => branches_pruned += 1,
                     EventType::SkippedLocalChanges
                     | EventType::SkippedConflicts
-                    | EventType::SkippedAheadOfRemote => skipped += 1,
-                    EventType::SyncError => errors += 1,
+                    | EventType::SkippedAheadOfRemote
+                    | EventType::SkippedSentinel => skipped += 1,
+                    EventType::FirstRunPreview => first_run += 1,
+                    EventType::NewRepo => new_repos += 1,
+                    // The rest by severity, so new event types are counted too
+                    _ => match event.event_type.severity() {
+                        Severity::Error => errors += 1,
+                        Severity::Warning => warnings += 1,
+                        Severity::Info => other += 1,
+                    },
                 }
             }
 
             println!("   By type (all time):");
             println!("      📥 Cloned: {}", cloned);
             println!("      🔄 Pulled: {}", pulled);
+            println!("      🔀 Rebased: {}", rebased);
+            println!("      🔗 Merged: {}", merged);
             println!("      ↻  Branch switches: {}", branch_switch);
+            println!("      🧹 Branch prunes: {}", branches_pruned);
             println!("      ⏭️  Skipped: {}", skipped);
+            println!("      👀 First-run previews: {}", first_run);
+            println!("      🆕 New repositories: {}", new_repos);
             println!("      ❌ Errors: {}", errors);
+            println!("      ⚠️  Other warnings: {}", warnings);
+            println!("      📝 Other: {}", other);
         }
 
-        EventsCommands::Cleanup { days } => {
+        EventsCommands::Cleanup {
+            after: None,
+            before: None,
+            days,
+        } => {
             let count = db.cleanup_old_events(days)?;
             println!(
                 "🧹 Cleaned up {} acknowledged events older than {} days",
                 count, days
             );
         }
+
+        EventsCommands::Cleanup { after, before, .. } => {
+            let now = chrono::Utc::now();
+            let after = after.map(|a| parse_time_bound(&a, now)).transpose()?;
+            let before = before.map(|b| parse_time_bound(&b, now)).transpose()?;
+            if let (Some(after), Some(before)) = (after, before) {
+                if after >= before {
+                    bail!(
+                        "--after ({}) must be earlier than --before ({})",
+                        after,
+                        before
+                    );
+                }
+            }
+
+            let count = db.cleanup_events_in_range(after, before)?;
+            println!(
+                "🧹 Cleaned up {} acknowledged events from {} to {}",
+                count,
+                after.map_or("the beginning".to_string(), |a| a.to_rfc3339()),
+                before.map_or("now".to_string(), |b| b.to_rfc3339())
+            );
+        }
     }
 
     Ok(())
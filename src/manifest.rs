@@ -0,0 +1,274 @@
+//! Portable repository manifests
+//!
+//! `manifest export` writes the synced repositories (name, clone URL, local
+//! path and default branch) to a YAML or JSON file that can be committed and
+//! replayed with `manifest apply` on another machine. Applying bypasses
+//! discovery: exactly the listed repositories are cloned (or synced, if
+//! already present).
+//!
+//! Paths are stored relative to `base_directory`, so a manifest still
+//! applies when the base directory or home directory differs. Checkouts
+//! outside the base directory are left out, and a manifest whose paths are
+//! absolute or contain `..` is rejected, so applying one never writes outside
+//! the base directory.
+
+use crate::discovery::{CloneMethod, RepoSource, RepoSpec};
+use crate::git::GitClient;
+use crate::state::StateDb;
+use crate::Config;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, warn};
+
+/// A list of repositories to reproduce
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub repositories: Vec<ManifestEntry>,
+}
+
+/// One repository in a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Repository name (owner/repo)
+    pub full_name: String,
+
+    /// URL to clone from
+    pub clone_url: String,
+
+    /// Checkout location (relative paths are under `base_directory`)
+    pub local_path: PathBuf,
+
+    /// Default branch of the remote (if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+}
+
+impl Manifest {
+    /// Build a manifest of the recorded repositories whose checkout exists
+    ///
+    /// The clone URL and default branch are read from each checkout's `origin`;
+    /// checkouts without an `origin` remote are left out.
+    pub async fn export(config: &Config, db: &StateDb, git_client: &GitClient) -> Result<Self> {
+        let base_dir = config.expanded_base_directory();
+        let mut repositories = Vec::new();
+
+        for repo in db.get_all_repos()? {
+            let Some(path) = repo.local_path.as_deref().map(PathBuf::from) else {
+                continue;
+            };
+            if !path.exists() {
+                continue;
+            }
+
+            let Some(clone_url) = git_client.get_remote_url(&path).await? else {
+                debug!(
+                    "Leaving {} out of the manifest: no origin remote",
+                    repo.full_name
+                );
+                continue;
+            };
+            let Some(local_path) = relative_to(&path, &base_dir) else {
+                warn!(
+                    "Leaving {} out of the manifest: {} is outside the base directory",
+                    repo.full_name,
+                    path.display()
+                );
+                continue;
+            };
+            let default_branch = git_client.get_remote_default_branch(&path).await?;
+
+            repositories.push(ManifestEntry {
+                full_name: repo.full_name,
+                clone_url,
+                local_path,
+                default_branch,
+            });
+        }
+
+        Ok(Self { repositories })
+    }
+
+    /// Serialize as YAML, or JSON with `json`
+    pub fn render(&self, json: bool) -> Result<String> {
+        if json {
+            serde_json::to_string_pretty(self).context("Failed to serialize manifest as JSON")
+        } else {
+            serde_yaml::to_string(self).context("Failed to serialize manifest as YAML")
+        }
+    }
+
+    /// Parse a YAML or JSON manifest, rejecting paths that would leave the
+    /// base directory
+    pub fn parse(content: &str) -> Result<Self> {
+        // JSON is valid YAML, so one parser covers both formats
+        let manifest: Self = serde_yaml::from_str(content).context("Failed to parse manifest")?;
+        for entry in &manifest.repositories {
+            if !is_contained(&entry.local_path) {
+                return Err(anyhow!(
+                    "Invalid local_path '{}' for {} in manifest: it must be relative to \
+                     base_directory and must not contain '..'",
+                    entry.local_path.display(),
+                    entry.full_name
+                ));
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Read a manifest file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid manifest: {}", path.display()))
+    }
+
+    /// Build the specs to sync, resolving relative paths against `base_directory`
+    pub fn to_specs(&self, config: &Config) -> Result<Vec<RepoSpec>> {
+        let base_dir = config.expanded_base_directory();
+        self.repositories
+            .iter()
+            .map(|entry| entry.to_spec(&base_dir))
+            .collect()
+    }
+}
+
+impl ManifestEntry {
+    fn to_spec(&self, base_dir: &Path) -> Result<RepoSpec> {
+        let (owner, name) = self
+            .full_name
+            .split_once('/')
+            .filter(|(owner, name)| !owner.is_empty() && !name.is_empty())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Invalid repository name '{}' in manifest: expected owner/repo",
+                    self.full_name
+                )
+            })?;
+
+        let clone_method = if self.clone_url.starts_with("https://") {
+            CloneMethod::Https
+        } else {
+            CloneMethod::Ssh
+        };
+
+        Ok(RepoSpec {
+            name: name.to_string(),
+            owner: owner.to_string(),
+            clone_url: self.clone_url.clone(),
+            clone_url_alt: None,
+            clone_method,
+            local_path: base_dir.join(&self.local_path),
+            is_fork: false,
+            is_archived: false,
+            size_bytes: None,
+            default_branch: self.default_branch.clone(),
+            provider: "manifest".to_string(),
+            source: RepoSource::Owned,
+            is_wiki: name.ends_with(".wiki"),
+            topics: Vec::new(),
+            pushed_at: None,
+            upstream_url: None,
+            upstream_branch: None,
+            html_url: None,
+            description: None,
+            provider_id: None,
+        })
+    }
+}
+
+/// `path` relative to `base` (None if it isn't under it)
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    path.strip_prefix(base).ok().map(Path::to_path_buf)
+}
+
+/// Whether a relative `path` names something below the directory it's joined to
+fn is_contained(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        && path
+            .components()
+            .any(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = Manifest {
+            repositories: vec![
+                ManifestEntry {
+                    full_name: "acme/api".to_string(),
+                    clone_url: "git@github.com:acme/api.git".to_string(),
+                    local_path: PathBuf::from("acme/api"),
+                    default_branch: Some("main".to_string()),
+                },
+                ManifestEntry {
+                    full_name: "me/notes".to_string(),
+                    clone_url: "https://github.com/me/notes.git".to_string(),
+                    local_path: PathBuf::from("me/notes"),
+                    default_branch: None,
+                },
+            ],
+        };
+
+        for json in [false, true] {
+            let rendered = manifest.render(json).unwrap();
+            assert_eq!(Manifest::parse(&rendered).unwrap(), manifest);
+        }
+
+        let config = Config {
+            base_directory: "/dev-root".to_string(),
+            ..Config::default()
+        };
+        let specs = manifest.to_specs(&config).unwrap();
+        assert_eq!(specs[0].full_name(), "acme/api");
+        assert_eq!(specs[0].local_path, PathBuf::from("/dev-root/acme/api"));
+        assert_eq!(specs[0].clone_method, CloneMethod::Ssh);
+        assert_eq!(specs[0].default_branch.as_deref(), Some("main"));
+        assert_eq!(specs[1].local_path, PathBuf::from("/dev-root/me/notes"));
+        assert_eq!(specs[1].clone_method, CloneMethod::Https);
+    }
+
+    #[test]
+    fn test_manifest_invalid_name() {
+        let manifest = Manifest::parse(
+            "repositories:\n  - full_name: no-slash\n    clone_url: x\n    local_path: y\n",
+        )
+        .unwrap();
+        assert!(manifest.to_specs(&Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_manifest_rejects_paths_outside_base() {
+        for local_path in ["/etc/cron.d", "../outside", "acme/../../outside", "."] {
+            let content = format!(
+                "repositories:\n  - full_name: acme/api\n    clone_url: x\n    local_path: {}\n",
+                local_path
+            );
+            let error = Manifest::parse(&content).unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid local_path"),
+                "{}: {}",
+                local_path,
+                error
+            );
+        }
+        assert!(Manifest::parse(
+            "repositories:\n  - full_name: acme/api\n    clone_url: x\n    local_path: ./acme/api\n"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let base = Path::new("/home/me/dev");
+        assert_eq!(
+            relative_to(Path::new("/home/me/dev/acme/api"), base),
+            Some(PathBuf::from("acme/api"))
+        );
+        assert_eq!(relative_to(Path::new("/srv/other"), base), None);
+    }
+}
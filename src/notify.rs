@@ -0,0 +1,26 @@
+//! Desktop notifications from the daemon (`notifications.desktop`)
+//!
+//! Notifications go through `notify-rust`: the freedesktop notification
+//! service over D-Bus on Linux and the BSDs, Notification Center on macOS and
+//! toast notifications on Windows. Where none can be shown (a headless
+//! daemon without a session bus) the failure is only logged. How often each
+//! subject is notified about is tracked in the state database.
+
+use tracing::debug;
+
+/// Application name shown with notifications
+const APP_NAME: &str = "RepoSentry";
+
+/// Show a desktop notification (a no-op where none can be shown)
+pub async fn show(title: &str, body: &str) {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname(APP_NAME).summary(title).body(body);
+
+    // Showing blocks on the platform's notification service
+    let result = tokio::task::spawn_blocking(move || notification.show().map(|_| ())).await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => debug!("Desktop notification unavailable: {}", e),
+        Err(e) => debug!("Desktop notification panicked: {}", e),
+    }
+}
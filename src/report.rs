@@ -0,0 +1,364 @@
+//! Persisted sync run reports (`sync --report`)
+//!
+//! A report is a self-contained record of one sync: when it ran, the
+//! configuration it ran with, the summary counts, every repository's outcome
+//! and duration, and the warnings raised along the way. JSON suits archiving
+//! and tooling; Markdown suits attaching to a ticket. The format is chosen by
+//! the file extension.
+
+use crate::git::SyncResult;
+use crate::sync::SyncSummary;
+use crate::Config;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Report file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Pick the format from a file extension (`.json`, `.md` or `.markdown`)
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(ReportFormat::Json),
+            Some(ext) if ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown") => {
+                Ok(ReportFormat::Markdown)
+            }
+            _ => Err(anyhow!(
+                "Unsupported report file '{}': use a .json or .md extension",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Everything recorded about one sync run
+#[derive(Debug, Serialize)]
+pub struct SyncReport {
+    pub started_at: DateTime<Utc>,
+    pub version: String,
+    pub summary: ReportSummary,
+    pub repositories: Vec<RepoReport>,
+    pub warnings: Vec<String>,
+    pub config: Config,
+}
+
+/// Aggregate counts of a sync run
+#[derive(Debug, Serialize)]
+pub struct ReportSummary {
+    pub total_repositories: usize,
+    pub successful_operations: usize,
+    pub failed_operations: usize,
+    pub skipped_operations: usize,
+    pub rebased_operations: usize,
+    pub merged_operations: usize,
+    pub duration_secs: f64,
+    pub rate_limit: Option<String>,
+    pub new_repositories: Vec<String>,
+}
+
+/// Outcome of one repository in a sync run
+#[derive(Debug, Serialize)]
+pub struct RepoReport {
+    pub path: PathBuf,
+    /// "cloned", "pulled", "rebased", "merged", "branch-switched",
+    /// "fetched-only", "up-to-date", "skipped" or "failed"
+    pub outcome: &'static str,
+    pub detail: Option<String>,
+    pub duration_secs: Option<f64>,
+}
+
+impl SyncReport {
+    /// Build the report of a completed sync
+    pub fn new(
+        started_at: DateTime<Utc>,
+        config: &Config,
+        summary: &SyncSummary,
+        warnings: Vec<String>,
+    ) -> Self {
+        let repositories = summary
+            .results
+            .iter()
+            .map(|result| {
+                let (outcome, detail) = describe_result(result);
+                RepoReport {
+                    path: result.path().to_path_buf(),
+                    outcome,
+                    detail,
+                    duration_secs: summary
+                        .durations
+                        .get(result.path())
+                        .map(|duration| duration.as_secs_f64()),
+                }
+            })
+            .collect();
+
+        Self {
+            started_at,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            summary: ReportSummary {
+                total_repositories: summary.total_repositories,
+                successful_operations: summary.successful_operations,
+                failed_operations: summary.failed_operations,
+                skipped_operations: summary.skipped_operations,
+                rebased_operations: summary.rebased_operations,
+                merged_operations: summary.merged_operations,
+                duration_secs: summary.duration.as_secs_f64(),
+                rate_limit: summary.rate_limit.clone(),
+                new_repositories: summary.new_repositories.clone(),
+            },
+            repositories,
+            warnings,
+            config: config.clone(),
+        }
+    }
+
+    /// Render the report in the given format
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Json => {
+                serde_json::to_string_pretty(self).context("Failed to serialize sync report")
+            }
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Write the report, in the format matching the file extension
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = self.render(ReportFormat::from_path(path)?)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write sync report: {}", path.display()))
+    }
+
+    fn to_markdown(&self) -> Result<String> {
+        let summary = &self.summary;
+        let mut out = String::new();
+
+        writeln!(out, "# RepoSentry sync report")?;
+        writeln!(out)?;
+        writeln!(out, "- Started: {}", self.started_at.to_rfc3339())?;
+        writeln!(out, "- Duration: {:.2}s", summary.duration_secs)?;
+        writeln!(out, "- RepoSentry version: {}", self.version)?;
+        if let Some(limit) = &summary.rate_limit {
+            writeln!(out, "- Rate limit: {}", limit)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "## Summary")?;
+        writeln!(out)?;
+        writeln!(out, "| Total | Successful | Failed | Skipped |")?;
+        writeln!(out, "|------:|-----------:|-------:|--------:|")?;
+        writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            summary.total_repositories,
+            summary.successful_operations,
+            summary.failed_operations,
+            summary.skipped_operations
+        )?;
+        if !summary.new_repositories.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "New repositories: {}",
+                summary.new_repositories.join(", ")
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "## Repositories")?;
+        writeln!(out)?;
+        writeln!(out, "| Repository | Outcome | Detail | Duration |")?;
+        writeln!(out, "|------------|---------|--------|---------:|")?;
+        for repo in &self.repositories {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                markdown_cell(&repo.path.display().to_string()),
+                repo.outcome,
+                markdown_cell(repo.detail.as_deref().unwrap_or("")),
+                repo.duration_secs
+                    .map(|secs| format!("{:.2}s", secs))
+                    .unwrap_or_default()
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "## Warnings")?;
+        writeln!(out)?;
+        if self.warnings.is_empty() {
+            writeln!(out, "None")?;
+        }
+        for warning in &self.warnings {
+            writeln!(out, "- {}", warning)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "## Configuration")?;
+        writeln!(out)?;
+        writeln!(out, "```yaml")?;
+        out.push_str(
+            &serde_yaml::to_string(&self.config).context("Failed to serialize configuration")?,
+        );
+        writeln!(out, "```")?;
+
+        Ok(out)
+    }
+}
+
+/// Note on auto-stashed local changes for a pull's detail
+fn stash_note(stashed: bool, stash_conflict: bool) -> &'static str {
+    if stash_conflict {
+        " (stashed local changes conflicted, kept in stash)"
+    } else if stashed {
+        " (stashed local changes)"
+    } else {
+        ""
+    }
+}
+
+/// Outcome label and detail of a sync result
+fn describe_result(result: &SyncResult) -> (&'static str, Option<String>) {
+    match result {
+        SyncResult::Cloned { branch, .. } => ("cloned", branch.clone()),
+        SyncResult::Pulled {
+            commits_updated,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => (
+            "pulled",
+            Some(format!(
+                "{} commits from {}{}",
+                commits_updated,
+                remote,
+                stash_note(*stashed, *stash_conflict)
+            )),
+        ),
+        SyncResult::Rebased {
+            commits_replayed,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => (
+            "rebased",
+            Some(format!(
+                "{} local commits replayed onto {}{}",
+                commits_replayed,
+                remote,
+                stash_note(*stashed, *stash_conflict)
+            )),
+        ),
+        SyncResult::Merged {
+            commits_merged,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => (
+            "merged",
+            Some(format!(
+                "{} commits merged from {}{}",
+                commits_merged,
+                remote,
+                stash_note(*stashed, *stash_conflict)
+            )),
+        ),
+        SyncResult::BranchSwitched {
+            from_branch,
+            to_branch,
+            commits_updated,
+            ..
+        } => (
+            "branch-switched",
+            Some(format!(
+                "{} -> {}, {} commits",
+                from_branch, to_branch, commits_updated
+            )),
+        ),
+        SyncResult::FetchedOnly { reason, .. } => ("fetched-only", Some(reason.clone())),
+        SyncResult::UpToDate { branch, .. } => ("up-to-date", branch.clone()),
+        SyncResult::Skipped { reason, .. } => ("skipped", Some(reason.clone())),
+        SyncResult::Failed { error, .. } => ("failed", Some(error.clone())),
+    }
+}
+
+/// Make text safe for a single Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[test]
+    fn test_report_format_from_path() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("run.json")).unwrap(),
+            ReportFormat::Json
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("run.MD")).unwrap(),
+            ReportFormat::Markdown
+        );
+        assert!(ReportFormat::from_path(Path::new("run.txt")).is_err());
+        assert!(ReportFormat::from_path(Path::new("run")).is_err());
+    }
+
+    #[test]
+    fn test_sync_report_render() {
+        let summary = SyncSummary {
+            total_repositories: 2,
+            successful_operations: 1,
+            failed_operations: 1,
+            skipped_operations: 0,
+            rebased_operations: 0,
+            merged_operations: 0,
+            duration: Duration::from_secs(3),
+            results: vec![
+                SyncResult::Cloned {
+                    path: PathBuf::from("/dev/acme/api"),
+                    branch: Some("main".to_string()),
+                },
+                SyncResult::Failed {
+                    path: PathBuf::from("/dev/acme/web"),
+                    error: "exit | 128\nfatal".to_string(),
+                },
+            ],
+            rate_limit: None,
+            new_repositories: vec!["acme/api".to_string()],
+            durations: HashMap::from([(PathBuf::from("/dev/acme/api"), Duration::from_secs(2))]),
+        };
+        let report = SyncReport::new(
+            Utc::now(),
+            &Config::default(),
+            &summary,
+            vec!["acme/web: origin mismatch".to_string()],
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["summary"]["failed_operations"], 1);
+        assert_eq!(json["repositories"][0]["outcome"], "cloned");
+        assert_eq!(json["repositories"][0]["duration_secs"], 2.0);
+        assert!(json["repositories"][1]["duration_secs"].is_null());
+        assert_eq!(json["config"]["sync"]["strategy"], "safe-pull");
+
+        let markdown = report.render(ReportFormat::Markdown).unwrap();
+        assert!(markdown.contains("| /dev/acme/api | cloned | main | 2.00s |"));
+        assert!(markdown.contains("| /dev/acme/web | failed | exit \\| 128 fatal |  |"));
+        assert!(markdown.contains("- acme/web: origin mismatch"));
+        assert!(markdown.contains("```yaml"));
+    }
+}
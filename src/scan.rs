@@ -0,0 +1,172 @@
+//! Bounded scanning of the base directory
+//!
+//! Commands that look at what is actually on disk (rather than what GitHub
+//! reports) walk `base_directory` to find checkouts. The walk is limited to
+//! `advanced.max_scan_depth` levels, never descends into a repository once it
+//! has been found, and skips hidden directories, symlinks and well-known
+//! bulky directories, so a base directory pointed at `/` or a deep tree can't
+//! turn into a full disk scan.
+
+use crate::{Config, RepoSpec};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Directories that never contain checkouts worth syncing
+const SKIPPED_DIRS: &[&str] = &["node_modules", "lost+found"];
+
+/// Check whether a directory is a git checkout (`.git` dir, or file for
+/// worktrees) or a bare repository such as a `mirror_mode` clone
+pub fn is_git_repo(path: &Path) -> bool {
+    path.join(".git").exists() || (path.join("HEAD").is_file() && path.join("objects").is_dir())
+}
+
+/// Find git repositories under the configured base directory
+pub fn find_local_repos(config: &Config) -> Vec<PathBuf> {
+    find_git_repos(
+        &config.expanded_base_directory(),
+        config.advanced.max_scan_depth,
+    )
+}
+
+/// Find repositories under the base directory that none of `repos` is checked out to
+pub fn find_unmanaged_repos(config: &Config, repos: &[RepoSpec]) -> Vec<PathBuf> {
+    let managed: HashSet<&Path> = repos.iter().map(|spec| spec.local_path.as_path()).collect();
+    find_local_repos(config)
+        .into_iter()
+        .filter(|path| !managed.contains(path.as_path()))
+        .collect()
+}
+
+/// Find git repositories at most `max_depth` levels below `base`
+///
+/// With the default `owner/repo` layout repositories sit two levels deep.
+/// Returns the repository paths sorted; unreadable directories are skipped.
+pub fn find_git_repos(base: &Path, max_depth: usize) -> Vec<PathBuf> {
+    if is_git_repo(base) {
+        return vec![base.to_path_buf()];
+    }
+
+    let mut repos = Vec::new();
+    let mut pending = vec![(base.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        if depth >= max_depth {
+            continue;
+        }
+
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!("Skipping unreadable directory {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+
+        for entry in entries.flatten() {
+            // file_type() doesn't follow symlinks, so links can't cause loops
+            if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                continue;
+            }
+            if is_skipped_dir(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+
+            let path = entry.path();
+            if is_git_repo(&path) {
+                repos.push(path);
+            } else {
+                pending.push((path, depth + 1));
+            }
+        }
+    }
+
+    repos.sort();
+    repos
+}
+
+/// Hidden and well-known bulky directories are never scanned
+fn is_skipped_dir(name: &str) -> bool {
+    name.starts_with('.') || SKIPPED_DIRS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::test_support::test_spec;
+    use tempfile::tempdir;
+
+    fn make_repo(path: &Path) {
+        fs::create_dir_all(path.join(".git")).unwrap();
+    }
+
+    #[test]
+    fn test_find_git_repos_is_bounded() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path();
+
+        make_repo(&base.join("solo"));
+        make_repo(&base.join("owner").join("repo"));
+        make_repo(&base.join("owner").join("repo").join("vendored"));
+        make_repo(&base.join(".cache").join("repo"));
+        make_repo(&base.join("node_modules").join("pkg"));
+        make_repo(&base.join("topic").join("owner").join("deep"));
+
+        assert_eq!(
+            find_git_repos(base, 2),
+            vec![base.join("owner").join("repo"), base.join("solo")]
+        );
+        assert_eq!(
+            find_git_repos(base, 3),
+            vec![
+                base.join("owner").join("repo"),
+                base.join("solo"),
+                base.join("topic").join("owner").join("deep"),
+            ]
+        );
+        assert!(find_git_repos(base, 0).is_empty());
+    }
+
+    #[test]
+    fn test_find_git_repos_finds_bare_mirrors() {
+        let temp_dir = tempdir().unwrap();
+        let mirror = temp_dir.path().join("owner").join("repo.git");
+        fs::create_dir_all(mirror.join("objects")).unwrap();
+        fs::write(mirror.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        assert_eq!(find_git_repos(temp_dir.path(), 2), vec![mirror]);
+    }
+
+    #[test]
+    fn test_find_unmanaged_repos() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config {
+            base_directory: temp_dir.path().display().to_string(),
+            ..Config::default()
+        };
+
+        let managed = temp_dir.path().join("test").join("repo");
+        let unmanaged = temp_dir.path().join("test").join("scratch");
+        make_repo(&managed);
+        make_repo(&unmanaged);
+
+        let spec = RepoSpec {
+            local_path: managed,
+            ..test_spec("repo")
+        };
+        assert_eq!(find_unmanaged_repos(&config, &[spec]), vec![unmanaged]);
+    }
+
+    #[test]
+    fn test_base_directory_is_a_repo() {
+        let temp_dir = tempdir().unwrap();
+        make_repo(temp_dir.path());
+        make_repo(&temp_dir.path().join("nested"));
+
+        assert_eq!(
+            find_git_repos(temp_dir.path(), 2),
+            vec![temp_dir.path().to_path_buf()]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Service manager integration (`daemon install`)
+//!
+//! Generates a systemd unit (Linux) or a launchd property list (macOS) that
+//! runs `reposentry daemon start --foreground`, so the service manager
+//! supervises the daemon instead of it forking into the background. The
+//! definition points at the absolute path of the running binary and the
+//! configuration file it was started with.
+
+use crate::config::get_log_file_path;
+use crate::Config;
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// systemd unit name
+pub const SYSTEMD_UNIT: &str = "reposentry.service";
+
+/// launchd job label (also the property list's file name)
+pub const LAUNCHD_LABEL: &str = "com.reposentry.daemon";
+
+/// Service manager a definition is generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceManager {
+    Systemd,
+    Launchd,
+}
+
+impl ServiceManager {
+    /// The service manager of the platform RepoSentry was built for
+    pub fn current() -> Result<Self> {
+        if cfg!(target_os = "macos") {
+            Ok(ServiceManager::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Ok(ServiceManager::Systemd)
+        } else {
+            Err(anyhow!(
+                "daemon install supports systemd (Linux) and launchd (macOS) only"
+            ))
+        }
+    }
+}
+
+/// Everything needed to write a service definition for the daemon
+#[derive(Debug, Clone)]
+pub struct ServiceDefinition {
+    pub manager: ServiceManager,
+    /// System-wide service (`--system`) rather than a per-user one
+    pub system: bool,
+    pub binary: PathBuf,
+    pub config_path: PathBuf,
+    pub working_directory: PathBuf,
+    /// Account a system-wide service runs as (the installing user)
+    pub user: Option<String>,
+    /// Where launchd sends the daemon's output (systemd uses the journal)
+    pub log_file: PathBuf,
+}
+
+impl ServiceDefinition {
+    /// Describe the daemon as started by this binary with `config`
+    pub fn for_current_process(config: &Config, system: bool) -> Result<Self> {
+        let binary = std::env::current_exe().context("Failed to locate the reposentry binary")?;
+        let config_path = match &config.source_path {
+            Some(path) => path.clone(),
+            None => Config::default_config_path()?,
+        };
+
+        Ok(Self {
+            manager: ServiceManager::current()?,
+            system,
+            binary: absolute(&binary),
+            config_path: absolute(&config_path),
+            working_directory: PathBuf::from(&config.base_directory),
+            user: std::env::var("USER").ok().filter(|user| !user.is_empty()),
+            log_file: PathBuf::from(get_log_file_path(&config.daemon.log_file)),
+        })
+    }
+
+    /// Where the definition is installed
+    pub fn install_path(&self) -> Result<PathBuf> {
+        match (self.manager, self.system) {
+            (ServiceManager::Systemd, true) => {
+                Ok(Path::new("/etc/systemd/system").join(SYSTEMD_UNIT))
+            }
+            (ServiceManager::Systemd, false) => {
+                let config_dir =
+                    dirs::config_dir().context("Failed to get user config directory")?;
+                Ok(config_dir.join("systemd").join("user").join(SYSTEMD_UNIT))
+            }
+            (ServiceManager::Launchd, true) => {
+                Ok(Path::new("/Library/LaunchDaemons").join(format!("{}.plist", LAUNCHD_LABEL)))
+            }
+            (ServiceManager::Launchd, false) => {
+                let home = dirs::home_dir().context("Failed to get home directory")?;
+                Ok(home
+                    .join("Library")
+                    .join("LaunchAgents")
+                    .join(format!("{}.plist", LAUNCHD_LABEL)))
+            }
+        }
+    }
+
+    /// The unit file or property list
+    pub fn render(&self) -> String {
+        match self.manager {
+            ServiceManager::Systemd => self.systemd_unit(),
+            ServiceManager::Launchd => self.launchd_plist(),
+        }
+    }
+
+    /// Command that enables and starts the installed service
+    pub fn enable_command(&self, path: &Path) -> String {
+        match (self.manager, self.system) {
+            (ServiceManager::Systemd, true) => format!(
+                "sudo systemctl daemon-reload && sudo systemctl enable --now {}",
+                SYSTEMD_UNIT
+            ),
+            (ServiceManager::Systemd, false) => format!(
+                "systemctl --user daemon-reload && systemctl --user enable --now {}",
+                SYSTEMD_UNIT
+            ),
+            (ServiceManager::Launchd, true) => {
+                format!("sudo launchctl load -w {}", path.display())
+            }
+            (ServiceManager::Launchd, false) => format!("launchctl load -w {}", path.display()),
+        }
+    }
+
+    /// Write the definition to `path`, refusing to replace an existing file
+    /// unless `force` is set
+    pub fn write(&self, path: &Path, force: bool) -> Result<()> {
+        if path.exists() && !force {
+            bail!(
+                "{} already exists (use --force to overwrite it)",
+                path.display()
+            );
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(path, self.render()).with_context(|| {
+            if self.system {
+                format!("Failed to write {} (--system needs root)", path.display())
+            } else {
+                format!("Failed to write {}", path.display())
+            }
+        })
+    }
+
+    fn systemd_unit(&self) -> String {
+        let user = match (&self.user, self.system) {
+            (Some(user), true) => format!("User={}\n", user),
+            _ => String::new(),
+        };
+        let wanted_by = if self.system {
+            "multi-user.target"
+        } else {
+            "default.target"
+        };
+
+        format!(
+            "[Unit]
+Description=RepoSentry repository synchronization daemon
+Wants=network-online.target
+After=network-online.target
+
+[Service]
+Type=simple
+{user}ExecStart={binary} --config {config} daemon start --foreground
+ExecReload=/bin/kill -HUP $MAINPID
+WorkingDirectory={working_directory}
+Restart=on-failure
+RestartSec=30
+
+[Install]
+WantedBy={wanted_by}
+",
+            user = user,
+            binary = systemd_quote(&self.binary),
+            config = systemd_quote(&self.config_path),
+            working_directory = systemd_escape(&self.working_directory),
+            wanted_by = wanted_by,
+        )
+    }
+
+    fn launchd_plist(&self) -> String {
+        let user = match (&self.user, self.system) {
+            (Some(user), true) => format!(
+                "    <key>UserName</key>\n    <string>{}</string>\n",
+                xml_escape(user)
+            ),
+            _ => String::new(),
+        };
+        let path = |path: &Path| xml_escape(&path.display().to_string());
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{binary}</string>
+        <string>--config</string>
+        <string>{config}</string>
+        <string>daemon</string>
+        <string>start</string>
+        <string>--foreground</string>
+    </array>
+{user}    <key>WorkingDirectory</key>
+    <string>{working_directory}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{log_file}</string>
+    <key>StandardErrorPath</key>
+    <string>{log_file}</string>
+</dict>
+</plist>
+"#,
+            label = LAUNCHD_LABEL,
+            binary = path(&self.binary),
+            config = path(&self.config_path),
+            user = user,
+            working_directory = path(&self.working_directory),
+            log_file = path(&self.log_file),
+        )
+    }
+}
+
+/// Make a path absolute without requiring it to exist
+fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Escape `%` specifiers in a path setting such as `WorkingDirectory`
+/// (which takes the path as is, unquoted)
+fn systemd_escape(path: &Path) -> String {
+    path.display().to_string().replace('%', "%%")
+}
+
+/// Quote a path as a single systemd command-line word, escaping the
+/// characters systemd would otherwise expand (`%` specifiers, `$` variables)
+fn systemd_quote(path: &Path) -> String {
+    let escaped = systemd_escape(path)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "$$");
+    format!("\"{}\"", escaped)
+}
+
+/// Escape text for an XML element
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn definition(manager: ServiceManager, system: bool) -> ServiceDefinition {
+        ServiceDefinition {
+            manager,
+            system,
+            binary: PathBuf::from("/opt/bin/reposentry"),
+            config_path: PathBuf::from("/home/ada/.config/reposentry/config.yml"),
+            working_directory: PathBuf::from("/home/ada/dev 100%"),
+            user: Some("ada".to_string()),
+            log_file: PathBuf::from("/home/ada/.local/share/reposentry/daemon.log"),
+        }
+    }
+
+    #[test]
+    fn test_systemd_unit() {
+        let unit = definition(ServiceManager::Systemd, false).render();
+        assert!(unit.contains(
+            "ExecStart=\"/opt/bin/reposentry\" --config \"/home/ada/.config/reposentry/config.yml\" daemon start --foreground"
+        ));
+        assert!(unit.contains("ExecReload=/bin/kill -HUP $MAINPID"));
+        assert!(unit.contains("WorkingDirectory=/home/ada/dev 100%%\n"));
+        assert!(unit.contains("WantedBy=default.target"));
+        assert!(!unit.contains("User="));
+
+        let unit = definition(ServiceManager::Systemd, true).render();
+        assert!(unit.contains("User=ada\n"));
+        assert!(unit.contains("WantedBy=multi-user.target"));
+    }
+
+    #[test]
+    fn test_launchd_plist() {
+        let mut service = definition(ServiceManager::Launchd, false);
+        service.working_directory = PathBuf::from("/Users/ada/R&D");
+        let plist = service.render();
+        assert!(plist.contains("<string>com.reposentry.daemon</string>"));
+        assert!(plist.contains("<string>/opt/bin/reposentry</string>"));
+        assert!(plist.contains("<string>--foreground</string>"));
+        assert!(plist.contains("<string>/Users/ada/R&amp;D</string>"));
+        assert!(!plist.contains("UserName"));
+
+        let plist = definition(ServiceManager::Launchd, true).render();
+        assert!(plist.contains("<key>UserName</key>\n    <string>ada</string>"));
+    }
+
+    #[test]
+    fn test_write_refuses_overwrite() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("systemd").join(SYSTEMD_UNIT);
+        let service = definition(ServiceManager::Systemd, false);
+
+        service.write(&path, false).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("[Service]"));
+
+        fs::write(&path, "edited").unwrap();
+        let error = service.write(&path, false).unwrap_err();
+        assert!(error.to_string().contains("--force"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+
+        service.write(&path, true).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("[Service]"));
+    }
+}
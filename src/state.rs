@@ -6,9 +6,10 @@
 //!
 //! The database is stored in XDG_DATA_HOME/reposentry/state.db
 
-use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 use tracing::{debug, info};
 
@@ -17,8 +18,12 @@ use tracing::{debug, info};
 pub enum EventType {
     /// Repository was cloned for the first time
     Cloned,
-    /// Repository was successfully pulled
+    /// Repository was successfully pulled (fast-forward)
     Pulled,
+    /// Local commits were rebased onto pulled changes
+    Rebased,
+    /// Pulled changes were merged with a merge commit
+    Merged,
     /// Branch was switched to track more recent activity
     BranchSwitch,
     /// Repository was skipped due to local uncommitted changes
@@ -29,6 +34,28 @@ pub enum EventType {
     SkippedAheadOfRemote,
     /// Sync operation failed with an error
     SyncError,
+    /// First encounter in fetch-only first-run mode (records what would be done)
+    FirstRunPreview,
+    /// Checkout's history depth doesn't match `sync.clone_depth`
+    DepthMismatch,
+    /// Checkout's origin doesn't match the expected clone URL
+    RemoteMismatch,
+    /// Repository discovered for the first time since the last sync
+    NewRepo,
+    /// Repository was skipped because it contains a `.reposentry-skip` file
+    SkippedSentinel,
+    /// Repository has failed `events.chronic_threshold` syncs in a row
+    ChronicFailure,
+    /// Repository was transferred to another owner, leaving its old checkout behind
+    RepoMoved,
+    /// Auto-stashed changes conflicted when re-applied after a pull
+    StashConflict,
+    /// Local branches whose upstream was deleted were removed (`sync.prune`)
+    BranchesPruned,
+    /// Clone skipped because the remote couldn't be reached (`advanced.precheck_remote`)
+    RemoteInaccessible,
+    /// Repository timed out `sync.timeout_strike_limit` syncs in a row
+    RepeatedTimeout,
 }
 
 impl EventType {
@@ -36,11 +63,24 @@ impl EventType {
         match self {
             EventType::Cloned => "cloned",
             EventType::Pulled => "pulled",
+            EventType::Rebased => "rebased",
+            EventType::Merged => "merged",
             EventType::BranchSwitch => "branch_switch",
             EventType::SkippedLocalChanges => "skipped_local_changes",
             EventType::SkippedConflicts => "skipped_conflicts",
             EventType::SkippedAheadOfRemote => "skipped_ahead_of_remote",
             EventType::SyncError => "sync_error",
+            EventType::FirstRunPreview => "first_run_preview",
+            EventType::DepthMismatch => "depth_mismatch",
+            EventType::RemoteMismatch => "remote_mismatch",
+            EventType::NewRepo => "new_repo",
+            EventType::SkippedSentinel => "skipped_sentinel",
+            EventType::ChronicFailure => "chronic_failure",
+            EventType::RepoMoved => "repo_moved",
+            EventType::StashConflict => "stash_conflict",
+            EventType::BranchesPruned => "branches_pruned",
+            EventType::RemoteInaccessible => "remote_inaccessible",
+            EventType::RepeatedTimeout => "repeated_timeout",
         }
     }
 
@@ -48,11 +88,24 @@ impl EventType {
         match s {
             "cloned" => Some(EventType::Cloned),
             "pulled" => Some(EventType::Pulled),
+            "rebased" => Some(EventType::Rebased),
+            "merged" => Some(EventType::Merged),
             "branch_switch" => Some(EventType::BranchSwitch),
             "skipped_local_changes" => Some(EventType::SkippedLocalChanges),
             "skipped_conflicts" => Some(EventType::SkippedConflicts),
             "skipped_ahead_of_remote" => Some(EventType::SkippedAheadOfRemote),
             "sync_error" => Some(EventType::SyncError),
+            "first_run_preview" => Some(EventType::FirstRunPreview),
+            "depth_mismatch" => Some(EventType::DepthMismatch),
+            "remote_mismatch" => Some(EventType::RemoteMismatch),
+            "new_repo" => Some(EventType::NewRepo),
+            "skipped_sentinel" => Some(EventType::SkippedSentinel),
+            "chronic_failure" => Some(EventType::ChronicFailure),
+            "repo_moved" => Some(EventType::RepoMoved),
+            "stash_conflict" => Some(EventType::StashConflict),
+            "branches_pruned" => Some(EventType::BranchesPruned),
+            "remote_inaccessible" => Some(EventType::RemoteInaccessible),
+            "repeated_timeout" => Some(EventType::RepeatedTimeout),
             _ => None,
         }
     }
@@ -62,11 +115,24 @@ impl EventType {
         match self {
             EventType::Cloned => Severity::Info,
             EventType::Pulled => Severity::Info,
+            EventType::Rebased => Severity::Info,
+            EventType::Merged => Severity::Info,
             EventType::BranchSwitch => Severity::Warning,
             EventType::SkippedLocalChanges => Severity::Warning,
             EventType::SkippedConflicts => Severity::Warning,
             EventType::SkippedAheadOfRemote => Severity::Info,
             EventType::SyncError => Severity::Error,
+            EventType::FirstRunPreview => Severity::Info,
+            EventType::DepthMismatch => Severity::Warning,
+            EventType::RemoteMismatch => Severity::Warning,
+            EventType::NewRepo => Severity::Info,
+            EventType::SkippedSentinel => Severity::Info,
+            EventType::ChronicFailure => Severity::Error,
+            EventType::RepoMoved => Severity::Warning,
+            EventType::StashConflict => Severity::Warning,
+            EventType::BranchesPruned => Severity::Info,
+            EventType::RemoteInaccessible => Severity::Warning,
+            EventType::RepeatedTimeout => Severity::Warning,
         }
     }
 }
@@ -142,6 +208,16 @@ pub struct RepoState {
     pub last_sync_status: RepoStatus,
     pub skip_reason: Option<String>,
     pub updated_at: DateTime<Utc>,
+    /// Discovery source (see `RepoSource`), if recorded
+    pub source: Option<String>,
+    /// Number of syncs in a row that ended in an error
+    pub consecutive_failures: u32,
+    /// What the last sync decided to do (e.g. `pull strategy=safe-pull remote=origin`)
+    pub last_action: Option<String>,
+    /// ID of the repository on its provider, if discovery reported one
+    pub provider_id: Option<u64>,
+    /// Provider `provider_id` belongs to (e.g. `github`)
+    pub provider: Option<String>,
 }
 
 /// A sync event record
@@ -155,6 +231,10 @@ pub struct SyncEvent {
     pub summary: String,
     pub details: Option<String>,
     pub acknowledged: bool,
+    /// Sync run the event was recorded in (None outside a sync)
+    pub run_id: Option<i64>,
+    /// Key-value tags for filtering (e.g. `org=acme`, `strategy=safe-pull`)
+    pub tags: BTreeMap<String, String>,
 }
 
 /// Builder for creating new sync events
@@ -164,6 +244,7 @@ pub struct SyncEventBuilder {
     event_type: EventType,
     summary: String,
     details: Option<String>,
+    tags: BTreeMap<String, String>,
 }
 
 impl SyncEventBuilder {
@@ -173,11 +254,23 @@ impl SyncEventBuilder {
             event_type,
             summary: summary.into(),
             details: None,
+            tags: BTreeMap::new(),
         }
     }
 
+    /// Set the repository (owner/repo), also tagging the event with its `org`
     pub fn repo(mut self, full_name: impl Into<String>) -> Self {
-        self.repo_full_name = Some(full_name.into());
+        let full_name = full_name.into();
+        if let Some((owner, _)) = full_name.split_once('/') {
+            self.tags.insert("org".to_string(), owner.to_string());
+        }
+        self.repo_full_name = Some(full_name);
+        self
+    }
+
+    /// Add a key-value tag, replacing any earlier value for `key`
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
         self
     }
 
@@ -194,9 +287,139 @@ impl SyncEventBuilder {
     }
 }
 
+/// Parse a `key=value` event tag filter (as given to `events list --tag`)
+pub fn parse_tag_filter(filter: &str) -> Result<(String, String)> {
+    let (key, value) = filter
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid tag filter '{}': expected key=value", filter))?;
+    let valid_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_key {
+        return Err(anyhow!(
+            "Invalid tag key '{}': use letters, digits, '_' and '-'",
+            key
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Columns of the `repositories` table read by `repo_state_from_row`, in order
+const REPO_COLUMNS: &str = "id, full_name, local_path, current_branch, last_sync_at, \
+     last_sync_status, skip_reason, updated_at, source, consecutive_failures, last_action, \
+     provider_id, provider";
+
+/// Columns of the `events` table read by `event_from_row`, in order
+const EVENT_COLUMNS: &str = "id, timestamp, repo_full_name, event_type, severity, summary, \
+     details, acknowledged, run_id, tags";
+
+/// Parse an RFC 3339 timestamp column
+fn timestamp_from_column(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Map a row selected with `REPO_COLUMNS` to a `RepoState`
+fn repo_state_from_row(row: &rusqlite::Row) -> rusqlite::Result<RepoState> {
+    Ok(RepoState {
+        id: row.get(0)?,
+        full_name: row.get(1)?,
+        local_path: row.get(2)?,
+        current_branch: row.get(3)?,
+        last_sync_at: timestamp_from_column(row.get(4)?),
+        last_sync_status: RepoStatus::parse(&row.get::<_, String>(5).unwrap_or_default()),
+        skip_reason: row.get(6)?,
+        updated_at: timestamp_from_column(row.get(7).ok()).unwrap_or_else(Utc::now),
+        source: row.get(8)?,
+        consecutive_failures: row.get(9)?,
+        last_action: row.get(10)?,
+        provider_id: row.get(11)?,
+        provider: row.get(12)?,
+    })
+}
+
+/// Map a row selected with `EVENT_COLUMNS` to a `SyncEvent`
+fn event_from_row(row: &rusqlite::Row) -> rusqlite::Result<SyncEvent> {
+    Ok(SyncEvent {
+        id: row.get(0)?,
+        timestamp: timestamp_from_column(row.get(1).ok()).unwrap_or_else(Utc::now),
+        repo_full_name: row.get(2)?,
+        event_type: EventType::parse(&row.get::<_, String>(3)?).unwrap_or(EventType::SyncError),
+        severity: Severity::parse(&row.get::<_, String>(4)?).unwrap_or(Severity::Info),
+        summary: row.get(5)?,
+        details: row.get(6)?,
+        acknowledged: row.get::<_, i32>(7)? != 0,
+        run_id: row.get(8)?,
+        tags: tags_from_column(row.get(9)?),
+    })
+}
+
+/// Tags stored as a JSON object in the `tags` column (none when NULL or invalid)
+fn tags_from_column(tags: Option<String>) -> BTreeMap<String, String> {
+    tags.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Write events as CSV (with a header row) for spreadsheet use
+///
+/// Columns: timestamp, repo_full_name, event_type, severity, summary,
+/// acknowledged.
+pub fn write_events_csv<W: std::io::Write>(events: &[SyncEvent], writer: W) -> Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+
+    csv_writer
+        .write_record([
+            "timestamp",
+            "repo_full_name",
+            "event_type",
+            "severity",
+            "summary",
+            "acknowledged",
+        ])
+        .context("Failed to write CSV header")?;
+
+    for event in events {
+        csv_writer
+            .write_record([
+                event.timestamp.to_rfc3339().as_str(),
+                event.repo_full_name.as_deref().unwrap_or(""),
+                event.event_type.as_str(),
+                event.severity.as_str(),
+                event.summary.as_str(),
+                if event.acknowledged { "true" } else { "false" },
+            ])
+            .context("Failed to write CSV record")?;
+    }
+
+    csv_writer.flush().context("Failed to flush CSV output")?;
+    Ok(())
+}
+
+/// Parse an event time bound: an RFC3339 timestamp, or a duration such as
+/// "2d" or "12h" meaning that long before `now`
+pub fn parse_time_bound(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value.trim()) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let secs = crate::config::parse_duration_secs(value).with_context(|| {
+        format!(
+            "Invalid time '{}': expected an RFC3339 timestamp or a duration like '2d'",
+            value
+        )
+    })?;
+    Ok(now - chrono::Duration::seconds(secs as i64))
+}
+
 /// State database manager
 pub struct StateDb {
     conn: Connection,
+    /// Record info-level events as already acknowledged
+    auto_ack_info: bool,
+    /// Sync run that newly recorded events belong to
+    current_run: Option<i64>,
 }
 
 impl StateDb {
@@ -216,7 +439,11 @@ impl StateDb {
         let conn = Connection::open(&path)
             .with_context(|| format!("Failed to open database at {}", path.display()))?;
 
-        let db = Self { conn };
+        let db = Self {
+            conn,
+            auto_ack_info: false,
+            current_run: None,
+        };
         db.initialize()?;
 
         info!("State database opened at {}", path.display());
@@ -226,11 +453,21 @@ impl StateDb {
     /// Open an in-memory database (for testing)
     pub fn open_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory().context("Failed to open in-memory database")?;
-        let db = Self { conn };
+        let db = Self {
+            conn,
+            auto_ack_info: false,
+            current_run: None,
+        };
         db.initialize()?;
         Ok(db)
     }
 
+    /// Record info-level events as already acknowledged (`events.auto_ack_info`)
+    pub fn with_auto_ack_info(mut self, enabled: bool) -> Self {
+        self.auto_ack_info = enabled;
+        self
+    }
+
     /// Get the default database path
     fn get_db_path() -> Result<PathBuf> {
         let data_dir = if let Ok(data_home) = std::env::var("XDG_DATA_HOME") {
@@ -258,7 +495,14 @@ impl StateDb {
                     last_sync_at TEXT,
                     last_sync_status TEXT DEFAULT 'unknown',
                     skip_reason TEXT,
-                    updated_at TEXT NOT NULL
+                    updated_at TEXT NOT NULL,
+                    source TEXT,
+                    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+                    last_action TEXT,
+                    consecutive_timeouts INTEGER NOT NULL DEFAULT 0,
+                    extended_timeout INTEGER,
+                    provider_id INTEGER,
+                    provider TEXT
                 );
 
                 -- Event log table
@@ -271,7 +515,16 @@ impl StateDb {
                     summary TEXT NOT NULL,
                     details TEXT,
                     acknowledged INTEGER DEFAULT 0,
-                    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+                    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                    run_id INTEGER,
+                    tags TEXT
+                );
+
+                -- One row per sync run, so events can be grouped by run
+                CREATE TABLE IF NOT EXISTS sync_runs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    started_at TEXT NOT NULL,
+                    finished_at TEXT
                 );
 
                 -- Indexes for efficient queries
@@ -280,14 +533,77 @@ impl StateDb {
                 CREATE INDEX IF NOT EXISTS idx_events_unack ON events(acknowledged, timestamp);
                 CREATE INDEX IF NOT EXISTS idx_events_repo ON events(repo_full_name, timestamp);
                 CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type, timestamp);
+
+                -- When the daemon last showed a desktop notification per subject
+                CREATE TABLE IF NOT EXISTS notifications (
+                    subject TEXT PRIMARY KEY,
+                    notified_at TEXT NOT NULL
+                );
+
+                -- Last discovery results (a single row, see advanced.cache_duration)
+                CREATE TABLE IF NOT EXISTS discovery_cache (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    fetched_at TEXT NOT NULL,
+                    config_key TEXT NOT NULL,
+                    specs TEXT NOT NULL
+                );
                 "#,
             )
             .context("Failed to initialize database schema")?;
 
+        self.migrate()?;
+
         debug!("Database schema initialized");
         Ok(())
     }
 
+    /// Add columns introduced after the initial schema to existing databases
+    fn migrate(&self) -> Result<()> {
+        self.add_column("repositories", "source", "TEXT")?;
+        self.add_column(
+            "repositories",
+            "consecutive_failures",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        self.add_column("repositories", "last_action", "TEXT")?;
+        self.add_column("events", "run_id", "INTEGER")?;
+        self.add_column("events", "tags", "TEXT")?;
+        self.add_column(
+            "repositories",
+            "consecutive_timeouts",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        self.add_column("repositories", "extended_timeout", "INTEGER")?;
+        self.add_column("repositories", "provider_id", "INTEGER")?;
+        self.add_column("repositories", "provider", "TEXT")?;
+        Ok(())
+    }
+
+    /// Add a column to a table unless it already exists
+    fn add_column(&self, table: &str, name: &str, definition: &str) -> Result<()> {
+        let exists: bool = self
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+                params![table, name],
+                |row| row.get::<_, i64>(0),
+            )
+            .with_context(|| format!("Failed to inspect {} table", table))?
+            > 0;
+
+        if !exists {
+            self.conn
+                .execute(
+                    &format!("ALTER TABLE {} ADD COLUMN {} {}", table, name, definition),
+                    [],
+                )
+                .with_context(|| format!("Failed to add {} column", name))?;
+            debug!("Migrated {} table: added {} column", table, name);
+        }
+
+        Ok(())
+    }
+
     // =========================================================================
     // Repository State Operations
     // =========================================================================
@@ -311,15 +627,20 @@ impl StateDb {
         self.conn
             .execute(
                 r#"
-                INSERT INTO repositories (full_name, local_path, current_branch, last_sync_at, last_sync_status, skip_reason, updated_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                INSERT INTO repositories (full_name, local_path, current_branch, last_sync_at, last_sync_status, skip_reason, updated_at, consecutive_failures)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CASE ?5 WHEN 'error' THEN 1 ELSE 0 END)
                 ON CONFLICT(full_name) DO UPDATE SET
                     local_path = COALESCE(?2, local_path),
                     current_branch = COALESCE(?3, current_branch),
                     last_sync_at = COALESCE(?4, last_sync_at),
                     last_sync_status = ?5,
                     skip_reason = ?6,
-                    updated_at = ?7
+                    updated_at = ?7,
+                    consecutive_failures = CASE ?5
+                        WHEN 'error' THEN consecutive_failures + 1
+                        WHEN 'ok' THEN 0
+                        ELSE consecutive_failures
+                    END
                 "#,
                 params![
                     full_name,
@@ -337,39 +658,172 @@ impl StateDb {
         Ok(())
     }
 
+    /// Record where a repository is checked out and which branch it's on,
+    /// as seen by `sync --analyze-only`
+    ///
+    /// Unlike `upsert_repo`, this leaves the sync status, `last_sync_at` and
+    /// the failure streak alone: nothing was synced. Creates the repository
+    /// record if it does not exist yet.
+    pub fn record_analysis(
+        &self,
+        full_name: &str,
+        local_path: &str,
+        current_branch: Option<&str>,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO repositories (full_name, local_path, current_branch, updated_at, last_action)
+                VALUES (?1, ?2, ?3, ?4, 'analyze')
+                ON CONFLICT(full_name) DO UPDATE SET
+                    local_path = ?2,
+                    current_branch = COALESCE(?3, current_branch),
+                    updated_at = ?4,
+                    last_action = 'analyze'
+                "#,
+                params![full_name, local_path, current_branch, now],
+            )
+            .context("Failed to record repository analysis")?;
+
+        debug!("Recorded analysis of {}", full_name);
+        Ok(())
+    }
+
+    /// Record how a repository was discovered
+    ///
+    /// Creates the repository record if it does not exist yet.
+    pub fn set_repo_source(&self, full_name: &str, source: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO repositories (full_name, source, updated_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(full_name) DO UPDATE SET source = ?2
+                "#,
+                params![full_name, source, now],
+            )
+            .context("Failed to update repository source")?;
+
+        Ok(())
+    }
+
+    /// Record a repository's ID on its provider (IDs are only unique per provider)
+    ///
+    /// Creates the repository record if it does not exist yet.
+    pub fn set_provider_id(&self, full_name: &str, provider: &str, provider_id: u64) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO repositories (full_name, provider_id, provider, updated_at)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT(full_name) DO UPDATE SET provider_id = ?2, provider = ?3
+                "#,
+                params![full_name, provider_id as i64, provider, now],
+            )
+            .context("Failed to update repository provider ID")?;
+
+        Ok(())
+    }
+
+    /// Record the action the last sync took for a repository
+    ///
+    /// Creates the repository record if it does not exist yet.
+    pub fn set_last_action(&self, full_name: &str, action: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO repositories (full_name, last_action, updated_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(full_name) DO UPDATE SET last_action = ?2
+                "#,
+                params![full_name, action, now],
+            )
+            .context("Failed to update repository action")?;
+
+        Ok(())
+    }
+
+    /// Count a sync that timed out, or reset the count after one that didn't
+    ///
+    /// Returns the number of syncs in a row that timed out. Creates the
+    /// repository record if it does not exist yet.
+    pub fn record_timeout_outcome(&self, full_name: &str, timed_out: bool) -> Result<u32> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO repositories (full_name, consecutive_timeouts, updated_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(full_name) DO UPDATE SET
+                    consecutive_timeouts = CASE ?2
+                        WHEN 1 THEN consecutive_timeouts + 1
+                        ELSE 0
+                    END
+                "#,
+                params![full_name, timed_out as i64, now],
+            )
+            .context("Failed to update repository timeouts")?;
+
+        self.conn
+            .query_row(
+                "SELECT consecutive_timeouts FROM repositories WHERE full_name = ?1",
+                params![full_name],
+                |row| row.get(0),
+            )
+            .context("Failed to query repository timeouts")
+    }
+
+    /// Timeout in seconds set by `sync.auto_extend_timeout`, if any
+    pub fn extended_timeout(&self, full_name: &str) -> Result<Option<u64>> {
+        let timeout: Option<Option<i64>> = self
+            .conn
+            .query_row(
+                "SELECT extended_timeout FROM repositories WHERE full_name = ?1",
+                params![full_name],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to query extended timeout")?;
+
+        Ok(timeout.flatten().map(|secs| secs as u64))
+    }
+
+    /// Extend a repository's timeout, starting its timeout count afresh
+    pub fn set_extended_timeout(&self, full_name: &str, secs: u64) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE repositories SET extended_timeout = ?2, consecutive_timeouts = 0 WHERE full_name = ?1",
+                params![full_name, secs as i64],
+            )
+            .context("Failed to extend repository timeout")?;
+
+        Ok(())
+    }
+
     /// Get a repository's current state
     pub fn get_repo(&self, full_name: &str) -> Result<Option<RepoState>> {
         let result = self
             .conn
             .query_row(
-                r#"
-                SELECT id, full_name, local_path, current_branch, last_sync_at, last_sync_status, skip_reason, updated_at
-                FROM repositories
-                WHERE full_name = ?1
-                "#,
+                &format!(
+                    r#"
+                    SELECT {}
+                    FROM repositories
+                    WHERE full_name = ?1
+                    "#,
+                    REPO_COLUMNS
+                ),
                 params![full_name],
-                |row| {
-                    Ok(RepoState {
-                        id: row.get(0)?,
-                        full_name: row.get(1)?,
-                        local_path: row.get(2)?,
-                        current_branch: row.get(3)?,
-                        last_sync_at: row
-                            .get::<_, Option<String>>(4)?
-                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                            .map(|dt| dt.with_timezone(&Utc)),
-                        last_sync_status: RepoStatus::parse(
-                            &row.get::<_, String>(5).unwrap_or_default(),
-                        ),
-                        skip_reason: row.get(6)?,
-                        updated_at: row
-                            .get::<_, String>(7)
-                            .ok()
-                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(Utc::now),
-                    })
-                },
+                repo_state_from_row,
             )
             .optional()
             .context("Failed to query repository")?;
@@ -377,40 +831,64 @@ impl StateDb {
         Ok(result)
     }
 
+    /// Full names of all repositories in the database
+    pub fn repo_names(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT full_name FROM repositories")?;
+
+        let names = stmt
+            .query_map([], |row| row.get(0))
+            .context("Failed to query repository names")?
+            .collect::<Result<HashSet<_>, _>>()
+            .context("Failed to collect repository names")?;
+
+        Ok(names)
+    }
+
+    /// Get all repositories
+    pub fn get_all_repos(&self) -> Result<Vec<RepoState>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT {}
+            FROM repositories
+            ORDER BY full_name
+            "#,
+            REPO_COLUMNS
+        ))?;
+
+        let repos = stmt
+            .query_map([], repo_state_from_row)
+            .context("Failed to query repositories")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect repositories")?;
+
+        Ok(repos)
+    }
+
+    /// Remove a repository's record (its events are kept)
+    pub fn delete_repo(&self, full_name: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM repositories WHERE full_name = ?1",
+                params![full_name],
+            )
+            .context("Failed to delete repository")?;
+        Ok(())
+    }
+
     /// Get all repositories with a specific status
     pub fn get_repos_by_status(&self, status: RepoStatus) -> Result<Vec<RepoState>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             r#"
-            SELECT id, full_name, local_path, current_branch, last_sync_at, last_sync_status, skip_reason, updated_at
+            SELECT {}
             FROM repositories
             WHERE last_sync_status = ?1
             ORDER BY updated_at DESC
             "#,
-        )?;
+            REPO_COLUMNS
+        ))?;
 
         let repos = stmt
-            .query_map(params![status.as_str()], |row| {
-                Ok(RepoState {
-                    id: row.get(0)?,
-                    full_name: row.get(1)?,
-                    local_path: row.get(2)?,
-                    current_branch: row.get(3)?,
-                    last_sync_at: row
-                        .get::<_, Option<String>>(4)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    last_sync_status: RepoStatus::parse(
-                        &row.get::<_, String>(5).unwrap_or_default(),
-                    ),
-                    skip_reason: row.get(6)?,
-                    updated_at: row
-                        .get::<_, String>(7)
-                        .ok()
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(Utc::now),
-                })
-            })
+            .query_map(params![status.as_str()], repo_state_from_row)
             .context("Failed to query repositories")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect repositories")?;
@@ -420,38 +898,18 @@ impl StateDb {
 
     /// Get repositories that have issues (skipped or error)
     pub fn get_repos_with_issues(&self) -> Result<Vec<RepoState>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             r#"
-            SELECT id, full_name, local_path, current_branch, last_sync_at, last_sync_status, skip_reason, updated_at
+            SELECT {}
             FROM repositories
             WHERE last_sync_status IN ('skipped', 'error')
             ORDER BY updated_at DESC
             "#,
-        )?;
+            REPO_COLUMNS
+        ))?;
 
         let repos = stmt
-            .query_map([], |row| {
-                Ok(RepoState {
-                    id: row.get(0)?,
-                    full_name: row.get(1)?,
-                    local_path: row.get(2)?,
-                    current_branch: row.get(3)?,
-                    last_sync_at: row
-                        .get::<_, Option<String>>(4)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    last_sync_status: RepoStatus::parse(
-                        &row.get::<_, String>(5).unwrap_or_default(),
-                    ),
-                    skip_reason: row.get(6)?,
-                    updated_at: row
-                        .get::<_, String>(7)
-                        .ok()
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(Utc::now),
-                })
-            })
+            .query_map([], repo_state_from_row)
             .context("Failed to query repositories with issues")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect repositories")?;
@@ -459,6 +917,89 @@ impl StateDb {
         Ok(repos)
     }
 
+    /// Get repositories that have failed at least `threshold` syncs in a row
+    pub fn get_chronic_repos(&self, threshold: u32) -> Result<Vec<RepoState>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT {}
+            FROM repositories
+            WHERE consecutive_failures >= ?1
+            ORDER BY consecutive_failures DESC, full_name ASC
+            "#,
+            REPO_COLUMNS
+        ))?;
+
+        let repos = stmt
+            .query_map(params![threshold.max(1)], repo_state_from_row)
+            .context("Failed to query chronically failing repositories")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect repositories")?;
+
+        Ok(repos)
+    }
+
+    /// Get repositories whose state was updated after `since`
+    pub fn get_repos_updated_since(&self, since: DateTime<Utc>) -> Result<Vec<RepoState>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT {}
+            FROM repositories
+            WHERE updated_at > ?1
+            ORDER BY updated_at ASC
+            "#,
+            REPO_COLUMNS
+        ))?;
+
+        let repos = stmt
+            .query_map(params![since.to_rfc3339()], repo_state_from_row)
+            .context("Failed to query updated repositories")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect repositories")?;
+
+        Ok(repos)
+    }
+
+    // =========================================================================
+    // Sync Run Operations
+    // =========================================================================
+
+    /// Start a sync run: events recorded until `finish_run` are tagged with its ID
+    pub fn start_run(&mut self) -> Result<i64> {
+        self.conn
+            .execute(
+                "INSERT INTO sync_runs (started_at) VALUES (?1)",
+                params![Utc::now().to_rfc3339()],
+            )
+            .context("Failed to record sync run")?;
+
+        let id = self.conn.last_insert_rowid();
+        self.current_run = Some(id);
+        debug!("Started sync run {}", id);
+        Ok(id)
+    }
+
+    /// Finish the current sync run
+    pub fn finish_run(&mut self) -> Result<()> {
+        let Some(id) = self.current_run.take() else {
+            return Ok(());
+        };
+
+        self.conn
+            .execute(
+                "UPDATE sync_runs SET finished_at = ?1 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), id],
+            )
+            .context("Failed to finish sync run")?;
+        Ok(())
+    }
+
+    /// ID of the most recently started sync run (None if there were none)
+    pub fn latest_run_id(&self) -> Result<Option<i64>> {
+        self.conn
+            .query_row("SELECT MAX(id) FROM sync_runs", [], |row| row.get(0))
+            .context("Failed to query latest sync run")
+    }
+
     // =========================================================================
     // Event Operations
     // =========================================================================
@@ -467,12 +1008,18 @@ impl StateDb {
     pub fn record_event(&self, builder: SyncEventBuilder) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
         let severity = builder.event_type.severity();
+        let acknowledged = self.auto_ack_info && severity == Severity::Info;
+        let tags = if builder.tags.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&builder.tags).context("Failed to serialize event tags")?)
+        };
 
         self.conn
             .execute(
                 r#"
-                INSERT INTO events (timestamp, repo_full_name, event_type, severity, summary, details)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                INSERT INTO events (timestamp, repo_full_name, event_type, severity, summary, details, acknowledged, run_id, tags)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
                 "#,
                 params![
                     now,
@@ -481,6 +1028,9 @@ impl StateDb {
                     severity.as_str(),
                     builder.summary,
                     builder.details,
+                    acknowledged as i32,
+                    self.current_run,
+                    tags,
                 ],
             )
             .context("Failed to record event")?;
@@ -496,14 +1046,18 @@ impl StateDb {
 
     /// Get unacknowledged events
     pub fn get_unacknowledged_events(&self) -> Result<Vec<SyncEvent>> {
-        self.get_events_with_filter(Some(false), None, None)
+        self.get_events_with_filter(Some(false), None, None, &[], None)
     }
 
     /// Get recent events with optional filters
+    ///
+    /// `tags` are `(key, value)` pairs an event must all carry.
     pub fn get_events_with_filter(
         &self,
         acknowledged: Option<bool>,
         event_type: Option<EventType>,
+        run_id: Option<i64>,
+        tags: &[(String, String)],
         limit: Option<u32>,
     ) -> Result<Vec<SyncEvent>> {
         let mut conditions = Vec::new();
@@ -517,6 +1071,19 @@ impl StateDb {
             conditions.push(format!("event_type = ?{}", param_values.len() + 1));
             param_values.push(Box::new(et.as_str().to_string()));
         }
+        if let Some(run_id) = run_id {
+            conditions.push(format!("run_id = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(run_id));
+        }
+        for (key, value) in tags {
+            conditions.push(format!(
+                "json_extract(tags, ?{}) = ?{}",
+                param_values.len() + 1,
+                param_values.len() + 2
+            ));
+            param_values.push(Box::new(format!("$.\"{}\"", key)));
+            param_values.push(Box::new(value.clone()));
+        }
 
         let where_clause = if conditions.is_empty() {
             String::new()
@@ -528,13 +1095,13 @@ impl StateDb {
 
         let sql = format!(
             r#"
-            SELECT id, timestamp, repo_full_name, event_type, severity, summary, details, acknowledged
+            SELECT {}
             FROM events
             {}
             ORDER BY timestamp DESC
             {}
             "#,
-            where_clause, limit_clause
+            EVENT_COLUMNS, where_clause, limit_clause
         );
 
         let mut stmt = self.conn.prepare(&sql)?;
@@ -543,24 +1110,7 @@ impl StateDb {
             param_values.iter().map(|p| p.as_ref()).collect();
 
         let events = stmt
-            .query_map(param_refs.as_slice(), |row| {
-                Ok(SyncEvent {
-                    id: row.get(0)?,
-                    timestamp: row
-                        .get::<_, String>(1)
-                        .ok()
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(Utc::now),
-                    repo_full_name: row.get(2)?,
-                    event_type: EventType::parse(&row.get::<_, String>(3)?)
-                        .unwrap_or(EventType::SyncError),
-                    severity: Severity::parse(&row.get::<_, String>(4)?).unwrap_or(Severity::Info),
-                    summary: row.get(5)?,
-                    details: row.get(6)?,
-                    acknowledged: row.get::<_, i32>(7)? != 0,
-                })
-            })
+            .query_map(param_refs.as_slice(), event_from_row)
             .context("Failed to query events")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect events")?;
@@ -577,36 +1127,19 @@ impl StateDb {
         let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
         let sql = format!(
             r#"
-            SELECT id, timestamp, repo_full_name, event_type, severity, summary, details, acknowledged
+            SELECT {}
             FROM events
             WHERE repo_full_name = ?1
             ORDER BY timestamp DESC
             {}
             "#,
-            limit_clause
+            EVENT_COLUMNS, limit_clause
         );
 
         let mut stmt = self.conn.prepare(&sql)?;
 
         let events = stmt
-            .query_map(params![repo_full_name], |row| {
-                Ok(SyncEvent {
-                    id: row.get(0)?,
-                    timestamp: row
-                        .get::<_, String>(1)
-                        .ok()
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(Utc::now),
-                    repo_full_name: row.get(2)?,
-                    event_type: EventType::parse(&row.get::<_, String>(3)?)
-                        .unwrap_or(EventType::SyncError),
-                    severity: Severity::parse(&row.get::<_, String>(4)?).unwrap_or(Severity::Info),
-                    summary: row.get(5)?,
-                    details: row.get(6)?,
-                    acknowledged: row.get::<_, i32>(7)? != 0,
-                })
-            })
+            .query_map(params![repo_full_name], event_from_row)
             .context("Failed to query events for repo")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect events")?;
@@ -614,6 +1147,36 @@ impl StateDb {
         Ok(events)
     }
 
+    /// Get events recorded after the event with ID `after_id`, oldest first
+    pub fn get_events_after(&self, after_id: i64) -> Result<Vec<SyncEvent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT {}
+            FROM events
+            WHERE id > ?1
+            ORDER BY id ASC
+            "#,
+            EVENT_COLUMNS
+        ))?;
+
+        let events = stmt
+            .query_map(params![after_id], event_from_row)
+            .context("Failed to query new events")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect events")?;
+
+        Ok(events)
+    }
+
+    /// ID of the most recently recorded event (0 if there are none)
+    pub fn latest_event_id(&self) -> Result<i64> {
+        self.conn
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM events", [], |row| {
+                row.get(0)
+            })
+            .context("Failed to query latest event ID")
+    }
+
     /// Acknowledge an event by ID
     pub fn acknowledge_event(&self, event_id: i64) -> Result<()> {
         self.conn
@@ -681,6 +1244,116 @@ impl StateDb {
             .context("Failed to cleanup old events")?;
         Ok(count as u64)
     }
+
+    /// Delete acknowledged events from `after` (inclusive) up to `before`
+    /// (exclusive); an open bound extends the window to that end
+    pub fn cleanup_events_in_range(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<u64> {
+        let count = self
+            .conn
+            .execute(
+                r#"
+                DELETE FROM events
+                WHERE acknowledged = 1
+                  AND (?1 IS NULL OR timestamp >= ?1)
+                  AND (?2 IS NULL OR timestamp < ?2)
+                "#,
+                params![
+                    after.map(|time| time.to_rfc3339()),
+                    before.map(|time| time.to_rfc3339())
+                ],
+            )
+            .context("Failed to cleanup events in range")?;
+        Ok(count as u64)
+    }
+
+    /// Rebuild the database file to reclaim space freed by deletions
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn
+            .execute_batch("VACUUM")
+            .context("Failed to vacuum state database")
+    }
+
+    // =========================================================================
+    // Desktop Notifications
+    // =========================================================================
+
+    /// Record a desktop notification about `subject` (a repository, or the
+    /// sync cycle as a whole) at `now`, unless one was already recorded on
+    /// the same local day
+    ///
+    /// Returns whether the notification should be shown.
+    pub fn claim_notification(&self, subject: &str, now: DateTime<Local>) -> Result<bool> {
+        let last: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT notified_at FROM notifications WHERE subject = ?1",
+                params![subject],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to load notification time")?;
+
+        let notified_today = last
+            .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+            .is_some_and(|at| at.with_timezone(&Local).date_naive() == now.date_naive());
+        if notified_today {
+            return Ok(false);
+        }
+
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO notifications (subject, notified_at) VALUES (?1, ?2)",
+                params![subject, now.with_timezone(&Utc).to_rfc3339()],
+            )
+            .context("Failed to record notification")?;
+        Ok(true)
+    }
+
+    // =========================================================================
+    // Discovery Cache
+    // =========================================================================
+
+    /// Replace the cached discovery results (`specs` is their JSON form)
+    ///
+    /// `config_key` identifies the configuration they were discovered with.
+    pub fn save_discovery_cache(&self, config_key: &str, specs: &str) -> Result<()> {
+        self.conn
+            .execute(
+                r#"
+                INSERT OR REPLACE INTO discovery_cache (id, fetched_at, config_key, specs)
+                VALUES (1, ?1, ?2, ?3)
+                "#,
+                params![Utc::now().to_rfc3339(), config_key, specs],
+            )
+            .context("Failed to save discovery cache")?;
+        Ok(())
+    }
+
+    /// Cached discovery results and when they were fetched, if they were
+    /// discovered with `config_key`
+    pub fn load_discovery_cache(
+        &self,
+        config_key: &str,
+    ) -> Result<Option<(DateTime<Utc>, String)>> {
+        let row: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT fetched_at, specs FROM discovery_cache WHERE id = 1 AND config_key = ?1",
+                params![config_key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to load discovery cache")?;
+
+        Ok(row.and_then(|(fetched_at, specs)| {
+            let fetched_at = DateTime::parse_from_rfc3339(&fetched_at).ok()?;
+            Some((fetched_at.with_timezone(&Utc), specs))
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -749,6 +1422,83 @@ mod tests {
         assert_eq!(repo.skip_reason, Some("local changes".to_string()));
     }
 
+    #[test]
+    fn test_repo_source() {
+        let db = StateDb::open_in_memory().unwrap();
+
+        // Source recorded before the first sync creates the record
+        db.set_repo_source("acme/tool", "org:acme").unwrap();
+        let repo = db.get_repo("acme/tool").unwrap().unwrap();
+        assert_eq!(repo.source, Some("org:acme".to_string()));
+        assert_eq!(repo.last_sync_status, RepoStatus::Unknown);
+
+        // Sync results preserve the source
+        db.upsert_repo(
+            "acme/tool",
+            Some("/path"),
+            Some("main"),
+            RepoStatus::Ok,
+            None,
+        )
+        .unwrap();
+        let repo = db.get_repo("acme/tool").unwrap().unwrap();
+        assert_eq!(repo.source, Some("org:acme".to_string()));
+        assert_eq!(repo.last_sync_status, RepoStatus::Ok);
+    }
+
+    #[test]
+    fn test_migrate_adds_source_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.db");
+
+        // Database created before the source column existed
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE repositories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                full_name TEXT UNIQUE NOT NULL,
+                local_path TEXT,
+                current_branch TEXT,
+                last_sync_at TEXT,
+                last_sync_status TEXT DEFAULT 'unknown',
+                skip_reason TEXT,
+                updated_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .unwrap();
+        drop(conn);
+
+        let db = StateDb::open_at(path).unwrap();
+        db.set_repo_source("owner/repo", "owned").unwrap();
+        let repo = db.get_repo("owner/repo").unwrap().unwrap();
+        assert_eq!(repo.source, Some("owned".to_string()));
+        assert_eq!(repo.consecutive_failures, 0);
+        assert_eq!(repo.last_action, None);
+    }
+
+    #[test]
+    fn test_last_action() {
+        let db = StateDb::open_in_memory().unwrap();
+
+        db.upsert_repo("owner/repo", None, Some("main"), RepoStatus::Ok, None)
+            .unwrap();
+        db.set_last_action(
+            "owner/repo",
+            "pull strategy=safe-pull remote=origin commits=2",
+        )
+        .unwrap();
+
+        let repo = db.get_repo("owner/repo").unwrap().unwrap();
+        assert_eq!(
+            repo.last_action.as_deref(),
+            Some("pull strategy=safe-pull remote=origin commits=2")
+        );
+        assert_eq!(repo.current_branch, Some("main".to_string()));
+        assert_eq!(repo.last_sync_status, RepoStatus::Ok);
+    }
+
     #[test]
     fn test_record_and_get_events() {
         let db = StateDb::open_in_memory().unwrap();
@@ -770,6 +1520,130 @@ mod tests {
         assert!(!events[0].acknowledged);
     }
 
+    #[test]
+    fn test_get_events_after() {
+        let db = StateDb::open_in_memory().unwrap();
+        assert_eq!(db.latest_event_id().unwrap(), 0);
+
+        let first = db
+            .record_event(SyncEventBuilder::new(EventType::Pulled, "first").repo("owner/a"))
+            .unwrap();
+        assert_eq!(db.latest_event_id().unwrap(), first);
+
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "second").repo("owner/b"))
+            .unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "third").repo("owner/c"))
+            .unwrap();
+
+        let summaries: Vec<String> = db
+            .get_events_after(first)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.summary)
+            .collect();
+        assert_eq!(summaries, vec!["second", "third"]);
+    }
+
+    #[test]
+    fn test_event_tags() {
+        let db = StateDb::open_in_memory().unwrap();
+        db.record_event(
+            SyncEventBuilder::new(EventType::Pulled, "Pulled 3 commits")
+                .repo("acme/api")
+                .tag("strategy", "safe-pull"),
+        )
+        .unwrap();
+        db.record_event(
+            SyncEventBuilder::new(EventType::Pulled, "Pulled 1 commits").repo("other/web"),
+        )
+        .unwrap();
+
+        let tags = |filters: &[(&str, &str)]| -> Vec<(String, String)> {
+            filters
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        let events = db
+            .get_events_with_filter(None, None, None, &tags(&[("org", "acme")]), None)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].tags.get("org").map(String::as_str), Some("acme"));
+        assert_eq!(
+            events[0].tags.get("strategy").map(String::as_str),
+            Some("safe-pull")
+        );
+
+        let filters = tags(&[("org", "acme"), ("strategy", "rebase")]);
+        let events = db
+            .get_events_with_filter(None, None, None, &filters, None)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tag_filter() {
+        assert_eq!(
+            parse_tag_filter("org=acme").unwrap(),
+            ("org".to_string(), "acme".to_string())
+        );
+        assert_eq!(
+            parse_tag_filter("size=a=b").unwrap(),
+            ("size".to_string(), "a=b".to_string())
+        );
+        assert!(parse_tag_filter("org").is_err());
+        assert!(parse_tag_filter("=acme").is_err());
+        assert!(parse_tag_filter("a.b=c").is_err());
+    }
+
+    #[test]
+    fn test_sync_runs() {
+        let mut db = StateDb::open_in_memory().unwrap();
+        assert_eq!(db.latest_run_id().unwrap(), None);
+
+        db.record_event(SyncEventBuilder::new(EventType::Cloned, "before"))
+            .unwrap();
+        let first = db.start_run().unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Cloned, "first run"))
+            .unwrap();
+        db.finish_run().unwrap();
+        let second = db.start_run().unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "second run"))
+            .unwrap();
+        db.finish_run().unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "after"))
+            .unwrap();
+
+        assert_eq!(db.latest_run_id().unwrap(), Some(second));
+        let summaries = |run_id| {
+            db.get_events_with_filter(None, None, Some(run_id), &[], None)
+                .unwrap()
+                .into_iter()
+                .map(|event| event.summary)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summaries(first), vec!["first run"]);
+        assert_eq!(summaries(second), vec!["second run"]);
+    }
+
+    #[test]
+    fn test_get_repos_updated_since() {
+        let db = StateDb::open_in_memory().unwrap();
+
+        db.upsert_repo("owner/old", None, None, RepoStatus::Ok, None)
+            .unwrap();
+        let since = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        db.upsert_repo("owner/new", None, None, RepoStatus::Error, None)
+            .unwrap();
+
+        let updated = db.get_repos_updated_since(since).unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].full_name, "owner/new");
+        assert_eq!(updated[0].last_sync_status, RepoStatus::Error);
+    }
+
     #[test]
     fn test_acknowledge_events() {
         let db = StateDb::open_in_memory().unwrap();
@@ -786,6 +1660,123 @@ mod tests {
         assert_eq!(events.len(), 0);
     }
 
+    #[test]
+    fn test_cleanup_old_events() {
+        let db = StateDb::open_in_memory().unwrap();
+
+        db.record_event(SyncEventBuilder::new(EventType::Cloned, "Cloned repo").repo("owner/a"))
+            .unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::SyncError, "Failed").repo("owner/b"))
+            .unwrap();
+        let events = db.get_unacknowledged_events().unwrap();
+        let cloned = events.iter().find(|e| e.event_type == EventType::Cloned);
+        db.acknowledge_event(cloned.unwrap().id).unwrap();
+
+        // Unacknowledged events are kept regardless of age
+        assert_eq!(db.cleanup_old_events(0).unwrap(), 1);
+        db.vacuum().unwrap();
+        assert_eq!(db.get_unacknowledged_events().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cleanup_events_in_range() {
+        let db = StateDb::open_in_memory().unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "Pulled").repo("owner/a"))
+            .unwrap();
+        let event = &db.get_unacknowledged_events().unwrap()[0];
+        db.acknowledge_event(event.id).unwrap();
+
+        let now = Utc::now();
+        let hour = chrono::Duration::hours(1);
+
+        // Windows that don't contain the event
+        assert_eq!(
+            db.cleanup_events_in_range(Some(now + hour), None).unwrap(),
+            0
+        );
+        assert_eq!(
+            db.cleanup_events_in_range(None, Some(now - hour)).unwrap(),
+            0
+        );
+
+        assert_eq!(
+            db.cleanup_events_in_range(Some(now - hour), Some(now + hour))
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_time_bound("2024-03-01T08:30:00+01:00", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2024-03-01T07:30:00+00:00"
+        );
+        assert_eq!(
+            parse_time_bound("2d", now).unwrap().to_rfc3339(),
+            "2024-03-08T12:00:00+00:00"
+        );
+        assert!(parse_time_bound("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn test_claim_notification_once_per_day() {
+        use chrono::TimeZone;
+
+        let db = StateDb::open_in_memory().unwrap();
+        let noon = Local.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+        assert!(db.claim_notification("owner/repo", noon).unwrap());
+        assert!(!db
+            .claim_notification("owner/repo", noon + chrono::Duration::hours(6))
+            .unwrap());
+        assert!(db.claim_notification("owner/other", noon).unwrap());
+        assert!(db
+            .claim_notification("owner/repo", noon + chrono::Duration::days(1))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_discovery_cache() {
+        let db = StateDb::open_in_memory().unwrap();
+        assert!(db.load_discovery_cache("key").unwrap().is_none());
+
+        db.save_discovery_cache("key", "[1]").unwrap();
+        db.save_discovery_cache("key", "[2]").unwrap();
+        let (fetched_at, specs) = db.load_discovery_cache("key").unwrap().unwrap();
+        assert_eq!(specs, "[2]");
+        assert!(Utc::now() - fetched_at < chrono::Duration::minutes(1));
+
+        // Discovered with a different configuration
+        assert!(db.load_discovery_cache("other").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_auto_ack_info() {
+        let db = StateDb::open_in_memory().unwrap().with_auto_ack_info(true);
+
+        db.record_event(SyncEventBuilder::new(EventType::Pulled, "Pulled 3 commits"))
+            .unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::SyncError, "Pull failed"))
+            .unwrap();
+
+        let unread = db.get_unacknowledged_events().unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].event_type, EventType::SyncError);
+
+        // Still in the history
+        let all = db
+            .get_events_with_filter(None, None, None, &[], None)
+            .unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
     #[test]
     fn test_unacknowledged_counts() {
         let db = StateDb::open_in_memory().unwrap();
@@ -836,6 +1827,44 @@ mod tests {
         assert_eq!(issues.len(), 2);
     }
 
+    #[test]
+    fn test_consecutive_failures() {
+        let db = StateDb::open_in_memory().unwrap();
+        let failures = |db: &StateDb| {
+            db.get_repo("owner/flaky")
+                .unwrap()
+                .unwrap()
+                .consecutive_failures
+        };
+
+        for _ in 0..3 {
+            db.upsert_repo(
+                "owner/flaky",
+                None,
+                None,
+                RepoStatus::Error,
+                Some("timeout"),
+            )
+            .unwrap();
+        }
+        assert_eq!(failures(&db), 3);
+
+        // Skips neither count as failures nor reset the streak
+        db.upsert_repo("owner/flaky", None, None, RepoStatus::Skipped, None)
+            .unwrap();
+        assert_eq!(failures(&db), 3);
+
+        let chronic = db.get_chronic_repos(3).unwrap();
+        assert_eq!(chronic.len(), 1);
+        assert_eq!(chronic[0].full_name, "owner/flaky");
+        assert!(db.get_chronic_repos(4).unwrap().is_empty());
+
+        db.upsert_repo("owner/flaky", None, None, RepoStatus::Ok, None)
+            .unwrap();
+        assert_eq!(failures(&db), 0);
+        assert!(db.get_chronic_repos(3).unwrap().is_empty());
+    }
+
     #[test]
     fn test_event_type_severity() {
         assert_eq!(EventType::Cloned.severity(), Severity::Info);
@@ -844,4 +1873,30 @@ mod tests {
         assert_eq!(EventType::SkippedLocalChanges.severity(), Severity::Warning);
         assert_eq!(EventType::SyncError.severity(), Severity::Error);
     }
+
+    #[test]
+    fn test_write_events_csv() {
+        let db = StateDb::open_in_memory().unwrap();
+        db.record_event(
+            SyncEventBuilder::new(EventType::SyncError, "Sync error: a, b\nc").repo("owner/repo"),
+        )
+        .unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Cloned, "Cloned \"x\""))
+            .unwrap();
+        let events = db
+            .get_events_with_filter(None, None, None, &[], None)
+            .unwrap();
+
+        let mut output = Vec::new();
+        write_events_csv(&events, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.next(),
+            Some("timestamp,repo_full_name,event_type,severity,summary,acknowledged")
+        );
+        assert!(output.contains(",owner/repo,sync_error,error,\"Sync error: a, b\nc\",false"));
+        assert!(output.contains(",,cloned,info,\"Cloned \"\"x\"\"\",false"));
+    }
 }
@@ -7,27 +7,145 @@
 //! that can come from any discovery source (GitHub, GitLab, local, etc.)
 
 use crate::discovery::RepoSpec;
-use crate::git::{GitClient, RepoState, SyncResult};
-use crate::state::{EventType, RepoStatus, StateDb, SyncEventBuilder};
+use crate::git::{
+    has_skip_sentinel, is_auth_error, is_disk_full, is_shallow_repo, move_checkout_aside,
+    FailureKind, GitClient, PromptTime, RepoState, SyncResult, DRY_RUN_REASON_PREFIX,
+    FIRST_RUN_REASON_PREFIX, IN_PROGRESS_REASON_PREFIX, REMOTE_INACCESSIBLE_REASON_PREFIX,
+    REMOTE_MISMATCH_REASON_PREFIX, SKIP_SENTINEL_REASON, UPSTREAM_REMOTE, WIKI_NOT_CREATED_REASON,
+};
+use crate::lock::SyncLock;
+use crate::state::{self, EventType, RepoStatus, StateDb, SyncEvent, SyncEventBuilder};
 use crate::Config;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
 /// Results from a complete sync operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncSummary {
     pub total_repositories: usize,
     pub successful_operations: usize,
     pub failed_operations: usize,
     pub skipped_operations: usize,
+    /// Pulls that rebased local commits (also counted as successful)
+    pub rebased_operations: usize,
+    /// Pulls that created a merge commit (also counted as successful)
+    pub merged_operations: usize,
     pub duration: Duration,
     pub results: Vec<SyncResult>,
+    /// Rate limit in effect for this sync, if any (e.g., "5MB/s")
+    pub rate_limit: Option<String>,
+    /// Repositories seen for the first time (owner/repo format)
+    pub new_repositories: Vec<String>,
+    /// How long each repository's operation took, by path (none for
+    /// repositories skipped before syncing started)
+    pub durations: HashMap<PathBuf, Duration>,
+}
+
+impl SyncSummary {
+    /// Failed repositories with their errors, grouped by cause
+    ///
+    /// The largest group comes first; repositories are sorted by path.
+    pub fn failures_by_kind(&self) -> Vec<(FailureKind, Vec<(&Path, &str)>)> {
+        let mut groups: HashMap<FailureKind, Vec<(&Path, &str)>> = HashMap::new();
+        for result in &self.results {
+            if let SyncResult::Failed { path, error } = result {
+                groups
+                    .entry(FailureKind::classify(error))
+                    .or_default()
+                    .push((path.as_path(), error.as_str()));
+            }
+        }
+
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        for (_, failures) in &mut groups {
+            failures.sort();
+        }
+        groups.sort_by(|(a_kind, a), (b_kind, b)| b.len().cmp(&a.len()).then(a_kind.cmp(b_kind)));
+        groups
+    }
+
+    /// Skipped repositories grouped by the event type their skip is recorded as
+    ///
+    /// Skips that aren't recorded (dry runs, wikis never created) are left
+    /// out. The largest group comes first; paths are sorted.
+    pub fn skips_by_reason(&self) -> Vec<(EventType, Vec<&Path>)> {
+        let mut groups: Vec<(EventType, Vec<&Path>)> = Vec::new();
+        for result in &self.results {
+            let SyncResult::Skipped { path, reason } = result else {
+                continue;
+            };
+            if reason == WIKI_NOT_CREATED_REASON || reason.starts_with(DRY_RUN_REASON_PREFIX) {
+                continue;
+            }
+            let event_type = skip_event_type(reason);
+            match groups.iter_mut().find(|(group, _)| *group == event_type) {
+                Some((_, paths)) => paths.push(path),
+                None => groups.push((event_type, vec![path.as_path()])),
+            }
+        }
+
+        for (_, paths) in &mut groups {
+            paths.sort();
+        }
+        groups.sort_by(|(a_type, a), (b_type, b)| {
+            b.len()
+                .cmp(&a.len())
+                .then(a_type.as_str().cmp(b_type.as_str()))
+        });
+        groups
+    }
+}
+
+/// Number of recent events included in a `RepoStatusView`
+const REPO_STATUS_EVENT_LIMIT: u32 = 10;
+
+/// Upper bound on parallel sync operations
+const MAX_CONCURRENCY: usize = 12;
+
+/// How much slower than the fastest round a round of operations may be
+/// before dynamic concurrency backs off
+const SATURATION_FACTOR: f64 = 2.0;
+
+/// File descriptors one git operation may hold open: its own pipes plus those
+/// of the helper processes it spawns (remote helpers, pack-objects, hooks)
+const FDS_PER_OPERATION: u64 = 16;
+
+/// File descriptors left for the rest of the process (state database, log
+/// files, network sockets)
+const RESERVED_FDS: u64 = 64;
+
+/// Number of repositories that must all fail to authenticate, as the first
+/// to finish, before a sync is stopped (see `sync.continue_on_auth_error`)
+const AUTH_FAILURE_SAMPLE: usize = 3;
+
+/// Combined view of a single repository's state
+///
+/// Joins the live on-disk git state with the persisted sync state and
+/// recent events, so library consumers can query one repository in one call.
+#[derive(Debug, Clone)]
+pub struct RepoStatusView {
+    /// Repository full name (owner/repo format)
+    pub full_name: String,
+    /// Local checkout path
+    pub local_path: PathBuf,
+    /// Live git state (None if the repository is not cloned locally)
+    pub local: Option<RepoState>,
+    /// Persisted sync state (None if never synced or no state database)
+    pub persisted: Option<state::RepoState>,
+    /// Most recent events for this repository (newest first)
+    pub recent_events: Vec<SyncEvent>,
 }
 
+/// Callback invoked with each repository's result as it completes
+pub type SyncResultCallback = Arc<dyn Fn(&SyncResult) + Send + Sync>;
+
 /// The main sync engine that orchestrates repository synchronization
 ///
 /// SyncEngine is provider-agnostic. It accepts `Vec<RepoSpec>` from any
@@ -37,6 +155,8 @@ pub struct SyncEngine {
     config: Arc<Config>,
     git_client: GitClient,
     state_db: Option<Arc<Mutex<StateDb>>>,
+    wait_for_lock: bool,
+    result_callback: Option<SyncResultCallback>,
 }
 
 impl SyncEngine {
@@ -49,6 +169,8 @@ impl SyncEngine {
             config,
             git_client,
             state_db: None,
+            wait_for_lock: false,
+            result_callback: None,
         }
     }
 
@@ -56,12 +178,16 @@ impl SyncEngine {
     pub fn with_state_db(config: Config) -> Result<Self> {
         let config = Arc::new(config);
         let git_client = GitClient::new(config.as_ref().clone());
-        let state_db = StateDb::open().context("Failed to open state database")?;
+        let state_db = StateDb::open()
+            .context("Failed to open state database")?
+            .with_auto_ack_info(config.events.auto_ack_info);
 
         Ok(Self {
             config,
             git_client,
             state_db: Some(Arc::new(Mutex::new(state_db))),
+            wait_for_lock: false,
+            result_callback: None,
         })
     }
 
@@ -69,35 +195,68 @@ impl SyncEngine {
     pub fn with_custom_state_db(config: Config, state_db: StateDb) -> Self {
         let config = Arc::new(config);
         let git_client = GitClient::new(config.as_ref().clone());
+        let state_db = state_db.with_auto_ack_info(config.events.auto_ack_info);
 
         Self {
             config,
             git_client,
             state_db: Some(Arc::new(Mutex::new(state_db))),
+            wait_for_lock: false,
+            result_callback: None,
         }
     }
 
+    /// Look up the GitHub token git uses for HTTPS remotes (`advanced.git_token_auth`)
+    pub async fn with_token_auth(mut self) -> Self {
+        self.git_client = self.git_client.with_token_auth().await;
+        self
+    }
+
+    /// Use `token` for HTTPS remotes instead of looking it up again
+    pub fn with_https_token(mut self, token: Option<Arc<str>>) -> Self {
+        self.git_client = self.git_client.with_https_token(token);
+        self
+    }
+
+    /// Wait for another process's sync to finish instead of failing
+    pub fn wait_for_lock(mut self, wait: bool) -> Self {
+        self.wait_for_lock = wait;
+        self
+    }
+
+    /// Call `callback` with each repository's result as soon as it completes
+    ///
+    /// The callback runs on the sync task, so in-flight operations aren't
+    /// polled while it executes. Keep it quick (e.g. send on a channel).
+    pub fn with_result_callback(
+        mut self,
+        callback: Box<dyn Fn(&SyncResult) + Send + Sync>,
+    ) -> Self {
+        self.result_callback = Some(Arc::from(callback));
+        self
+    }
+
     /// Sync repositories from RepoSpec list (provider-agnostic)
     ///
     /// This is the primary sync method. It accepts pre-discovered repositories
     /// as `Vec<RepoSpec>` and performs parallel synchronization.
     ///
     /// If a state database is configured, sync results are automatically recorded.
+    /// Fails if another process is already syncing, unless the engine was
+    /// configured to wait for the sync lock.
     pub async fn sync_repos(&self, repos: Vec<RepoSpec>) -> Result<SyncSummary> {
+        let _lock = SyncLock::acquire(&self.config, self.wait_for_lock).await?;
+        let _run = self.start_run();
         let start_time = Instant::now();
 
         info!("Starting synchronization of {} repositories", repos.len());
 
-        let sync_results = self
-            .sync_specs_parallel(repos)
-            .await
-            .context("Failed to synchronize repositories")?;
-
-        // Record results to state database if configured
-        self.record_sync_results(&sync_results);
+        let (sync_results, durations, new_repositories) = self.run_sync(repos).await?;
 
         let duration = start_time.elapsed();
-        let summary = self.compile_summary(sync_results, duration);
+        let mut summary = self.compile_summary(sync_results, duration);
+        summary.new_repositories = new_repositories;
+        summary.durations = durations;
 
         info!(
             "Sync completed in {:.2}s: {} successful, {} failed, {} skipped",
@@ -110,23 +269,107 @@ impl SyncEngine {
         Ok(summary)
     }
 
+    /// Sync a single repository and return its result
+    ///
+    /// The per-repository counterpart of `sync_repos`: the same lock, checks
+    /// and state recording apply. Git failures are reported as
+    /// `SyncResult::Failed`; an error means the sync couldn't run at all.
+    pub async fn sync_one(&self, spec: RepoSpec) -> Result<SyncResult> {
+        let _lock = SyncLock::acquire(&self.config, self.wait_for_lock).await?;
+        let _run = self.start_run();
+        let full_name = spec.full_name();
+
+        let (mut results, _, _) = self.run_sync(vec![spec]).await?;
+
+        results
+            .pop()
+            .ok_or_else(|| anyhow!("No sync result for {}", full_name))
+    }
+
+    /// The sync shared by `sync_repos` and `sync_one`, run under the sync lock
+    ///
+    /// Records new repositories and sources, sets sentinel checkouts aside
+    /// and handles moves and remote mismatches, syncs the rest in parallel and
+    /// records every result. Returns the results, each repository's sync
+    /// duration and the new repositories; fails when the disk fills up or
+    /// authentication is broken.
+    async fn run_sync(
+        &self,
+        repos: Vec<RepoSpec>,
+    ) -> Result<(Vec<SyncResult>, HashMap<PathBuf, Duration>, Vec<String>)> {
+        // Before the sources are recorded, which adds every repository
+        let new_repositories = self.new_repos(&repos);
+        self.record_new_repos(&new_repositories);
+
+        self.record_repo_sources(&repos);
+        self.record_depth_mismatches(&repos);
+        let (repos, early_skips) = self.prepare_checkouts(repos).await;
+
+        let (mut sync_results, durations) = self
+            .sync_specs_parallel(repos)
+            .await
+            .context("Failed to synchronize repositories")?;
+        for result in &early_skips {
+            self.notify_result(result);
+        }
+        sync_results.extend(early_skips);
+
+        // Record results to state database if configured
+        self.record_sync_results(&sync_results);
+
+        if let Some(path) = sync_results.iter().find_map(disk_full_path) {
+            return Err(anyhow!(
+                "Disk full: no space left on device while syncing {}. Sync aborted; \
+                 free up space under {} and run it again",
+                path.display(),
+                self.config.base_directory
+            ));
+        }
+
+        if let Some(error) = self.auth_broken(&sync_results) {
+            return Err(anyhow!(
+                "Authentication appears broken - stopping: the first {} repositories failed \
+                 to authenticate ({}). Check your credentials with `reposentry doctor`, or \
+                 pass --continue-on-auth-error to try every repository anyway",
+                AUTH_FAILURE_SAMPLE,
+                error
+            ));
+        }
+
+        Ok((sync_results, durations, new_repositories))
+    }
+
     /// Analyze repositories without syncing (dry-run)
     ///
     /// Returns the current state of each repository for preview.
     pub async fn analyze_repos(&self, repos: &[RepoSpec]) -> Result<Vec<RepoState>> {
         info!("Running dry-run analysis for {} repositories", repos.len());
 
-        let mut repo_states = Vec::new();
-
-        for spec in repos {
-            let state = self
-                .git_client
-                .analyze_from_spec(spec)
-                .await
-                .context("Failed to analyze repository state")?;
-            repo_states.push(state);
+        let mut parallel = self.config.sync.max_parallel_analyze.max(1);
+        if let Some(limit) = fd_concurrency_limit().filter(|&limit| limit < parallel) {
+            debug!(
+                "Open file limit (ulimit -n) allows {} parallel analyses (sync.max_parallel_analyze: {})",
+                limit, parallel
+            );
+            parallel = limit;
         }
 
+        // `buffered` keeps the states in the order of `repos`
+        // Each future owns its spec; borrowing ones fail the `Send` check
+        // where callers `tokio::spawn` the analysis
+        let git_client = &self.git_client;
+        let analyses = repos
+            .iter()
+            .cloned()
+            .map(|spec| async move { git_client.analyze_from_spec(&spec).await });
+        let repo_states: Vec<RepoState> = futures::stream::iter(analyses)
+            .buffered(parallel)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .context("Failed to analyze repository state")?;
+
         info!(
             "Dry-run analysis completed for {} repositories",
             repo_states.len()
@@ -135,13 +378,148 @@ impl SyncEngine {
         Ok(repo_states)
     }
 
+    /// Record analyzed repository states in the state database
+    ///
+    /// Used by `sync --analyze-only` to refresh each checkout's path and
+    /// branch without pulling or cloning. The sync status, last sync time and
+    /// failure streak are left as the last real sync set them. `states` are
+    /// the results of `analyze_repos` for `repos`; repositories that aren't
+    /// cloned are left out. Returns how many were recorded.
+    pub fn record_analysis(&self, repos: &[RepoSpec], states: &[RepoState]) -> Result<usize> {
+        let state_db = self
+            .state_db
+            .as_ref()
+            .ok_or_else(|| anyhow!("State database unavailable"))?;
+        let db = state_db
+            .lock()
+            .map_err(|_| anyhow!("Failed to acquire state database lock"))?;
+
+        let mut recorded = 0;
+        for (spec, state) in repos.iter().zip(states).filter(|(_, state)| state.exists) {
+            db.record_analysis(
+                &spec.full_name(),
+                &state.path.to_string_lossy(),
+                state.current_branch.as_deref(),
+            )?;
+            recorded += 1;
+        }
+
+        Ok(recorded)
+    }
+
+    /// Get the combined state of a single repository
+    ///
+    /// `full_name` is in owner/repo format. The local path is taken from the
+    /// state database if recorded, otherwise computed from the configuration.
+    pub async fn repo_status(&self, full_name: &str) -> Result<RepoStatusView> {
+        let (owner, name) = full_name
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", full_name))?;
+
+        let (persisted, recent_events) = match &self.state_db {
+            Some(state_db) => {
+                let db = state_db
+                    .lock()
+                    .map_err(|_| anyhow!("Failed to acquire state database lock"))?;
+                let persisted = db.get_repo(full_name)?;
+                let events = db.get_events_for_repo(full_name, Some(REPO_STATUS_EVENT_LIMIT))?;
+                (persisted, events)
+            }
+            None => (None, Vec::new()),
+        };
+
+        let local_path = persisted
+            .as_ref()
+            .and_then(|repo| repo.local_path.as_ref())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.config.repo_local_path(owner, name, &[]));
+
+        let local = if local_path.exists() {
+            let remote_url = self
+                .git_client
+                .get_remote_url(&local_path)
+                .await?
+                .unwrap_or_default();
+            Some(
+                self.git_client
+                    .analyze_repo_state(&local_path, &remote_url)
+                    .await
+                    .context("Failed to analyze repository state")?,
+            )
+        } else {
+            None
+        };
+
+        Ok(RepoStatusView {
+            full_name: full_name.to_string(),
+            local_path,
+            local,
+            persisted,
+            recent_events,
+        })
+    }
+
+    /// Controller for `sync.dynamic_concurrency`, starting at `limit`
+    ///
+    /// The limit never grows past `sync.max_parallel`, nor past what the
+    /// open file limit allows.
+    fn concurrency_controller(
+        &self,
+        limit: usize,
+        fd_limit: Option<usize>,
+    ) -> Option<ConcurrencyController> {
+        if !self.config.sync.dynamic_concurrency
+            || self.config.sync.effective_rate_limit().is_some()
+        {
+            return None;
+        }
+
+        let max_parallel = self.config.sync.max_parallel;
+        let ceiling = fd_limit.map_or(max_parallel, |fd_limit| fd_limit.min(max_parallel));
+        Some(ConcurrencyController::new(limit).with_ceiling(ceiling))
+    }
+
     /// Synchronize repositories in parallel with network-aware concurrency
-    async fn sync_specs_parallel(&self, repos: Vec<RepoSpec>) -> Result<Vec<SyncResult>> {
+    async fn sync_specs_parallel(
+        &self,
+        repos: Vec<RepoSpec>,
+    ) -> Result<(Vec<SyncResult>, HashMap<PathBuf, Duration>)> {
         let base_parallel = self.config.sync.max_parallel;
-        let operation_timeout = Duration::from_secs(self.config.sync.timeout);
 
         // Network-aware concurrency: adjust based on repository characteristics
-        let adaptive_parallel = self.calculate_adaptive_concurrency(&repos, base_parallel);
+        let adaptive_parallel = match self.config.sync.effective_rate_limit() {
+            Some(limit) => {
+                info!(
+                    "Rate limit {} in effect: syncing one repository at a time",
+                    limit
+                );
+                1
+            }
+            None => {
+                if let Some(limit) = &self.config.sync.rate_limit {
+                    warn!("Ignoring invalid sync.rate_limit: {}", limit);
+                }
+                self.calculate_adaptive_concurrency(&repos, base_parallel)
+            }
+        };
+
+        // Too many git processes at once can run out of file descriptors
+        let fd_limit = fd_concurrency_limit();
+        let adaptive_parallel = match fd_limit {
+            Some(limit) if limit < adaptive_parallel => {
+                warn!(
+                    "Open file limit (ulimit -n) allows {} parallel operations: reducing concurrency from {} (sync.max_parallel: {})",
+                    limit, adaptive_parallel, base_parallel
+                );
+                limit
+            }
+            _ => adaptive_parallel,
+        };
+
+        let mut controller = self.concurrency_controller(adaptive_parallel, fd_limit);
+        let adaptive_parallel = controller
+            .as_ref()
+            .map_or(adaptive_parallel, |controller| controller.limit);
 
         info!(
             "Syncing {} repositories with adaptive concurrency: base={}, calculated={}",
@@ -150,36 +528,93 @@ impl SyncEngine {
             adaptive_parallel
         );
 
+        let first_run_repos = self.first_run_repos(&repos);
+
+        let repos = if self.config.sync.fair_scheduling {
+            interleave_by_owner(repos)
+        } else {
+            repos
+        };
+
         // Create a semaphore to control concurrency
         let semaphore = Arc::new(tokio::sync::Semaphore::new(adaptive_parallel));
 
+        // Permits to retire as operations finish, after dynamic concurrency backs off
+        let excess_permits = Arc::new(AtomicUsize::new(0));
+
+        let prune = self.config.sync.prune && !self.config.advanced.mirror_mode;
+
         // Create futures for all sync operations
         let mut futures = FuturesUnordered::new();
 
         for spec in repos {
             let semaphore = semaphore.clone();
+            let excess_permits = excess_permits.clone();
             let git_client = self.git_client.clone();
+            // A dry run must not consume the first-run preview
+            let first_run = !git_client.is_dry_run() && first_run_repos.contains(&spec.full_name());
+            let operation_timeout = Duration::from_secs(self.repo_timeout(&spec.full_name()));
 
             let future = async move {
-                // Acquire semaphore permit
-                let _permit = semaphore.acquire().await.expect("Semaphore closed");
+                // The semaphore is closed once the sync aborts: don't start
+                let permit = semaphore.acquire().await.ok()?;
+                let started = Instant::now();
 
                 let spec_name = spec.full_name();
                 let spec_path = spec.local_path.clone();
+                let mut timed_out = false;
 
                 // Run sync operation with timeout
-                let sync_future = git_client.sync_from_spec(&spec);
-                match timeout(operation_timeout, sync_future).await {
-                    Ok(result) => result,
-                    Err(_) => {
+                let sync_future = async {
+                    if first_run {
+                        git_client.preview_from_spec(&spec).await
+                    } else {
+                        git_client.sync_from_spec(&spec).await
+                    }
+                };
+                let prompt_time = Arc::new(PromptTime::default());
+                let result = match prompt_time
+                    .clone()
+                    .timeout(operation_timeout, sync_future)
+                    .await
+                {
+                    Some(result) => result,
+                    None => {
                         warn!("Sync operation timed out for repository: {}", spec_name);
+                        timed_out = true;
                         Err(anyhow::anyhow!(
                             "Operation timed out after {}s",
                             operation_timeout.as_secs()
                         ))
                     }
                 }
-                .map_err(|e| (spec_path, e))
+                .map_err(|e| (spec_path, e));
+
+                // Branches whose upstream was deleted, once the fetch saw it gone
+                let pruned = match &result {
+                    Ok(sync_result) if prune && !first_run && prunes_after(sync_result) => {
+                        match git_client.prune_gone_branches(sync_result.path()).await {
+                            Ok(branches) => Some(branches),
+                            Err(e) => {
+                                warn!("Failed to prune branches of {}: {:#}", spec_name, e);
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+
+                // Shrink the pool by not returning this permit
+                let retire = excess_permits
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok();
+                if retire {
+                    permit.forget();
+                }
+
+                // Time spent at prompts isn't the repository's sync time
+                let elapsed = started.elapsed().saturating_sub(prompt_time.total());
+                Some((spec_name, result, elapsed, pruned, timed_out))
             };
 
             futures.push(future);
@@ -187,24 +622,129 @@ impl SyncEngine {
 
         // Collect all results
         let mut results = Vec::new();
+        let mut durations = HashMap::new();
 
-        while let Some(result) = futures.next().await {
-            match result {
+        while let Some(outcome) = futures.next().await {
+            // Repositories not started before the sync aborted
+            let Some((full_name, result, elapsed, pruned, timed_out)) = outcome else {
+                continue;
+            };
+            if let Some(controller) = controller.as_mut() {
+                if let Some((old, new)) = controller.record(elapsed) {
+                    info!("Dynamic concurrency: {} -> {}", old, new);
+                    if new > old {
+                        // Cancel pending retirements before adding permits
+                        let owed = excess_permits.swap(0, Ordering::SeqCst);
+                        let added = new - old;
+                        excess_permits.store(owed.saturating_sub(added), Ordering::SeqCst);
+                        semaphore.add_permits(added.saturating_sub(owed));
+                    } else {
+                        excess_permits.fetch_add(old - new, Ordering::SeqCst);
+                    }
+                }
+            }
+
+            let sync_result = match result {
                 Ok(sync_result) => {
                     debug!("Sync completed: {:?}", sync_result);
-                    results.push(sync_result);
+                    sync_result
                 }
                 Err((path, e)) => {
                     error!("Sync failed for {}: {:?}", path.display(), e);
-                    results.push(SyncResult::Failed {
+                    SyncResult::Failed {
                         path,
                         error: format!("Sync operation failed: {}", e),
-                    });
+                    }
                 }
+            };
+            self.notify_result(&sync_result);
+            if let Some(branches) = pruned {
+                self.record_pruned_branches(&full_name, &branches);
+            }
+            if !self.git_client.is_dry_run() {
+                self.record_timeout_outcome(&full_name, timed_out);
+            }
+            let disk_full = disk_full_path(&sync_result).is_some();
+            durations.insert(sync_result.path().to_path_buf(), elapsed);
+            results.push(sync_result);
+
+            // Every remaining repository would fail the same way: start no
+            // more, but let the running ones finish and record their results
+            if semaphore.is_closed() {
+                continue;
+            }
+            if disk_full {
+                error!("Disk full, aborting the remaining sync operations");
+                semaphore.close();
+            } else if self.auth_broken(&results).is_some() {
+                error!(
+                    "The first {} repositories failed to authenticate, aborting the remaining sync operations",
+                    AUTH_FAILURE_SAMPLE
+                );
+                semaphore.close();
             }
         }
 
-        Ok(results)
+        Ok((results, durations))
+    }
+
+    /// Error of the first result, when the first `AUTH_FAILURE_SAMPLE`
+    /// results all failed to authenticate
+    ///
+    /// Always None with `sync.continue_on_auth_error`.
+    fn auth_broken<'a>(&self, results: &'a [SyncResult]) -> Option<&'a str> {
+        if self.config.sync.continue_on_auth_error || results.len() < AUTH_FAILURE_SAMPLE {
+            return None;
+        }
+        let mut errors = results[..AUTH_FAILURE_SAMPLE]
+            .iter()
+            .map(|result| match result {
+                SyncResult::Failed { error, .. } if is_auth_error(error) => Some(error.as_str()),
+                _ => None,
+            });
+        let first = errors.next()??;
+        errors.all(|error| error.is_some()).then_some(first)
+    }
+
+    /// Pass a completed result to the result callback, if one is set
+    fn notify_result(&self, result: &SyncResult) {
+        if let Some(callback) = &self.result_callback {
+            callback(result);
+        }
+    }
+
+    /// Names of existing checkouts that have never been synced, when
+    /// `sync.first_run_mode` is "fetch-only"
+    ///
+    /// Requires a state database; without one, every repository syncs normally.
+    fn first_run_repos(&self, repos: &[RepoSpec]) -> HashSet<String> {
+        // Mirrors have no working tree to protect
+        if !self.config.sync.is_first_run_fetch_only() || self.config.advanced.mirror_mode {
+            return HashSet::new();
+        }
+
+        let Some(state_db) = &self.state_db else {
+            return HashSet::new();
+        };
+
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return HashSet::new();
+        };
+
+        repos
+            .iter()
+            .filter(|spec| spec.exists_locally())
+            .map(|spec| spec.full_name())
+            .filter(|full_name| match db.get_repo(full_name) {
+                Ok(Some(repo)) => repo.last_sync_at.is_none(),
+                Ok(None) => true,
+                Err(e) => {
+                    warn!("Failed to look up repo state for {}: {}", full_name, e);
+                    false
+                }
+            })
+            .collect()
     }
 
     /// Compile sync summary from results
@@ -213,12 +753,22 @@ impl SyncEngine {
         let mut successful_operations = 0;
         let mut failed_operations = 0;
         let mut skipped_operations = 0;
+        let mut rebased_operations = 0;
+        let mut merged_operations = 0;
 
         for result in &results {
             match result {
                 SyncResult::Cloned { .. }
                 | SyncResult::Pulled { .. }
                 | SyncResult::BranchSwitched { .. } => successful_operations += 1,
+                SyncResult::Rebased { .. } => {
+                    successful_operations += 1;
+                    rebased_operations += 1;
+                }
+                SyncResult::Merged { .. } => {
+                    successful_operations += 1;
+                    merged_operations += 1;
+                }
                 SyncResult::FetchedOnly { .. } | SyncResult::UpToDate { .. } => {
                     successful_operations += 1
                 }
@@ -232,8 +782,13 @@ impl SyncEngine {
             successful_operations,
             failed_operations,
             skipped_operations,
+            rebased_operations,
+            merged_operations,
             duration,
             results,
+            rate_limit: self.config.sync.effective_rate_limit().map(String::from),
+            new_repositories: Vec::new(),
+            durations: HashMap::new(),
         }
     }
 
@@ -273,7 +828,7 @@ impl SyncEngine {
         let calculated = (network_optimized as f64 * size_factor * count_factor).round() as usize;
 
         // 5. Enforce reasonable bounds
-        calculated.clamp(1, 12)
+        calculated.clamp(1, MAX_CONCURRENCY)
     }
 
     /// Get configuration for external inspection
@@ -302,6 +857,9 @@ impl SyncEngine {
             return;
         };
 
+        // Every event carries the sync strategy, so runs can be compared by it
+        let strategy = self.config.sync.strategy.as_str();
+
         // Record repo state and event based on result
         match result {
             SyncResult::Cloned { path, branch } => {
@@ -321,7 +879,9 @@ impl SyncEngine {
                     branch_ref.map(|b| format!(" on branch {}", b)).unwrap_or_default()
                 );
                 if let Err(e) = db.record_event(
-                    SyncEventBuilder::new(EventType::Cloned, summary).repo(repo_full_name),
+                    SyncEventBuilder::new(EventType::Cloned, summary)
+                        .repo(repo_full_name)
+                        .tag("strategy", strategy),
                 ) {
                     warn!("Failed to record clone event: {}", e);
                 }
@@ -331,6 +891,8 @@ impl SyncEngine {
                 path,
                 commits_updated,
                 branch,
+                remote,
+                ..
             } => {
                 let branch_ref = branch.as_deref();
                 if let Err(e) = db.upsert_repo(
@@ -345,15 +907,76 @@ impl SyncEngine {
 
                 // Only record pull event if commits were updated (not just up to date)
                 if *commits_updated > 0 {
-                    let summary = format!("Pulled {} commits", commits_updated);
-                    if let Err(e) = db.record_event(
-                        SyncEventBuilder::new(EventType::Pulled, summary).repo(repo_full_name),
-                    ) {
+                    let summary = if remote == UPSTREAM_REMOTE {
+                        format!("Pulled {} commits from upstream", commits_updated)
+                    } else {
+                        format!("Pulled {} commits", commits_updated)
+                    };
+                    let mut event = SyncEventBuilder::new(EventType::Pulled, summary)
+                        .repo(repo_full_name)
+                        .tag("strategy", strategy)
+                        .tag("remote", remote)
+                        .details(format!("{{\"remote\": \"{}\"}}", remote));
+                    if let Some(branch) = branch_ref {
+                        event = event.tag("branch", branch);
+                    }
+                    if let Err(e) = db.record_event(event) {
                         warn!("Failed to record pull event: {}", e);
                     }
                 }
             }
 
+            SyncResult::Rebased {
+                path,
+                commits_replayed: commits,
+                branch,
+                remote,
+                ..
+            }
+            | SyncResult::Merged {
+                path,
+                commits_merged: commits,
+                branch,
+                remote,
+                ..
+            } => {
+                if let Err(e) = db.upsert_repo(
+                    repo_full_name,
+                    Some(&path.to_string_lossy()),
+                    branch.as_deref(),
+                    RepoStatus::Ok,
+                    None,
+                ) {
+                    warn!("Failed to update repo state: {}", e);
+                }
+
+                let (event_type, summary) = if matches!(result, SyncResult::Rebased { .. }) {
+                    (
+                        EventType::Rebased,
+                        format!("Rebased {} local commits onto {}", commits, remote),
+                    )
+                } else {
+                    (
+                        EventType::Merged,
+                        format!("Merged {} commits from {}", commits, remote),
+                    )
+                };
+                let mut event = SyncEventBuilder::new(event_type, summary)
+                    .repo(repo_full_name)
+                    .tag("strategy", strategy)
+                    .tag("remote", remote)
+                    .details(format!(
+                        "{{\"remote\": \"{}\", \"commits\": {}}}",
+                        remote, commits
+                    ));
+                if let Some(branch) = branch {
+                    event = event.tag("branch", branch);
+                }
+                if let Err(e) = db.record_event(event) {
+                    warn!("Failed to record pull event: {}", e);
+                }
+            }
+
             SyncResult::BranchSwitched {
                 path,
                 from_branch,
@@ -377,6 +1000,8 @@ impl SyncEngine {
                 if let Err(e) = db.record_event(
                     SyncEventBuilder::new(EventType::BranchSwitch, summary)
                         .repo(repo_full_name)
+                        .tag("strategy", strategy)
+                        .tag("branch", to_branch.as_str())
                         .details(format!(
                             "{{\"from\": \"{}\", \"to\": \"{}\", \"commits\": {}}}",
                             from_branch, to_branch, commits_updated
@@ -386,6 +1011,29 @@ impl SyncEngine {
                 }
             }
 
+            SyncResult::FetchedOnly { path, reason }
+                if reason.starts_with(FIRST_RUN_REASON_PREFIX) =>
+            {
+                // First contact: mark as synced so the next run proceeds normally
+                if let Err(e) = db.upsert_repo(
+                    repo_full_name,
+                    Some(&path.to_string_lossy()),
+                    None,
+                    RepoStatus::Ok,
+                    None,
+                ) {
+                    warn!("Failed to update repo state: {}", e);
+                }
+
+                if let Err(e) = db.record_event(
+                    SyncEventBuilder::new(EventType::FirstRunPreview, reason.clone())
+                        .repo(repo_full_name)
+                        .tag("strategy", strategy),
+                ) {
+                    warn!("Failed to record first-run event: {}", e);
+                }
+            }
+
             SyncResult::FetchedOnly { path, reason } => {
                 // Determine if this is due to local changes or conflicts
                 let (event_type, status) = if reason.contains("local changes") {
@@ -413,7 +1061,9 @@ impl SyncEngine {
                 if event_type != EventType::Pulled {
                     let summary = format!("Fetch only: {}", reason);
                     if let Err(e) = db.record_event(
-                        SyncEventBuilder::new(event_type, summary).repo(repo_full_name),
+                        SyncEventBuilder::new(event_type, summary)
+                            .repo(repo_full_name)
+                            .tag("strategy", strategy),
                     ) {
                         warn!("Failed to record fetch-only event: {}", e);
                     }
@@ -433,17 +1083,16 @@ impl SyncEngine {
                 // Don't record event for up-to-date repos (too noisy)
             }
 
+            SyncResult::Skipped { reason, .. } if reason == WIKI_NOT_CREATED_REASON => {
+                // Wiki enabled but never created: nothing to track
+            }
+
+            SyncResult::Skipped { reason, .. } if reason.starts_with(DRY_RUN_REASON_PREFIX) => {
+                // Nothing was changed: keep the recorded state as it was
+            }
+
             SyncResult::Skipped { path, reason } => {
-                // Determine skip type for proper categorization
-                let event_type = if reason.contains("local changes") {
-                    EventType::SkippedLocalChanges
-                } else if reason.contains("conflict") {
-                    EventType::SkippedConflicts
-                } else if reason.contains("ahead") {
-                    EventType::SkippedAheadOfRemote
-                } else {
-                    EventType::SkippedLocalChanges // Default to local changes
-                };
+                let event_type = skip_event_type(reason);
 
                 if let Err(e) = db.upsert_repo(
                     repo_full_name,
@@ -457,7 +1106,9 @@ impl SyncEngine {
 
                 let summary = format!("Skipped: {}", reason);
                 if let Err(e) = db.record_event(
-                    SyncEventBuilder::new(event_type, summary).repo(repo_full_name),
+                    SyncEventBuilder::new(event_type, summary)
+                        .repo(repo_full_name)
+                        .tag("strategy", strategy),
                 ) {
                     warn!("Failed to record skip event: {}", e);
                 }
@@ -476,67 +1127,933 @@ impl SyncEngine {
 
                 let summary = format!("Sync error: {}", error);
                 if let Err(e) = db.record_event(
-                    SyncEventBuilder::new(EventType::SyncError, summary).repo(repo_full_name),
+                    SyncEventBuilder::new(EventType::SyncError, summary)
+                        .repo(repo_full_name)
+                        .tag("strategy", strategy),
                 ) {
                     warn!("Failed to record error event: {}", e);
                 }
+
+                // Alert once, when the streak reaches the threshold
+                let threshold = self.config.events.chronic_threshold;
+                let failures = db
+                    .get_repo(repo_full_name)
+                    .ok()
+                    .flatten()
+                    .map(|repo| repo.consecutive_failures)
+                    .unwrap_or(0);
+                if threshold > 0 && failures == threshold {
+                    let summary = format!("Failed {} syncs in a row: {}", failures, error);
+                    if let Err(e) = db.record_event(
+                        SyncEventBuilder::new(EventType::ChronicFailure, summary)
+                            .repo(repo_full_name)
+                            .tag("strategy", strategy),
+                    ) {
+                        warn!("Failed to record chronic failure event: {}", e);
+                    }
+                }
+            }
+        }
+
+        // Any kind of pull can leave auto-stashed changes behind
+        if result.stash_conflict() {
+            let event = SyncEventBuilder::new(
+                EventType::StashConflict,
+                "Auto-stashed changes conflicted after pull; they're kept in git stash list",
+            )
+            .repo(repo_full_name)
+            .tag("strategy", strategy);
+            if let Err(e) = db.record_event(event) {
+                warn!("Failed to record stash conflict event: {}", e);
             }
         }
+
+        if let Err(e) = db.set_last_action(repo_full_name, &sync_action(result, &self.config)) {
+            warn!("Failed to record sync action: {}", e);
+        }
     }
 
-    /// Record all sync results to the state database
-    pub fn record_sync_results(&self, results: &[SyncResult]) {
-        for result in results {
-            // Extract repo full name from the path
-            let repo_name = match result {
-                SyncResult::Cloned { path, .. }
-                | SyncResult::Pulled { path, .. }
-                | SyncResult::BranchSwitched { path, .. }
-                | SyncResult::FetchedOnly { path, .. }
-                | SyncResult::UpToDate { path, .. }
-                | SyncResult::Skipped { path, .. }
-                | SyncResult::Failed { path, .. } => {
-                    // Try to extract owner/repo from path (assuming structure like /base/owner/repo or /base/repo)
-                    let components: Vec<_> = path.components().rev().take(2).collect();
-                    match components.as_slice() {
-                        [repo, owner] => format!(
-                            "{}/{}",
-                            owner.as_os_str().to_string_lossy(),
-                            repo.as_os_str().to_string_lossy()
-                        ),
-                        [repo] => repo.as_os_str().to_string_lossy().to_string(),
-                        _ => path.to_string_lossy().to_string(),
-                    }
-                }
-            };
+    /// Names of discovered repositories the state database has never seen
+    ///
+    /// Empty without a state database, and on the very first sync (when every
+    /// repository would be new). Wikis are not reported separately.
+    pub fn new_repos(&self, repos: &[RepoSpec]) -> Vec<String> {
+        let Some(state_db) = &self.state_db else {
+            return Vec::new();
+        };
 
-            self.record_sync_result(result, &repo_name);
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return Vec::new();
+        };
+
+        let known = match db.repo_names() {
+            Ok(known) => known,
+            Err(e) => {
+                warn!("Failed to read known repositories: {}", e);
+                return Vec::new();
+            }
+        };
+        if known.is_empty() {
+            return Vec::new();
         }
+
+        repos
+            .iter()
+            .filter(|spec| !spec.is_wiki)
+            .map(|spec| spec.full_name())
+            .filter(|full_name| !known.contains(full_name))
+            .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
+    /// Start a sync run, so the events recorded until the returned guard is
+    /// dropped can be listed together (`events list --run`)
+    fn start_run(&self) -> SyncRun {
+        let Some(state_db) = &self.state_db else {
+            return SyncRun(None);
+        };
 
-    #[test]
-    fn test_sync_summary_calculation() {
-        let results = vec![
-            SyncResult::Cloned {
-                path: PathBuf::from("/test/repo1"),
-                branch: Some("main".to_string()),
-            },
-            SyncResult::Pulled {
-                path: PathBuf::from("/test/repo2"),
-                commits_updated: 5,
-                branch: Some("main".to_string()),
-            },
-            SyncResult::Failed {
-                path: PathBuf::from("/test/repo3"),
-                error: "Test error".to_string(),
+        match state_db.lock() {
+            Ok(mut db) => match db.start_run() {
+                Ok(id) => debug!("Sync run {} started", id),
+                Err(e) => warn!("Failed to start sync run: {}", e),
             },
-            SyncResult::Skipped {
+            Err(_) => warn!("Failed to acquire state database lock"),
+        }
+        SyncRun(Some(state_db.clone()))
+    }
+
+    /// Record a `NewRepo` event for each newly discovered repository
+    fn record_new_repos(&self, full_names: &[String]) {
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        for full_name in full_names {
+            info!("New repository discovered: {}", full_name);
+            if let Err(e) = db.record_event(
+                SyncEventBuilder::new(EventType::NewRepo, "New repository discovered")
+                    .repo(full_name),
+            ) {
+                warn!("Failed to record new repository event: {}", e);
+            }
+        }
+    }
+
+    /// Record the discovery source of each repository to the state database
+    fn record_repo_sources(&self, repos: &[RepoSpec]) {
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        for spec in repos {
+            let full_name = spec.full_name();
+            if let Err(e) = db.set_repo_source(&full_name, &spec.source.to_string()) {
+                warn!("Failed to record repo source: {}", e);
+            }
+            if let Some(provider_id) = spec.provider_id {
+                if let Err(e) = db.set_provider_id(&full_name, &spec.provider, provider_id) {
+                    warn!("Failed to record repo provider ID: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Record a warning for full checkouts when `sync.clone_depth` is set
+    ///
+    /// Full history is never truncated automatically. The warning is only
+    /// recorded once, until another event is recorded for the repository.
+    fn record_depth_mismatches(&self, repos: &[RepoSpec]) {
+        // Mirrors always hold full history
+        let Some(depth) = self.config.clone_depth() else {
+            return;
+        };
+
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        for spec in repos {
+            if !spec.exists_locally() || is_shallow_repo(&spec.local_path) {
+                continue;
+            }
+
+            let full_name = spec.full_name();
+            let already_warned = db
+                .get_events_for_repo(&full_name, Some(1))
+                .map(|events| {
+                    events
+                        .first()
+                        .is_some_and(|event| event.event_type == EventType::DepthMismatch)
+                })
+                .unwrap_or(false);
+            if already_warned {
+                continue;
+            }
+
+            let summary = format!(
+                "Full clone kept despite clone_depth {}; re-clone to make it shallow",
+                depth
+            );
+            if let Err(e) = db.record_event(
+                SyncEventBuilder::new(EventType::DepthMismatch, summary).repo(&full_name),
+            ) {
+                warn!("Failed to record depth mismatch event: {}", e);
+            }
+        }
+    }
+
+    /// Get checkouts ready to sync: moved repositories and remote mismatches
+    ///
+    /// Checkouts with the skip sentinel are set aside first, so neither step
+    /// touches them. Returns the repositories to sync and Skipped results for
+    /// the others.
+    async fn prepare_checkouts(&self, repos: Vec<RepoSpec>) -> (Vec<RepoSpec>, Vec<SyncResult>) {
+        let (sentinel, repos): (Vec<RepoSpec>, Vec<RepoSpec>) = repos
+            .into_iter()
+            .partition(|spec| has_skip_sentinel(&spec.local_path));
+        let mut skipped: Vec<SyncResult> = sentinel
+            .into_iter()
+            .map(|spec| {
+                info!("Skip sentinel present, not syncing: {}", spec.full_name());
+                SyncResult::Skipped {
+                    path: spec.local_path,
+                    reason: SKIP_SENTINEL_REASON.to_string(),
+                }
+            })
+            .collect();
+
+        self.handle_moved_repos(&repos).await;
+        let (repos, mismatch_skips) = self.resolve_remote_mismatches(repos).await;
+        skipped.extend(mismatch_skips);
+
+        (repos, skipped)
+    }
+
+    /// Apply `advanced.on_remote_mismatch` to checkouts whose origin doesn't
+    /// match the expected clone URL
+    ///
+    /// Returns the repositories to sync and Skipped results for the others.
+    /// Repointed checkouts then sync normally; re-cloned ones have been moved
+    /// aside, so the sync clones them fresh.
+    async fn resolve_remote_mismatches(
+        &self,
+        repos: Vec<RepoSpec>,
+    ) -> (Vec<RepoSpec>, Vec<SyncResult>) {
+        let mut to_sync = Vec::with_capacity(repos.len());
+        let mut skipped = Vec::new();
+
+        for spec in repos {
+            let actual = if spec.exists_locally() {
+                self.git_client
+                    .get_remote_url(&spec.local_path)
+                    .await
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+
+            // No checkout or no origin: nothing to compare against
+            let Some(actual) = actual else {
+                to_sync.push(spec);
+                continue;
+            };
+
+            let matches = std::iter::once(&spec.clone_url)
+                .chain(&spec.clone_url_alt)
+                .any(|url| self.git_client.remote_urls_match(&actual, url));
+            if matches {
+                to_sync.push(spec);
+                continue;
+            }
+
+            let mismatch = format!(
+                "{}: expected {}, found {}",
+                REMOTE_MISMATCH_REASON_PREFIX, spec.clone_url, actual
+            );
+            match self.handle_remote_mismatch(&spec, &actual).await {
+                Ok(Some(action)) => {
+                    info!("{} ({}): {}", mismatch, spec.full_name(), action);
+                    self.record_remote_mismatch(&spec.full_name(), &mismatch, &action);
+                    to_sync.push(spec);
+                }
+                Ok(None) => skipped.push(SyncResult::Skipped {
+                    path: spec.local_path,
+                    reason: mismatch,
+                }),
+                Err(e) => {
+                    warn!("{} ({}): {}", mismatch, spec.full_name(), e);
+                    skipped.push(SyncResult::Skipped {
+                        path: spec.local_path,
+                        reason: format!("{} ({})", mismatch, e),
+                    });
+                }
+            }
+        }
+
+        (to_sync, skipped)
+    }
+
+    /// Deal with checkouts left behind by repositories transferred to another owner
+    ///
+    /// With `advanced.follow_org_moves` the old checkout is moved to the new
+    /// location (and origin repointed), so the sync continues from it;
+    /// otherwise the orphaned copy is reported and a fresh clone is made.
+    async fn handle_moved_repos(&self, repos: &[RepoSpec]) {
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+
+        let moves = {
+            let Ok(db) = state_db.lock() else {
+                warn!("Failed to acquire state database lock");
+                return;
+            };
+            match db.get_all_repos() {
+                Ok(recorded) => find_moved_repos(repos, &recorded),
+                Err(e) => {
+                    warn!("Failed to read recorded repositories: {}", e);
+                    return;
+                }
+            }
+        };
+
+        for (spec, old_name, old_path) in moves {
+            let full_name = spec.full_name();
+            if self.git_client.is_dry_run() {
+                info!(
+                    "{} {}: previously cloned as {} at {}",
+                    DRY_RUN_REASON_PREFIX,
+                    full_name,
+                    old_name,
+                    old_path.display()
+                );
+                continue;
+            }
+
+            let (summary, moved) = if self.config.advanced.follow_org_moves {
+                match self.move_checkout(spec, &old_path).await {
+                    Ok(()) => (
+                        format!(
+                            "Moved checkout of {} from {} to {}",
+                            old_name,
+                            old_path.display(),
+                            spec.local_path.display()
+                        ),
+                        true,
+                    ),
+                    Err(e) => (
+                        format!(
+                            "Failed to move old checkout {} ({:#}); it is orphaned",
+                            old_path.display(),
+                            e
+                        ),
+                        false,
+                    ),
+                }
+            } else {
+                (
+                    format!(
+                        "Previously cloned as {} at {}; that checkout is now orphaned \
+                         (set advanced.follow_org_moves to move it)",
+                        old_name,
+                        old_path.display()
+                    ),
+                    false,
+                )
+            };
+            warn!("{}: {}", full_name, summary);
+
+            let Ok(db) = state_db.lock() else {
+                warn!("Failed to acquire state database lock");
+                return;
+            };
+            let event = SyncEventBuilder::new(EventType::RepoMoved, summary).repo(&full_name);
+            if let Err(e) = db.record_event(event) {
+                warn!("Failed to record repository move event: {}", e);
+            }
+            if moved {
+                if let Err(e) = db.delete_repo(&old_name) {
+                    warn!("Failed to remove record of {}: {}", old_name, e);
+                }
+            }
+        }
+    }
+
+    /// Move a checkout to a spec's location and point origin at its clone URL
+    async fn move_checkout(&self, spec: &RepoSpec, old_path: &Path) -> Result<()> {
+        if let Some(parent) = spec.local_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::rename(old_path, &spec.local_path).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                old_path.display(),
+                spec.local_path.display()
+            )
+        })?;
+
+        self.git_client
+            .set_remote_url(&spec.local_path, &spec.clone_url)
+            .await
+    }
+
+    /// Repoint or move aside a mismatched checkout, per `advanced.on_remote_mismatch`
+    ///
+    /// Returns a description of the action taken, or None with "skip". Fails
+    /// without changing anything if the action isn't safe.
+    async fn handle_remote_mismatch(
+        &self,
+        spec: &RepoSpec,
+        actual: &str,
+    ) -> Result<Option<String>> {
+        let path = &spec.local_path;
+
+        if self.git_client.is_dry_run()
+            && (self.config.advanced.is_update_remote_on_mismatch()
+                || self.config.advanced.is_reclone_on_mismatch())
+        {
+            return Err(anyhow!(
+                "{} would {}",
+                DRY_RUN_REASON_PREFIX,
+                self.config.advanced.on_remote_mismatch
+            ));
+        }
+
+        if self.config.advanced.is_update_remote_on_mismatch() {
+            if !self
+                .git_client
+                .shares_history_with(path, &spec.clone_url)
+                .await?
+            {
+                return Err(anyhow!("history is unrelated, not updating origin"));
+            }
+            self.git_client
+                .set_remote_url(path, &spec.clone_url)
+                .await?;
+            return Ok(Some(format!(
+                "updated origin from {} to {}",
+                actual, spec.clone_url
+            )));
+        }
+
+        if self.config.advanced.is_reclone_on_mismatch() {
+            if self.git_client.has_any_local_changes(path).await? {
+                return Err(anyhow!("local changes present, not re-cloning"));
+            }
+            let moved_to = move_checkout_aside(path)?;
+            return Ok(Some(format!(
+                "moved old clone to {} and re-cloned",
+                moved_to.display()
+            )));
+        }
+
+        Ok(None)
+    }
+
+    /// Record the action taken for a mismatched origin
+    fn record_remote_mismatch(&self, repo_full_name: &str, mismatch: &str, action: &str) {
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        let summary = format!("{}; {}", mismatch, action);
+        if let Err(e) = db.record_event(
+            SyncEventBuilder::new(EventType::RemoteMismatch, summary).repo(repo_full_name),
+        ) {
+            warn!("Failed to record remote mismatch event: {}", e);
+        }
+    }
+
+    /// Record the local branches deleted because their upstream is gone
+    fn record_pruned_branches(&self, repo_full_name: &str, branches: &[String]) {
+        if branches.is_empty() {
+            return;
+        }
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        let summary = format!(
+            "Pruned {} branches with deleted upstreams: {}",
+            branches.len(),
+            branches.join(", ")
+        );
+        if let Err(e) = db.record_event(
+            SyncEventBuilder::new(EventType::BranchesPruned, summary)
+                .repo(repo_full_name)
+                .details(format!("{{\"pruned\": {}}}", branches.len())),
+        ) {
+            warn!("Failed to record pruned branches event: {}", e);
+        }
+    }
+
+    /// Timeout for syncing a repository in seconds
+    ///
+    /// A `sync.timeout_overrides` entry replaces `sync.timeout`. With
+    /// `sync.auto_extend_timeout`, a longer timeout recorded after repeated
+    /// timeouts takes precedence.
+    fn repo_timeout(&self, full_name: &str) -> u64 {
+        let configured = self.config.sync.timeout_for(full_name);
+        if !self.config.sync.auto_extend_timeout {
+            return configured;
+        }
+        let Some(state_db) = &self.state_db else {
+            return configured;
+        };
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return configured;
+        };
+
+        match db.extended_timeout(full_name) {
+            Ok(extended) => extended.map_or(configured, |secs| secs.max(configured)),
+            Err(e) => {
+                warn!("Failed to read extended timeout of {}: {}", full_name, e);
+                configured
+            }
+        }
+    }
+
+    /// Track consecutive timeouts of a repository
+    ///
+    /// On reaching `sync.timeout_strike_limit`, records an event suggesting a
+    /// timeout twice as long, or applies it with `sync.auto_extend_timeout`.
+    fn record_timeout_outcome(&self, full_name: &str, timed_out: bool) {
+        let timeout = self.repo_timeout(full_name);
+        let Some(state_db) = &self.state_db else {
+            return;
+        };
+        let Ok(db) = state_db.lock() else {
+            warn!("Failed to acquire state database lock");
+            return;
+        };
+
+        let strikes = match db.record_timeout_outcome(full_name, timed_out) {
+            Ok(strikes) => strikes,
+            Err(e) => {
+                warn!("Failed to record timeout of {}: {}", full_name, e);
+                return;
+            }
+        };
+        let limit = self.config.sync.timeout_strike_limit;
+        if limit == 0 || strikes != limit {
+            return;
+        }
+
+        let suggested = timeout.saturating_mul(2);
+        let summary = if self.config.sync.auto_extend_timeout {
+            if let Err(e) = db.set_extended_timeout(full_name, suggested) {
+                warn!("Failed to extend timeout of {}: {}", full_name, e);
+                return;
+            }
+            format!(
+                "Timed out {} syncs in a row: timeout extended from {}s to {}s",
+                strikes, timeout, suggested
+            )
+        } else {
+            format!(
+                "Timed out {} syncs in a row after {}s: consider adding \"{}: {}\" to sync.timeout_overrides",
+                strikes, timeout, full_name, suggested
+            )
+        };
+        if let Err(e) = db.record_event(
+            SyncEventBuilder::new(EventType::RepeatedTimeout, summary)
+                .repo(full_name)
+                .tag("strategy", self.config.sync.strategy.as_str())
+                .details(format!(
+                    "{{\"timeout\": {}, \"suggested_timeout\": {}}}",
+                    timeout, suggested
+                )),
+        ) {
+            warn!("Failed to record repeated timeout event: {}", e);
+        }
+    }
+
+    /// Record all sync results to the state database
+    pub fn record_sync_results(&self, results: &[SyncResult]) {
+        for result in results {
+            // Try to extract owner/repo from path (assuming structure like /base/owner/repo or /base/repo)
+            let path = result.path();
+            let components: Vec<_> = path.components().rev().take(2).collect();
+            let repo_name = match components.as_slice() {
+                [repo, owner] => format!(
+                    "{}/{}",
+                    owner.as_os_str().to_string_lossy(),
+                    repo.as_os_str().to_string_lossy()
+                ),
+                [repo] => repo.as_os_str().to_string_lossy().to_string(),
+                _ => path.to_string_lossy().to_string(),
+            };
+
+            self.record_sync_result(result, &repo_name);
+        }
+    }
+}
+
+/// Find checkouts of repositories that were transferred to another owner
+///
+/// A repository whose checkout doesn't exist yet is matched against recorded
+/// repositories that are no longer discovered but whose checkout is still on
+/// disk (without a skip sentinel). A match needs the same provider and
+/// provider ID (which survives transfers; IDs are only unique per provider), so
+/// repositories that merely share a name or ID, or specs without an ID
+/// (manifest entries, gists), are never matched. Returns the spec with the old
+/// full name and checkout path; IDs matching more than one candidate are left alone.
+fn find_moved_repos<'a>(
+    repos: &'a [RepoSpec],
+    recorded: &[state::RepoState],
+) -> Vec<(&'a RepoSpec, String, PathBuf)> {
+    let discovered: HashSet<String> = repos.iter().map(RepoSpec::full_name).collect();
+    let orphans: Vec<(&str, (&str, u64), PathBuf)> = recorded
+        .iter()
+        .filter(|repo| !discovered.contains(&repo.full_name))
+        .filter_map(|repo| {
+            let id = (repo.provider.as_deref()?, repo.provider_id?);
+            let path = PathBuf::from(repo.local_path.as_deref()?);
+            (path.exists() && !has_skip_sentinel(&path)).then_some((
+                repo.full_name.as_str(),
+                id,
+                path,
+            ))
+        })
+        .collect();
+
+    repos
+        .iter()
+        .filter(|spec| !spec.is_wiki && !spec.local_path.exists())
+        .filter_map(|spec| {
+            let spec_id = (spec.provider.as_str(), spec.provider_id?);
+            let mut candidates = orphans
+                .iter()
+                .filter(|(_, id, path)| *id == spec_id && *path != spec.local_path);
+            let (old_name, _, old_path) = candidates.next()?;
+            if candidates.next().is_some() {
+                return None;
+            }
+            Some((spec, old_name.to_string(), old_path.clone()))
+        })
+        .collect()
+}
+
+/// Event type recorded for a skip, categorizing it by its reason
+fn skip_event_type(reason: &str) -> EventType {
+    if reason.starts_with(REMOTE_MISMATCH_REASON_PREFIX) {
+        EventType::RemoteMismatch
+    } else if reason.starts_with(REMOTE_INACCESSIBLE_REASON_PREFIX) {
+        EventType::RemoteInaccessible
+    } else if reason == SKIP_SENTINEL_REASON {
+        EventType::SkippedSentinel
+    } else if reason.starts_with(IN_PROGRESS_REASON_PREFIX) {
+        EventType::SkippedConflicts
+    } else if reason.contains("local changes") {
+        EventType::SkippedLocalChanges
+    } else if reason.contains("conflict") {
+        EventType::SkippedConflicts
+    } else if reason.contains("ahead") {
+        EventType::SkippedAheadOfRemote
+    } else {
+        EventType::SkippedLocalChanges // Default to local changes
+    }
+}
+
+/// Path of a repository that failed because the disk is full
+fn disk_full_path(result: &SyncResult) -> Option<&PathBuf> {
+    match result {
+        SyncResult::Failed { path, error } if is_disk_full(error) => Some(path),
+        _ => None,
+    }
+}
+
+/// Describe the action a sync took, for the per-repository audit trail
+///
+/// The verb comes first, followed by `key=value` details, e.g.
+/// `pull strategy=safe-pull remote=origin commits=3 auto-stash`.
+fn sync_action(result: &SyncResult, config: &Config) -> String {
+    let strategy = &config.sync.strategy;
+    let branch = |branch: &Option<String>| {
+        branch
+            .as_deref()
+            .map(|b| format!(" branch={}", b))
+            .unwrap_or_default()
+    };
+    let stash = |stashed: bool, conflict: bool| match (stashed, conflict) {
+        (_, true) => " auto-stash stash-conflict",
+        (true, false) => " auto-stash",
+        (false, false) => "",
+    };
+
+    match result {
+        SyncResult::Cloned { branch: b, .. } => {
+            let depth = config
+                .clone_depth()
+                .map(|depth| format!(" depth={}", depth))
+                .unwrap_or_default();
+            let mirror = if config.advanced.mirror_mode {
+                " mirror"
+            } else {
+                ""
+            };
+            format!("clone{}{}{}", branch(b), depth, mirror)
+        }
+        SyncResult::Pulled {
+            commits_updated,
+            branch: b,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => format!(
+            "pull strategy={} remote={} commits={}{}{}",
+            strategy,
+            remote,
+            commits_updated,
+            branch(b),
+            stash(*stashed, *stash_conflict)
+        ),
+        SyncResult::Rebased {
+            commits_replayed,
+            branch: b,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => format!(
+            "rebase strategy={} remote={} replayed={}{}{}",
+            strategy,
+            remote,
+            commits_replayed,
+            branch(b),
+            stash(*stashed, *stash_conflict)
+        ),
+        SyncResult::Merged {
+            commits_merged,
+            branch: b,
+            remote,
+            stashed,
+            stash_conflict,
+            ..
+        } => format!(
+            "merge strategy={} remote={} commits={}{}{}",
+            strategy,
+            remote,
+            commits_merged,
+            branch(b),
+            stash(*stashed, *stash_conflict)
+        ),
+        SyncResult::BranchSwitched {
+            from_branch,
+            to_branch,
+            commits_updated,
+            ..
+        } => format!(
+            "branch-switch strategy={} from={} to={} commits={}",
+            strategy, from_branch, to_branch, commits_updated
+        ),
+        SyncResult::FetchedOnly { reason, .. } => {
+            format!("fetch-only strategy={} reason={:?}", strategy, reason)
+        }
+        SyncResult::UpToDate { branch: b, .. } => {
+            format!("up-to-date strategy={}{}", strategy, branch(b))
+        }
+        SyncResult::Skipped { reason, .. } => format!("skip reason={:?}", reason),
+        SyncResult::Failed { .. } => "error".to_string(),
+    }
+}
+
+/// A sync run in the state database, finished when dropped
+struct SyncRun(Option<Arc<Mutex<StateDb>>>);
+
+impl Drop for SyncRun {
+    fn drop(&mut self) {
+        let Some(state_db) = &self.0 else {
+            return;
+        };
+
+        match state_db.lock() {
+            Ok(mut db) => {
+                if let Err(e) = db.finish_run() {
+                    warn!("Failed to finish sync run: {}", e);
+                }
+            }
+            Err(_) => warn!("Failed to acquire state database lock"),
+        }
+    }
+}
+
+/// Parallel operations the soft open-file limit leaves room for (None when
+/// unlimited or unknown)
+fn fd_concurrency_limit() -> Option<usize> {
+    #[cfg(unix)]
+    {
+        use nix::sys::resource::{getrlimit, Resource, RLIM_INFINITY};
+
+        let (soft, _) = getrlimit(Resource::RLIMIT_NOFILE).ok()?;
+        (soft != RLIM_INFINITY).then(|| concurrency_for_fd_limit(soft))
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Parallel operations that keep expected descriptor use well under `soft_limit`
+fn concurrency_for_fd_limit(soft_limit: u64) -> usize {
+    let available = soft_limit.saturating_sub(RESERVED_FDS);
+    (available / FDS_PER_OPERATION).max(1) as usize
+}
+
+/// Whether local branches are pruned after a sync with this result
+///
+/// Only existing checkouts that were fetched are; a fresh clone has no stale
+/// branches, and skipped or failed ones weren't fetched.
+fn prunes_after(result: &SyncResult) -> bool {
+    matches!(
+        result,
+        SyncResult::Pulled { .. }
+            | SyncResult::Rebased { .. }
+            | SyncResult::Merged { .. }
+            | SyncResult::BranchSwitched { .. }
+            | SyncResult::FetchedOnly { .. }
+            | SyncResult::UpToDate { .. }
+    )
+}
+
+/// AIMD controller for `sync.dynamic_concurrency`
+///
+/// Completion times are averaged over rounds of one operation per permit.
+/// A round much slower than the fastest seen so far means the network is
+/// saturated, so the limit is halved; otherwise it grows by one.
+struct ConcurrencyController {
+    limit: usize,
+    /// Highest the limit may grow to
+    ceiling: usize,
+    round: Vec<Duration>,
+    fastest_round: Option<Duration>,
+}
+
+impl ConcurrencyController {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.clamp(1, MAX_CONCURRENCY),
+            ceiling: MAX_CONCURRENCY,
+            round: Vec::new(),
+            fastest_round: None,
+        }
+    }
+
+    /// Never grow the limit past `ceiling` (`sync.max_parallel` or the
+    /// open-file limit, and at most `MAX_CONCURRENCY`)
+    fn with_ceiling(mut self, ceiling: usize) -> Self {
+        self.ceiling = ceiling.clamp(1, MAX_CONCURRENCY);
+        self.limit = self.limit.min(self.ceiling);
+        self
+    }
+
+    /// Record a completed operation, returning `(old, new)` when the limit changes
+    fn record(&mut self, elapsed: Duration) -> Option<(usize, usize)> {
+        self.round.push(elapsed);
+        if self.round.len() < self.limit {
+            return None;
+        }
+
+        let average = self.round.iter().sum::<Duration>() / self.round.len() as u32;
+        self.round.clear();
+
+        let fastest = *self.fastest_round.get_or_insert(average);
+        let old = self.limit;
+        if average.as_secs_f64() > fastest.as_secs_f64() * SATURATION_FACTOR {
+            self.limit = (self.limit / 2).max(1);
+        } else {
+            self.fastest_round = Some(fastest.min(average));
+            self.limit = (self.limit + 1).min(self.ceiling);
+        }
+
+        (self.limit != old).then_some((old, self.limit))
+    }
+}
+
+/// Reorder repositories round-robin across owners
+///
+/// Permits are handed out in dispatch order, so this lets every owner make
+/// progress concurrently. Owners keep their first-seen order, and each
+/// owner's repositories keep their relative order.
+fn interleave_by_owner(repos: Vec<RepoSpec>) -> Vec<RepoSpec> {
+    let total = repos.len();
+    let mut queues: Vec<(String, VecDeque<RepoSpec>)> = Vec::new();
+
+    for spec in repos {
+        match queues.iter_mut().find(|(owner, _)| *owner == spec.owner) {
+            Some((_, queue)) => queue.push_back(spec),
+            None => queues.push((spec.owner.clone(), VecDeque::from([spec]))),
+        }
+    }
+
+    let mut interleaved = Vec::with_capacity(total);
+    while interleaved.len() < total {
+        for (_, queue) in &mut queues {
+            if let Some(spec) = queue.pop_front() {
+                interleaved.push(spec);
+            }
+        }
+    }
+
+    interleaved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::test_support::test_spec;
+    use crate::git::SKIP_SENTINEL_FILENAME;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sync_summary_calculation() {
+        let results = vec![
+            SyncResult::Cloned {
+                path: PathBuf::from("/test/repo1"),
+                branch: Some("main".to_string()),
+            },
+            SyncResult::Pulled {
+                path: PathBuf::from("/test/repo2"),
+                commits_updated: 5,
+                branch: Some("main".to_string()),
+                remote: "origin".to_string(),
+                stashed: false,
+                stash_conflict: false,
+            },
+            SyncResult::Failed {
+                path: PathBuf::from("/test/repo3"),
+                error: "Test error".to_string(),
+            },
+            SyncResult::Skipped {
                 path: PathBuf::from("/test/repo4"),
                 reason: "Has changes".to_string(),
             },
@@ -552,6 +2069,1021 @@ mod tests {
         assert_eq!(summary.skipped_operations, 1);
     }
 
+    #[tokio::test]
+    async fn test_repo_status_joins_persisted_state() {
+        let db = StateDb::open_in_memory().unwrap();
+        db.upsert_repo(
+            "owner/repo",
+            Some("/nonexistent/reposentry/owner/repo"),
+            Some("main"),
+            RepoStatus::Skipped,
+            Some("local changes"),
+        )
+        .unwrap();
+        db.record_event(SyncEventBuilder::new(EventType::Cloned, "Cloned").repo("owner/repo"))
+            .unwrap();
+
+        let engine = SyncEngine::with_custom_state_db(Config::default(), db);
+        let view = engine.repo_status("owner/repo").await.unwrap();
+
+        assert_eq!(view.full_name, "owner/repo");
+        assert_eq!(
+            view.local_path,
+            PathBuf::from("/nonexistent/reposentry/owner/repo")
+        );
+        assert!(view.local.is_none());
+        let persisted = view.persisted.unwrap();
+        assert_eq!(persisted.last_sync_status, RepoStatus::Skipped);
+        assert_eq!(persisted.skip_reason, Some("local changes".to_string()));
+        assert_eq!(view.recent_events.len(), 1);
+    }
+
+    #[test]
+    fn test_first_run_repos() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("test").join("repo");
+        std::fs::create_dir_all(local_path.join(".git")).unwrap();
+
+        let spec = RepoSpec {
+            local_path,
+            ..test_spec("repo")
+        };
+        let repos = vec![spec];
+
+        let mut config = Config::default();
+        config.sync.first_run_mode = "fetch-only".to_string();
+        let engine =
+            SyncEngine::with_custom_state_db(config.clone(), StateDb::open_in_memory().unwrap());
+
+        // Never synced: first contact
+        assert!(engine.first_run_repos(&repos).contains("test/repo"));
+
+        // Recorded sync: normal behavior
+        engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .upsert_repo("test/repo", None, None, RepoStatus::Ok, None)
+            .unwrap();
+        assert!(engine.first_run_repos(&repos).is_empty());
+
+        // Default mode never gates
+        config.sync.first_run_mode = "sync".to_string();
+        let engine = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap());
+        assert!(engine.first_run_repos(&repos).is_empty());
+    }
+
+    #[test]
+    fn test_new_repos() {
+        let spec = |name: &str| RepoSpec {
+            local_path: PathBuf::from("/tmp/test").join(name),
+            ..test_spec(name)
+        };
+        let repos = vec![spec("old"), spec("new"), spec("new").wiki_spec()];
+
+        let engine =
+            SyncEngine::with_custom_state_db(Config::default(), StateDb::open_in_memory().unwrap());
+
+        // First sync: nothing is reported as new
+        assert!(engine.new_repos(&repos).is_empty());
+
+        engine.record_repo_sources(&repos[..1]);
+        let new_repos = engine.new_repos(&repos);
+        assert_eq!(new_repos, vec!["test/new"]);
+
+        engine.record_new_repos(&new_repos);
+        let events = engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_events_for_repo("test/new", None)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, EventType::NewRepo);
+    }
+
+    #[test]
+    fn test_record_depth_mismatches() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("test").join("repo");
+        std::fs::create_dir_all(local_path.join(".git")).unwrap();
+
+        let spec = RepoSpec {
+            local_path: local_path.clone(),
+            ..test_spec("repo")
+        };
+        let repos = vec![spec];
+
+        let mut config = Config::default();
+        config.sync.clone_depth = Some(1);
+        let engine =
+            SyncEngine::with_custom_state_db(config.clone(), StateDb::open_in_memory().unwrap());
+        let warnings = || {
+            engine
+                .state_db()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .get_events_for_repo("test/repo", None)
+                .unwrap()
+                .len()
+        };
+
+        // Full clone with a depth configured: warned once
+        engine.record_depth_mismatches(&repos);
+        engine.record_depth_mismatches(&repos);
+        assert_eq!(warnings(), 1);
+
+        // Shallow clone matches the configuration
+        std::fs::write(local_path.join(".git").join("shallow"), "").unwrap();
+        let engine2 = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap());
+        engine2.record_depth_mismatches(&repos);
+        assert!(engine2
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_events_for_repo("test/repo", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_record_analysis() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let spec = |name: &str| RepoSpec {
+            local_path: temp_dir.path().join("test").join(name),
+            ..test_spec(name)
+        };
+        let state = |spec: &RepoSpec, exists: bool, dirty: bool| RepoState {
+            path: spec.local_path.clone(),
+            exists,
+            has_uncommitted_changes: dirty,
+            has_untracked_files: false,
+            is_ahead_of_remote: false,
+            is_behind_remote: false,
+            has_conflicts: false,
+            remote_url: None,
+            current_branch: exists.then(|| "feature".to_string()),
+            is_shallow: false,
+            in_progress_operation: None,
+            branch_switch: None,
+        };
+        let repos = vec![spec("clean"), spec("dirty"), spec("missing")];
+        let states = vec![
+            state(&repos[0], true, false),
+            state(&repos[1], true, true),
+            state(&repos[2], false, false),
+        ];
+
+        let engine =
+            SyncEngine::with_custom_state_db(Config::default(), StateDb::open_in_memory().unwrap());
+        // "dirty" has been failing to sync
+        {
+            let db = engine.state_db().unwrap().lock().unwrap();
+            for _ in 0..2 {
+                db.upsert_repo(
+                    "test/dirty",
+                    None,
+                    Some("main"),
+                    RepoStatus::Error,
+                    Some("network error"),
+                )
+                .unwrap();
+            }
+        }
+        assert_eq!(engine.record_analysis(&repos, &states).unwrap(), 2);
+
+        let db = engine.state_db().unwrap().lock().unwrap();
+        let clean = db.get_repo("test/clean").unwrap().unwrap();
+        assert_eq!(clean.last_sync_status, RepoStatus::Unknown);
+        assert!(clean.last_sync_at.is_none());
+        assert_eq!(clean.current_branch.as_deref(), Some("feature"));
+        assert_eq!(clean.last_action.as_deref(), Some("analyze"));
+
+        // Analysis keeps the failure streak and the error it came from
+        let dirty = db.get_repo("test/dirty").unwrap().unwrap();
+        assert_eq!(dirty.last_sync_status, RepoStatus::Error);
+        assert_eq!(dirty.skip_reason.as_deref(), Some("network error"));
+        assert_eq!(dirty.consecutive_failures, 2);
+        assert!(dirty.last_sync_at.is_none());
+        assert_eq!(dirty.current_branch.as_deref(), Some("feature"));
+        assert!(db.get_repo("test/missing").unwrap().is_none());
+
+        // Analysis alone doesn't add events
+        assert!(db
+            .get_events_for_repo("test/dirty", None)
+            .unwrap()
+            .is_empty());
+
+        assert!(SyncEngine::new(Config::default())
+            .record_analysis(&repos, &states)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sync_action() {
+        let config = Config {
+            sync: crate::config::SyncConfig {
+                clone_depth: Some(1),
+                ..Default::default()
+            },
+            ..Config::default()
+        };
+        let path = PathBuf::from("/base/test/repo");
+
+        assert_eq!(
+            sync_action(
+                &SyncResult::Cloned {
+                    path: path.clone(),
+                    branch: Some("main".to_string()),
+                },
+                &config
+            ),
+            "clone branch=main depth=1"
+        );
+        assert_eq!(
+            sync_action(
+                &SyncResult::Pulled {
+                    path: path.clone(),
+                    commits_updated: 3,
+                    branch: None,
+                    remote: "origin".to_string(),
+                    stashed: true,
+                    stash_conflict: false,
+                },
+                &config
+            ),
+            "pull strategy=safe-pull remote=origin commits=3 auto-stash"
+        );
+        assert_eq!(
+            sync_action(
+                &SyncResult::BranchSwitched {
+                    path: path.clone(),
+                    from_branch: "main".to_string(),
+                    to_branch: "dev".to_string(),
+                    commits_updated: 1,
+                },
+                &config
+            ),
+            "branch-switch strategy=safe-pull from=main to=dev commits=1"
+        );
+        assert_eq!(
+            sync_action(
+                &SyncResult::FetchedOnly {
+                    path,
+                    reason: "Repository has unresolved conflicts".to_string(),
+                },
+                &config
+            ),
+            "fetch-only strategy=safe-pull reason=\"Repository has unresolved conflicts\""
+        );
+    }
+
+    #[test]
+    fn test_chronic_failure_event() {
+        let engine =
+            SyncEngine::with_custom_state_db(Config::default(), StateDb::open_in_memory().unwrap());
+        let failed = SyncResult::Failed {
+            path: PathBuf::from("/base/test/flaky"),
+            error: "Connection timed out".to_string(),
+        };
+        let chronic_events = || {
+            engine
+                .state_db()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .get_events_for_repo("test/flaky", None)
+                .unwrap()
+                .into_iter()
+                .filter(|event| event.event_type == EventType::ChronicFailure)
+                .count()
+        };
+
+        engine.record_sync_results(&[failed.clone(), failed.clone()]);
+        assert_eq!(chronic_events(), 0);
+
+        // Reported once when the streak reaches the threshold (3)
+        engine.record_sync_results(&[failed.clone(), failed]);
+        assert_eq!(chronic_events(), 1);
+
+        let repo = engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_repo("test/flaky")
+            .unwrap()
+            .unwrap();
+        assert_eq!(repo.last_action.as_deref(), Some("error"));
+    }
+
+    #[test]
+    fn test_stash_conflict_event_after_rebase() {
+        let engine =
+            SyncEngine::with_custom_state_db(Config::default(), StateDb::open_in_memory().unwrap());
+        engine.record_sync_results(&[SyncResult::Rebased {
+            path: PathBuf::from("/base/test/repo"),
+            commits_replayed: 1,
+            branch: Some("main".to_string()),
+            remote: "origin".to_string(),
+            stashed: true,
+            stash_conflict: true,
+        }]);
+
+        let db = engine.state_db().unwrap().lock().unwrap();
+        let event_types: Vec<EventType> = db
+            .get_events_for_repo("test/repo", None)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.event_type)
+            .collect();
+        assert!(event_types.contains(&EventType::Rebased));
+        assert!(event_types.contains(&EventType::StashConflict));
+        assert_eq!(
+            db.get_repo("test/repo").unwrap().unwrap().last_action.as_deref(),
+            Some("rebase strategy=safe-pull remote=origin replayed=1 branch=main auto-stash stash-conflict")
+        );
+    }
+
+    #[test]
+    fn test_failures_by_kind() {
+        let failed = |name: &str, error: &str| SyncResult::Failed {
+            path: PathBuf::from(format!("/base/acme/{}", name)),
+            error: error.to_string(),
+        };
+        let auth = "fatal: Authentication failed";
+        let timeout = "Operation timed out after 300s";
+        let summary = SyncEngine::new(Config::default()).compile_summary(
+            vec![
+                failed("web", auth),
+                failed("huge", timeout),
+                SyncResult::UpToDate {
+                    path: PathBuf::from("/base/acme/docs"),
+                    branch: None,
+                },
+                failed("api", auth),
+            ],
+            Duration::ZERO,
+        );
+
+        let groups = summary.failures_by_kind();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, FailureKind::Auth);
+        assert_eq!(
+            groups[0].1,
+            [
+                (Path::new("/base/acme/api"), auth),
+                (Path::new("/base/acme/web"), auth)
+            ]
+        );
+        assert_eq!(groups[1].0, FailureKind::Timeout);
+        assert_eq!(groups[1].1, [(Path::new("/base/acme/huge"), timeout)]);
+    }
+
+    #[test]
+    fn test_skips_by_reason() {
+        let skipped = |name: &str, reason: &str| SyncResult::Skipped {
+            path: PathBuf::from(format!("/base/acme/{}", name)),
+            reason: reason.to_string(),
+        };
+        let summary = SyncEngine::new(Config::default()).compile_summary(
+            vec![
+                skipped("web", "Repository has local changes"),
+                skipped("api", "Repository has local changes"),
+                skipped("docs", SKIP_SENTINEL_REASON),
+                skipped("api.wiki", WIKI_NOT_CREATED_REASON),
+            ],
+            Duration::ZERO,
+        );
+
+        let groups = summary.skips_by_reason();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, EventType::SkippedLocalChanges);
+        assert_eq!(
+            groups[0].1,
+            [Path::new("/base/acme/api"), Path::new("/base/acme/web")]
+        );
+        assert_eq!(groups[1].0, EventType::SkippedSentinel);
+    }
+
+    #[test]
+    fn test_summary_json_round_trip() {
+        let summary = SyncEngine::new(Config::default()).compile_summary(
+            vec![
+                SyncResult::Pulled {
+                    path: PathBuf::from("/base/acme/api"),
+                    commits_updated: 3,
+                    branch: Some("main".to_string()),
+                    remote: "origin".to_string(),
+                    stashed: false,
+                    stash_conflict: false,
+                },
+                SyncResult::FetchedOnly {
+                    path: PathBuf::from("/base/acme/web"),
+                    reason: "Repository has local changes".to_string(),
+                },
+            ],
+            Duration::from_millis(1500),
+        );
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["results"][0]["outcome"], "pulled");
+        assert_eq!(value["results"][0]["commits_updated"], 3);
+        assert_eq!(value["results"][1]["outcome"], "fetched-only");
+
+        let parsed: SyncSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.total_repositories, summary.total_repositories);
+        assert_eq!(parsed.successful_operations, summary.successful_operations);
+        assert_eq!(parsed.duration, summary.duration);
+        assert!(matches!(
+            &parsed.results[0],
+            SyncResult::Pulled { commits_updated: 3, remote, .. } if remote == "origin"
+        ));
+        assert!(matches!(
+            &parsed.results[1],
+            SyncResult::FetchedOnly { path, .. } if path == Path::new("/base/acme/web")
+        ));
+    }
+
+    #[test]
+    fn test_auth_broken() {
+        let auth_failure = |name: &str| SyncResult::Failed {
+            path: PathBuf::from(format!("/base/acme/{}", name)),
+            error: "Sync operation failed: fatal: Authentication failed".to_string(),
+        };
+        let pulled = SyncResult::UpToDate {
+            path: PathBuf::from("/base/acme/web"),
+            branch: None,
+        };
+        let engine = SyncEngine::new(Config::default());
+
+        let failures = vec![auth_failure("a"), auth_failure("b"), auth_failure("c")];
+        assert_eq!(
+            engine.auth_broken(&failures),
+            Some("Sync operation failed: fatal: Authentication failed")
+        );
+        assert_eq!(engine.auth_broken(&failures[..2]), None);
+
+        // One success among the first results means the credentials work
+        let mixed = vec![auth_failure("a"), pulled, auth_failure("c")];
+        assert_eq!(engine.auth_broken(&mixed), None);
+
+        let mut config = Config::default();
+        config.sync.continue_on_auth_error = true;
+        assert_eq!(SyncEngine::new(config).auth_broken(&failures), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_auth_abort_finishes_running_syncs() {
+        use crate::git::GitRunner;
+        use std::ffi::OsString;
+        use std::os::unix::process::ExitStatusExt;
+        use std::path::Path;
+        use std::process::{ExitStatus, Output};
+
+        /// Clones fail to authenticate, except the slow one, which succeeds
+        struct AuthFailingRunner;
+
+        #[async_trait::async_trait]
+        impl GitRunner for AuthFailingRunner {
+            async fn run(
+                &self,
+                args: &[OsString],
+                _cwd: Option<&Path>,
+                _envs: &[(String, String)],
+            ) -> std::io::Result<Output> {
+                let args: Vec<_> = args.iter().map(|arg| arg.to_string_lossy()).collect();
+                let fails = args.iter().any(|arg| arg == "clone")
+                    && !args.iter().any(|arg| arg.contains("slow"));
+                if args.iter().any(|arg| arg.contains("slow")) {
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                }
+                Ok(Output {
+                    status: ExitStatus::from_raw(if fails { 128 << 8 } else { 0 }),
+                    stdout: Vec::new(),
+                    stderr: if fails {
+                        b"fatal: Authentication failed".to_vec()
+                    } else {
+                        Vec::new()
+                    },
+                })
+            }
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repos: Vec<RepoSpec> = ["a", "slow", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| RepoSpec {
+                local_path: temp_dir.path().join(name),
+                ..test_spec(name)
+            })
+            .collect();
+
+        let mut config = Config::default();
+        config.sync.max_parallel = 2;
+        config.sync.dynamic_concurrency = false;
+        config.advanced.verify_clone = false;
+        let mut engine = SyncEngine::new(config);
+        engine.git_client = engine
+            .git_client
+            .clone()
+            .with_dry_run(false)
+            .with_runner(Arc::new(AuthFailingRunner));
+
+        // a, b and c fail while slow is still cloning: it finishes and is
+        // recorded, while d and e never start
+        let (results, _) = engine.sync_specs_parallel(repos).await.unwrap();
+        let paths: Vec<_> = results.iter().map(|result| result.path()).collect();
+        assert_eq!(results.len(), 4, "{:?}", results);
+        assert!(paths.contains(&temp_dir.path().join("slow").as_path()));
+        assert!(!paths.contains(&temp_dir.path().join("d").as_path()));
+        assert!(engine.auth_broken(&results).is_some());
+    }
+
+    #[test]
+    fn test_repeated_timeout_event() {
+        let mut config = Config::default();
+        config.sync.timeout = 60;
+        let engine =
+            SyncEngine::with_custom_state_db(config.clone(), StateDb::open_in_memory().unwrap());
+        let timeout_events = |engine: &SyncEngine| {
+            engine
+                .state_db()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .get_events_for_repo("test/huge", None)
+                .unwrap()
+                .into_iter()
+                .filter(|event| event.event_type == EventType::RepeatedTimeout)
+                .map(|event| event.summary)
+                .collect::<Vec<_>>()
+        };
+
+        // A sync that finishes in time resets the count
+        engine.record_timeout_outcome("test/huge", true);
+        engine.record_timeout_outcome("test/huge", false);
+        engine.record_timeout_outcome("test/huge", true);
+        engine.record_timeout_outcome("test/huge", true);
+        assert!(timeout_events(&engine).is_empty());
+
+        engine.record_timeout_outcome("test/huge", true);
+        assert_eq!(
+            timeout_events(&engine),
+            ["Timed out 3 syncs in a row after 60s: consider adding \"test/huge: 120\" to sync.timeout_overrides"]
+        );
+        assert_eq!(engine.repo_timeout("test/huge"), 60);
+
+        config.sync.auto_extend_timeout = true;
+        let engine = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap());
+        for _ in 0..3 {
+            engine.record_timeout_outcome("test/huge", true);
+        }
+        assert_eq!(engine.repo_timeout("test/huge"), 120);
+        assert_eq!(engine.repo_timeout("test/small"), 60);
+        assert_eq!(
+            timeout_events(&engine),
+            ["Timed out 3 syncs in a row: timeout extended from 60s to 120s"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_remote_mismatches() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+
+        // Checkout of `old`, while discovery expects `new` (same history)
+        let old = temp_dir.path().join("old");
+        std::fs::create_dir(&old).unwrap();
+        git(&old, &["init", "--quiet"]);
+        git(
+            &old,
+            &["commit", "--quiet", "--allow-empty", "-m", "initial"],
+        );
+        let new = temp_dir.path().join("new");
+        let local_path = temp_dir.path().join("test").join("repo");
+        git(
+            temp_dir.path(),
+            &[
+                "clone",
+                "--quiet",
+                old.to_str().unwrap(),
+                new.to_str().unwrap(),
+            ],
+        );
+        git(
+            temp_dir.path(),
+            &[
+                "clone",
+                "--quiet",
+                old.to_str().unwrap(),
+                local_path.to_str().unwrap(),
+            ],
+        );
+
+        let spec = RepoSpec {
+            clone_url: new.to_str().unwrap().to_string(),
+            clone_method: crate::discovery::CloneMethod::Https,
+            local_path: local_path.clone(),
+            ..test_spec("repo")
+        };
+
+        // Default "skip": left alone and reported
+        let engine = SyncEngine::new(Config::default());
+        let (to_sync, skipped) = engine.resolve_remote_mismatches(vec![spec.clone()]).await;
+        assert!(to_sync.is_empty());
+        assert!(matches!(
+            &skipped[..],
+            [SyncResult::Skipped { reason, .. }] if reason.starts_with(REMOTE_MISMATCH_REASON_PREFIX)
+        ));
+
+        // "update-remote": origin repointed and the action recorded
+        let mut config = Config::default();
+        config.advanced.on_remote_mismatch = "update-remote".to_string();
+        let engine = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap());
+        let (to_sync, skipped) = engine.resolve_remote_mismatches(vec![spec]).await;
+        assert_eq!(to_sync.len(), 1);
+        assert!(skipped.is_empty());
+        assert_eq!(
+            engine
+                .git_client()
+                .get_remote_url(&local_path)
+                .await
+                .unwrap()
+                .as_deref(),
+            new.to_str()
+        );
+        let events = engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_events_for_repo("test/repo", None)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, EventType::RemoteMismatch);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_checkouts_skips_sentinel_before_remote_mismatch() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("test").join("repo");
+        let origin = "https://example.com/other/repo.git";
+        std::fs::create_dir_all(&local_path).unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &["remote", "add", "origin", origin],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&local_path)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+        std::fs::write(local_path.join(SKIP_SENTINEL_FILENAME), "").unwrap();
+
+        let spec = RepoSpec {
+            clone_url: "https://example.com/test/repo.git".to_string(),
+            clone_method: crate::discovery::CloneMethod::Https,
+            local_path: local_path.clone(),
+            ..test_spec("repo")
+        };
+
+        // Would repoint origin, if the sentinel didn't come first
+        let mut config = Config::default();
+        config.advanced.on_remote_mismatch = "update-remote".to_string();
+        let engine = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap());
+        let (to_sync, skipped) = engine.prepare_checkouts(vec![spec]).await;
+        assert!(to_sync.is_empty());
+        assert!(matches!(
+            &skipped[..],
+            [SyncResult::Skipped { reason, .. }] if reason == SKIP_SENTINEL_REASON
+        ));
+        assert_eq!(
+            engine
+                .git_client()
+                .get_remote_url(&local_path)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(origin)
+        );
+        let events = engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_events_for_repo("test/repo", None)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_result_callback_sees_each_result() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repos: Vec<RepoSpec> = (0..3)
+            .map(|i| RepoSpec {
+                clone_url: temp_dir
+                    .path()
+                    .join("missing")
+                    .to_string_lossy()
+                    .to_string(),
+                clone_method: crate::discovery::CloneMethod::Https,
+                local_path: temp_dir.path().join(format!("repo{}", i)),
+                ..test_spec(&format!("repo{}", i))
+            })
+            .collect();
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = seen.clone();
+        let engine =
+            SyncEngine::new(Config::default()).with_result_callback(Box::new(move |result| {
+                assert!(matches!(result, SyncResult::Failed { .. }));
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+
+        let (results, durations) = engine.sync_specs_parallel(repos).await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(durations.len(), 3);
+        assert_eq!(seen.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_sync_one() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let spec = RepoSpec {
+            clone_url: temp_dir
+                .path()
+                .join("missing")
+                .to_string_lossy()
+                .to_string(),
+            clone_method: crate::discovery::CloneMethod::Https,
+            local_path: temp_dir.path().join("test").join("repo"),
+            ..test_spec("repo")
+        };
+
+        let engine =
+            SyncEngine::with_custom_state_db(Config::default(), StateDb::open_in_memory().unwrap())
+                .wait_for_lock(true);
+        let result = engine.sync_one(spec).await.unwrap();
+        assert!(matches!(result, SyncResult::Failed { .. }));
+
+        let db = engine.state_db().unwrap().lock().unwrap();
+        let repo = db.get_repo("test/repo").unwrap().unwrap();
+        assert_eq!(repo.last_sync_status, RepoStatus::Error);
+        assert_eq!(repo.source.as_deref(), Some("owned"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_one_leaves_sentinel_checkout_alone() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("test").join("repo");
+        let origin = "https://example.com/other/repo.git";
+        std::fs::create_dir_all(&local_path).unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &["remote", "add", "origin", origin],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&local_path)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+        std::fs::write(local_path.join(SKIP_SENTINEL_FILENAME), "").unwrap();
+
+        let spec = RepoSpec {
+            clone_url: "https://example.com/test/repo.git".to_string(),
+            clone_method: crate::discovery::CloneMethod::Https,
+            local_path: local_path.clone(),
+            ..test_spec("repo")
+        };
+
+        let mut config = Config::default();
+        config.advanced.on_remote_mismatch = "update-remote".to_string();
+        let engine = SyncEngine::with_custom_state_db(config, StateDb::open_in_memory().unwrap())
+            .wait_for_lock(true);
+        let result = engine.sync_one(spec).await.unwrap();
+        assert!(matches!(
+            result,
+            SyncResult::Skipped { ref reason, .. } if reason == SKIP_SENTINEL_REASON
+        ));
+        assert_eq!(
+            engine
+                .git_client()
+                .get_remote_url(&local_path)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(origin)
+        );
+        let events = engine
+            .state_db()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .get_events_for_repo("test/repo", None)
+            .unwrap();
+        assert!(events
+            .iter()
+            .all(|event| event.event_type == EventType::SkippedSentinel));
+    }
+
+    #[test]
+    fn test_concurrency_controller() {
+        let secs = Duration::from_secs;
+        let mut controller = ConcurrencyController::new(2);
+
+        // A round is one completion per permit; steady rounds ramp up
+        assert_eq!(controller.record(secs(10)), None);
+        assert_eq!(controller.record(secs(10)), Some((2, 3)));
+        for _ in 0..2 {
+            assert_eq!(controller.record(secs(8)), None);
+        }
+        assert_eq!(controller.record(secs(8)), Some((3, 4)));
+
+        // Much slower than the fastest round: back off multiplicatively
+        for _ in 0..3 {
+            controller.record(secs(30));
+        }
+        assert_eq!(controller.record(secs(30)), Some((4, 2)));
+
+        // Never below one or above the maximum
+        let mut controller = ConcurrencyController::new(1);
+        assert_eq!(controller.record(secs(1)), Some((1, 2)));
+        controller.record(secs(100));
+        assert_eq!(controller.record(secs(100)), Some((2, 1)));
+        assert_eq!(controller.record(secs(100)), None);
+        assert_eq!(controller.limit, 1);
+        let mut controller = ConcurrencyController::new(MAX_CONCURRENCY);
+        for _ in 0..MAX_CONCURRENCY {
+            controller.record(secs(1));
+        }
+        assert_eq!(controller.limit, MAX_CONCURRENCY);
+
+        // A ceiling (from the open-file limit) caps growth
+        let mut controller = ConcurrencyController::new(4).with_ceiling(3);
+        assert_eq!(controller.limit, 3);
+        for _ in 0..3 {
+            controller.record(secs(1));
+        }
+        assert_eq!(controller.limit, 3);
+    }
+
+    #[test]
+    fn test_dynamic_concurrency_stops_at_max_parallel() {
+        let mut config = Config::default();
+        config.sync.max_parallel = 3;
+        assert!(SyncEngine::new(config.clone())
+            .concurrency_controller(2, None)
+            .is_none());
+
+        config.sync.dynamic_concurrency = true;
+        let engine = SyncEngine::new(config);
+        let mut controller = engine.concurrency_controller(2, None).unwrap();
+        for _ in 0..MAX_CONCURRENCY * MAX_CONCURRENCY {
+            controller.record(Duration::from_secs(1));
+        }
+        assert_eq!(controller.limit, 3);
+
+        // The open file limit still wins when it's lower
+        let controller = engine.concurrency_controller(3, Some(2)).unwrap();
+        assert_eq!(controller.limit, 2);
+        assert_eq!(controller.ceiling, 2);
+    }
+
+    #[test]
+    fn test_concurrency_for_fd_limit() {
+        // macOS's default of 256 leaves room for a dozen git operations
+        assert_eq!(concurrency_for_fd_limit(256), 12);
+        assert_eq!(concurrency_for_fd_limit(1024), 60);
+        // Even a tiny limit allows one operation at a time
+        assert_eq!(concurrency_for_fd_limit(64), 1);
+        assert_eq!(concurrency_for_fd_limit(20), 1);
+    }
+
+    #[test]
+    fn test_interleave_by_owner() {
+        let spec = |owner: &str, name: &str| RepoSpec {
+            owner: owner.to_string(),
+            clone_url: format!("git@github.com:{}/{}.git", owner, name),
+            local_path: PathBuf::from(format!("/test/{}/{}", owner, name)),
+            ..test_spec(name)
+        };
+        let repos = vec![
+            spec("big", "a"),
+            spec("big", "b"),
+            spec("big", "c"),
+            spec("small", "x"),
+            spec("me", "y"),
+            spec("small", "z"),
+        ];
+
+        let order: Vec<String> = interleave_by_owner(repos)
+            .iter()
+            .map(|spec| spec.full_name())
+            .collect();
+
+        assert_eq!(
+            order,
+            vec!["big/a", "small/x", "me/y", "big/b", "small/z", "big/c"]
+        );
+    }
+
+    #[test]
+    fn test_find_moved_repos() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base = temp_dir.path();
+        let spec = |owner: &str, name: &str, provider_id: Option<u64>| RepoSpec {
+            owner: owner.to_string(),
+            clone_url: format!("git@github.com:{}/{}.git", owner, name),
+            local_path: base.join(owner).join(name),
+            provider: "github".to_string(),
+            provider_id,
+            ..test_spec(name)
+        };
+        let recorded = |full_name: &str, provider_id: Option<u64>| state::RepoState {
+            id: 0,
+            full_name: full_name.to_string(),
+            local_path: Some(base.join(full_name).to_string_lossy().to_string()),
+            current_branch: None,
+            last_sync_at: None,
+            last_sync_status: RepoStatus::Ok,
+            skip_reason: None,
+            updated_at: chrono::Utc::now(),
+            source: None,
+            consecutive_failures: 0,
+            last_action: None,
+            provider_id,
+            provider: Some("github".to_string()),
+        };
+        let repos = vec![
+            spec("new", "moved", Some(1)),
+            spec("new", "twin", Some(3)),
+            spec("new", "kept", Some(4)),
+            spec("new", "fresh", Some(5)),
+            // Same name as a recorded repository, but a different one
+            spec("bob", "utils", Some(7)),
+            // No ID to confirm the match with
+            spec("new", "noid", None),
+            // Same ID, but on another provider
+            RepoSpec {
+                provider: "gitlab".to_string(),
+                ..spec("new", "lab", Some(8))
+            },
+        ];
+        let recorded = vec![
+            recorded("old/moved", Some(1)),
+            recorded("old/gone", Some(2)),
+            recorded("a/twin", Some(3)),
+            recorded("b/twin", Some(3)),
+            recorded("new/kept", Some(4)),
+            recorded("alice/utils", Some(6)),
+            recorded("old/noid", None),
+            recorded("old/lab", Some(8)),
+        ];
+        for repo in &recorded {
+            std::fs::create_dir_all(repo.local_path.as_deref().unwrap()).unwrap();
+        }
+
+        let moves = find_moved_repos(&repos, &recorded);
+        assert_eq!(moves.len(), 1);
+        let (spec, old_name, old_path) = &moves[0];
+        assert_eq!(spec.full_name(), "new/moved");
+        assert_eq!(old_name, "old/moved");
+        assert_eq!(*old_path, base.join("old/moved"));
+    }
+
+    #[tokio::test]
+    async fn test_repo_status_invalid_name() {
+        let engine = SyncEngine::new(Config::default());
+        assert!(engine.repo_status("no-slash").await.is_err());
+    }
+
     #[test]
     fn test_sync_engine_creation() {
         let config = Config::default();
@@ -567,17 +3099,10 @@ mod tests {
         // Test with small repos
         let small_repos: Vec<RepoSpec> = (0..5)
             .map(|i| RepoSpec {
-                name: format!("repo{}", i),
-                owner: "test".to_string(),
-                clone_url: format!("git@github.com:test/repo{}.git", i),
-                clone_url_alt: None,
-                clone_method: crate::discovery::CloneMethod::Ssh,
                 local_path: PathBuf::from(format!("/test/repo{}", i)),
-                is_fork: false,
-                is_archived: false,
                 size_bytes: Some(1024 * 1024), // 1MB
                 default_branch: Some("main".to_string()),
-                provider: "test".to_string(),
+                ..test_spec(&format!("repo{}", i))
             })
             .collect();
 
@@ -593,17 +3118,10 @@ mod tests {
         // Large number of large repos should reduce concurrency
         let large_repos: Vec<RepoSpec> = (0..100)
             .map(|i| RepoSpec {
-                name: format!("repo{}", i),
-                owner: "test".to_string(),
-                clone_url: format!("git@github.com:test/repo{}.git", i),
-                clone_url_alt: None,
-                clone_method: crate::discovery::CloneMethod::Ssh,
                 local_path: PathBuf::from(format!("/test/repo{}", i)),
-                is_fork: false,
-                is_archived: false,
                 size_bytes: Some(100 * 1024 * 1024), // 100MB
                 default_branch: Some("main".to_string()),
-                provider: "test".to_string(),
+                ..test_spec(&format!("repo{}", i))
             })
             .collect();
 
@@ -3,20 +3,35 @@
 use super::events::{AppEvent, EventHandler};
 use super::widgets::{ColorScheme, ProgressDialog};
 use crate::daemon::is_daemon_running;
-use crate::discovery::{Discovery, GitHubDiscovery, RepoSpec};
-use crate::git::{RepoState, SyncResult};
+use crate::discovery::{
+    configured_sources, discover_from, load_cached_specs, store_cached_specs, Activity, RepoSource,
+    RepoSpec,
+};
+use crate::git::{
+    in_progress_operation, GitClient, RepoState, SyncResult, DRY_RUN_ENV, DRY_RUN_REASON_PREFIX,
+};
+use crate::state::{self, RepoStatus, StateDb};
 use crate::sync::{SyncEngine, SyncSummary};
 use crate::Config;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     widgets::ListState,
     Frame,
 };
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// How long transient status messages stay visible
+const TRANSIENT_STATUS_DURATION: Duration = Duration::from_secs(3);
+
+/// How often to poll the state database for activity from the daemon
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 /// Which panel has focus
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FocusedPanel {
@@ -31,6 +46,25 @@ pub enum RightPanelTab {
     Config,
 }
 
+/// Repository list ordering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepoSortMode {
+    /// Problem repositories first (conflicts, local changes, failed syncs),
+    /// then repositories needing a sync, then up-to-date ones
+    IssuesFirst,
+    /// By repository name
+    Alphabetical,
+}
+
+impl RepoSortMode {
+    fn label(self) -> &'static str {
+        match self {
+            RepoSortMode::IssuesFirst => "issues first",
+            RepoSortMode::Alphabetical => "alphabetical",
+        }
+    }
+}
+
 /// Application state
 pub struct App {
     config: Config,
@@ -49,6 +83,7 @@ pub struct App {
     repositories: Vec<RepoState>,
     selected_repo: usize,
     list_state: ListState,
+    sort_mode: RepoSortMode,
 
     // Status
     daemon_running: bool,
@@ -56,6 +91,7 @@ pub struct App {
     last_sync_summary: Option<SyncSummary>,
     current_operation: Option<String>,
     status_message: String,
+    transient_status: Option<(String, Instant)>,
     logs: Vec<String>,
     log_scroll_offset: usize,
 
@@ -63,6 +99,9 @@ pub struct App {
     show_help: bool,
     show_progress: bool,
     show_error: Option<String>,
+    show_repo_actions: bool,
+    /// `git status` output of a repository (title, output)
+    show_git_status: Option<(String, String)>,
 
     // Config display
     config_text: String,
@@ -76,6 +115,19 @@ pub struct App {
     is_loading: bool,
     is_analyzing: bool,
     discovery_receiver: Option<mpsc::Receiver<DiscoveryMessage>>,
+    /// When the displayed repository list was fetched, if it came from the cache
+    specs_cached_at: Option<DateTime<Utc>>,
+    /// Interval between background re-analyses (`tui.auto_refresh`)
+    auto_refresh: Option<Duration>,
+    /// When repository states were last analyzed
+    last_refreshed: Instant,
+
+    // Activity recorded by the daemon (or CLI syncs) in the shared state database
+    state_db: Option<StateDb>,
+    last_event_id: i64,
+    last_state_update: DateTime<Utc>,
+    last_state_poll: Instant,
+    recorded_failures: HashSet<PathBuf>,
 }
 
 /// Message sent from background discovery task
@@ -84,10 +136,16 @@ pub enum DiscoveryMessage {
     Started,
     /// Progress update
     Progress(String),
-    /// Repositories discovered (before local analysis)
-    SpecsDiscovered(Vec<RepoSpec>),
+    /// Repositories discovered (before local analysis), with when they were
+    /// fetched if they came from the cache
+    SpecsDiscovered(Vec<RepoSpec>, Option<DateTime<Utc>>),
     /// Analysis completed for repositories
     AnalysisCompleted(Vec<RepoState>),
+    /// Auto-refresh completed, with the re-discovered repositories if
+    /// `tui.auto_refresh_discovery` is set
+    Refreshed(Option<Vec<RepoSpec>>, Vec<RepoState>),
+    /// Auto-refresh couldn't re-discover repositories
+    RefreshFailed(String),
     /// Discovery failed
     Failed(String),
 }
@@ -101,11 +159,12 @@ impl App {
         // Create event handler
         let event_handler = EventHandler::new(Duration::from_millis(250));
 
-        // Create sync engine (provider-agnostic) - fast, no I/O
-        let sync_engine = SyncEngine::new(config.clone());
+        // Create sync engine (provider-agnostic), with the token for HTTPS remotes if enabled
+        let sync_engine = SyncEngine::new(config.clone()).with_token_auth().await;
 
         // Check daemon status - fast, just reads a file
         let daemon_running = is_daemon_running(&config).unwrap_or(false);
+        let auto_refresh = config.tui.auto_refresh_interval();
 
         // Initialize list state
         let mut list_state = ListState::default();
@@ -119,45 +178,72 @@ impl App {
         let config_path = Config::default_config_path()
             .unwrap_or_else(|_| std::path::PathBuf::from("~/.config/reposentry/config.yml"));
 
+        // Shared state database; only activity recorded from now on is shown
+        let state_db = StateDb::open().ok();
+        let last_event_id = state_db
+            .as_ref()
+            .and_then(|db| db.latest_event_id().ok())
+            .unwrap_or(0);
+
+        let mut logs = vec![
+            "Application started".to_string(),
+            "Discovering repositories in background...".to_string(),
+        ];
+        logs.extend(
+            config
+                .unknown_keys
+                .iter()
+                .map(|unknown_key| format!("WARNING: Ignoring {}", unknown_key)),
+        );
+        logs.extend(
+            config
+                .load_warnings
+                .iter()
+                .map(|warning| format!("WARNING: {}", warning)),
+        );
+
         // Create channel for background discovery
         let (tx, rx) = mpsc::channel(32);
 
+        // A fresh cached repository list skips the GitHub round trip
+        let cached = state_db
+            .as_ref()
+            .and_then(|db| load_cached_specs(db, &config));
+
         // Spawn background discovery task
         let discovery_config = config.clone();
         tokio::spawn(async move {
             let _ = tx.send(DiscoveryMessage::Started).await;
-            let _ = tx.send(DiscoveryMessage::Progress("Connecting to GitHub...".to_string())).await;
-
-            match GitHubDiscovery::new(discovery_config.clone()).await {
-                Ok(discovery) => {
-                    let _ = tx.send(DiscoveryMessage::Progress("Fetching repositories...".to_string())).await;
 
-                    match discovery.discover().await {
-                        Ok(specs) => {
-                            let count = specs.len();
-
-                            // Send specs immediately so UI can show the list
-                            let _ = tx.send(DiscoveryMessage::SpecsDiscovered(specs.clone())).await;
-
-                            let _ = tx.send(DiscoveryMessage::Progress(
-                                format!("Analyzing {} repositories...", count)
-                            )).await;
+            let (specs, cached_at) = match cached {
+                Some(cached) => (cached.specs, Some(cached.fetched_at)),
+                None => match discover_and_cache(&discovery_config, &tx).await {
+                    Ok(specs) => (specs, None),
+                    Err(error) => {
+                        let _ = tx.send(DiscoveryMessage::Failed(error)).await;
+                        return;
+                    }
+                },
+            };
+            let count = specs.len();
+
+            // Send specs immediately so UI can show the list
+            let _ = tx
+                .send(DiscoveryMessage::SpecsDiscovered(specs.clone(), cached_at))
+                .await;
+
+            let _ = tx
+                .send(DiscoveryMessage::Progress(format!(
+                    "Analyzing {} repositories...",
+                    count
+                )))
+                .await;
 
-                            // Analyze repos - this is slower, runs after list is displayed
-                            let sync_engine = SyncEngine::new(discovery_config);
-                            let states = sync_engine.analyze_repos(&specs).await.unwrap_or_default();
+            // Analyze repos - this is slower, runs after list is displayed
+            let sync_engine = SyncEngine::new(discovery_config);
+            let states = sync_engine.analyze_repos(&specs).await.unwrap_or_default();
 
-                            let _ = tx.send(DiscoveryMessage::AnalysisCompleted(states)).await;
-                        }
-                        Err(e) => {
-                            let _ = tx.send(DiscoveryMessage::Failed(format!("Discovery failed: {}", e))).await;
-                        }
-                    }
-                }
-                Err(e) => {
-                    let _ = tx.send(DiscoveryMessage::Failed(format!("GitHub connection failed: {}", e))).await;
-                }
-            }
+            let _ = tx.send(DiscoveryMessage::AnalysisCompleted(states)).await;
         });
 
         Ok(Self {
@@ -170,20 +256,21 @@ impl App {
             right_panel_tab: RightPanelTab::Log,
             repositories: Vec::new(),
             selected_repo: 0,
+            sort_mode: RepoSortMode::IssuesFirst,
             list_state,
             daemon_running,
             last_sync: None,
             last_sync_summary: None,
             current_operation: Some("Discovering repositories...".to_string()),
             status_message: "Loading...".to_string(),
-            logs: vec![
-                "Application started".to_string(),
-                "Discovering repositories in background...".to_string(),
-            ],
+            transient_status: None,
+            logs,
             log_scroll_offset: 0,
             show_help: false,
             show_progress: false,
             show_error: None,
+            show_repo_actions: false,
+            show_git_status: None,
             config_text,
             config_path,
             should_exit: false,
@@ -191,6 +278,14 @@ impl App {
             is_loading: true,
             is_analyzing: false,
             discovery_receiver: Some(rx),
+            specs_cached_at: None,
+            auto_refresh,
+            last_refreshed: Instant::now(),
+            state_db,
+            last_event_id,
+            last_state_update: Utc::now(),
+            last_state_poll: Instant::now(),
+            recorded_failures: HashSet::new(),
         })
     }
 
@@ -213,6 +308,24 @@ impl App {
             return Ok(());
         }
 
+        if self.show_git_status.is_some() {
+            if matches!(key_event.code, KeyCode::Esc | KeyCode::Char('q')) {
+                self.show_git_status = None;
+            }
+            return Ok(());
+        }
+
+        if self.show_repo_actions {
+            self.show_repo_actions = false;
+            match key_event.code {
+                KeyCode::Char('g') => self.show_selected_git_status().await,
+                KeyCode::Char('s') => self.stash_selected_repo().await,
+                KeyCode::Char('t') => self.open_shell_in_selected_repo().await?,
+                _ => {}
+            }
+            return Ok(());
+        }
+
         // Global keybinds
         match key_event.code {
             KeyCode::Char('q') => {
@@ -222,7 +335,10 @@ impl App {
                 self.show_help = true;
             }
             KeyCode::Char('r') => {
-                self.refresh_data().await?;
+                self.refresh_data(false).await?;
+            }
+            KeyCode::Char('R') => {
+                self.refresh_data(true).await?;
             }
             KeyCode::Char('s') => {
                 self.start_sync().await?;
@@ -230,6 +346,15 @@ impl App {
             KeyCode::Char('d') => {
                 self.toggle_daemon().await?;
             }
+            KeyCode::Char('i') => {
+                self.ignore_selected_repo();
+            }
+            KeyCode::Char('o') => {
+                self.toggle_sort_mode();
+            }
+            KeyCode::Char('x') => {
+                self.open_repo_actions();
+            }
             // Switch focus between panels with Tab
             KeyCode::Tab => {
                 self.focused_panel = match self.focused_panel {
@@ -314,11 +439,305 @@ impl App {
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown");
             self.add_log(format!("Selected: {}", name));
+
+            // What the last sync (here or in the daemon) did with it
+            let last_action = self
+                .repo_specs
+                .get(self.selected_repo)
+                .zip(self.state_db.as_ref())
+                .and_then(|(spec, db)| db.get_repo(&spec.full_name()).ok().flatten())
+                .and_then(|record| record.last_action);
+            if let Some(action) = last_action {
+                self.add_log(format!("  Last action: {}", action));
+            }
             // TODO: Show repo details or trigger sync for this repo
         }
         Ok(())
     }
 
+    /// Add the selected repository to the ignored list and drop it from view
+    fn ignore_selected_repo(&mut self) {
+        if self.focused_panel != FocusedPanel::Repositories {
+            return;
+        }
+        if self.is_loading || self.is_analyzing {
+            self.set_transient_status("Wait for analysis to finish before ignoring".to_string());
+            return;
+        }
+        let Some(spec) = self.repo_specs.get(self.selected_repo) else {
+            return;
+        };
+        let full_name = spec.full_name();
+
+        if let Err(e) = self.config.add_ignored_repo(&self.config_path, &full_name) {
+            self.add_log(format!("ERROR: Failed to ignore {}: {}", full_name, e));
+            self.show_error = Some(format!("Failed to ignore {}: {}", full_name, e));
+            return;
+        }
+
+        // Apply immediately: update config and drop the repo from the list
+        if !self.config.github.ignored_repos.contains(&full_name) {
+            self.config.github.ignored_repos.push(full_name.clone());
+        }
+        self.sync_engine = SyncEngine::new(self.config.clone())
+            .with_https_token(self.sync_engine.git_client().https_token());
+        if let Ok(content) = std::fs::read_to_string(&self.config_path) {
+            self.config_text = content;
+        }
+
+        self.repo_specs.remove(self.selected_repo);
+        if self.selected_repo < self.repositories.len() {
+            self.repositories.remove(self.selected_repo);
+        }
+        self.selected_repo = self
+            .selected_repo
+            .min(self.repositories.len().saturating_sub(1));
+        self.list_state.select(Some(self.selected_repo));
+
+        self.add_log(format!(
+            "Ignored {} (added to github.ignored_repos)",
+            full_name
+        ));
+        self.set_transient_status(format!("Ignored {}", full_name));
+    }
+
+    /// Show the action menu (status, stash, shell) for the selected checkout
+    fn open_repo_actions(&mut self) {
+        if self.focused_panel != FocusedPanel::Repositories {
+            return;
+        }
+        match self.repositories.get(self.selected_repo) {
+            Some(repo) if repo.exists => self.show_repo_actions = true,
+            Some(_) => self.set_transient_status("Not cloned yet".to_string()),
+            None => {}
+        }
+    }
+
+    /// Path and display name of the selected repository
+    fn selected_repo_path(&self) -> Option<(PathBuf, String)> {
+        let repo = self.repositories.get(self.selected_repo)?;
+        let name = self
+            .repo_specs
+            .iter()
+            .find(|spec| spec.local_path == repo.path)
+            .map(|spec| spec.full_name())
+            .unwrap_or_else(|| repo.path.display().to_string());
+        Some((repo.path.clone(), name))
+    }
+
+    /// Show `git status` of the selected repository in a popup
+    async fn show_selected_git_status(&mut self) {
+        let Some((path, name)) = self.selected_repo_path() else {
+            return;
+        };
+
+        let git = GitClient::new(self.config.clone());
+        match git.status_output(&path).await {
+            Ok(output) => self.show_git_status = Some((name, output)),
+            Err(e) => self.show_error = Some(format!("{}: {:#}", name, e)),
+        }
+    }
+
+    /// Stash the selected repository's changes (untracked files included)
+    /// under a named stash
+    async fn stash_selected_repo(&mut self) {
+        let Some((path, name)) = self.selected_repo_path() else {
+            return;
+        };
+        let message = format!(
+            "RepoSentry TUI stash {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M")
+        );
+
+        match GitClient::new(self.config.clone())
+            .stash_changes(&path, &message, true)
+            .await
+        {
+            Ok(()) => {
+                self.add_log(format!("Stashed {} as \"{}\"", name, message));
+                self.set_transient_status(format!("Stashed {}", name));
+                self.reanalyze_repo(&path).await;
+            }
+            Err(e) => {
+                self.add_log(format!("ERROR: Failed to stash {}: {:#}", name, e));
+                self.show_error = Some(format!("Failed to stash {}: {:#}", name, e));
+            }
+        }
+    }
+
+    /// Open $SHELL in the selected repository, suspending the TUI until it exits
+    async fn open_shell_in_selected_repo(&mut self) -> Result<()> {
+        let Some((path, name)) = self.selected_repo_path() else {
+            return Ok(());
+        };
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+
+        self.add_log(format!("Opening {} in {}...", shell, name));
+
+        crossterm::terminal::disable_raw_mode()?;
+        crossterm::execute!(std::io::stdout(), crossterm::terminal::LeaveAlternateScreen)?;
+
+        let status = std::process::Command::new(&shell)
+            .current_dir(&path)
+            .status();
+
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+
+        if let Err(e) = status {
+            self.add_log(format!("ERROR: Failed to start {}: {}", shell, e));
+        }
+
+        // Whatever was done in the shell may have changed the checkout
+        self.reanalyze_repo(&path).await;
+        Ok(())
+    }
+
+    /// Re-read the local state of one repository after it was changed from the TUI
+    async fn reanalyze_repo(&mut self, path: &Path) {
+        let Some(spec) = self
+            .repo_specs
+            .iter()
+            .find(|spec| spec.local_path == path)
+            .cloned()
+        else {
+            return;
+        };
+
+        match self.sync_engine.analyze_repos(&[spec]).await {
+            Ok(mut states) => {
+                if let (Some(state), Some(repo)) = (
+                    states.pop(),
+                    self.repositories.iter_mut().find(|repo| repo.path == path),
+                ) {
+                    *repo = state;
+                }
+                self.sort_repositories();
+            }
+            Err(e) => self.add_log(format!("ERROR: Failed to re-analyze: {:#}", e)),
+        }
+    }
+
+    /// Switch between issues-first and alphabetical repository ordering
+    fn toggle_sort_mode(&mut self) {
+        self.sort_mode = match self.sort_mode {
+            RepoSortMode::IssuesFirst => RepoSortMode::Alphabetical,
+            RepoSortMode::Alphabetical => RepoSortMode::IssuesFirst,
+        };
+        self.sort_repositories();
+        self.set_transient_status(format!("Sorted {}", self.sort_mode.label()));
+    }
+
+    /// Reorder the repository list (and the index-aligned specs) by the
+    /// current sort mode, keeping the selected repository selected
+    fn sort_repositories(&mut self) {
+        let selected_path = self
+            .repositories
+            .get(self.selected_repo)
+            .map(|repo| repo.path.clone());
+
+        let failed = self.failed_paths();
+
+        let mut order: Vec<usize> = (0..self.repositories.len()).collect();
+        order.sort_by_cached_key(|&index| {
+            let repo = &self.repositories[index];
+            let rank = match self.sort_mode {
+                RepoSortMode::IssuesFirst => issue_rank(repo, failed.contains(repo.path.as_path())),
+                RepoSortMode::Alphabetical => 0,
+            };
+            let name = repo
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            (rank, name)
+        });
+
+        if self.repo_specs.len() == self.repositories.len() {
+            self.repo_specs = order.iter().map(|&i| self.repo_specs[i].clone()).collect();
+        }
+        self.repositories = order
+            .iter()
+            .map(|&i| self.repositories[i].clone())
+            .collect();
+
+        self.selected_repo = selected_path
+            .and_then(|path| self.repositories.iter().position(|repo| repo.path == path))
+            .unwrap_or(0);
+        self.list_state.select(Some(self.selected_repo));
+    }
+
+    /// Repositories whose last sync failed, in this session or as recorded by the daemon
+    fn failed_paths(&self) -> HashSet<&Path> {
+        self.last_sync_summary
+            .iter()
+            .flat_map(|summary| &summary.results)
+            .filter_map(|result| match result {
+                SyncResult::Failed { path, .. } => Some(path.as_path()),
+                _ => None,
+            })
+            .chain(self.recorded_failures.iter().map(PathBuf::as_path))
+            .collect()
+    }
+
+    /// Pick up activity recorded in the state database by the daemon
+    ///
+    /// New events are added to the log, and repositories whose recorded state
+    /// changed since the last poll get their list entry updated.
+    fn poll_state_db(&mut self) {
+        self.last_state_poll = Instant::now();
+        let Some(db) = &self.state_db else {
+            return;
+        };
+
+        let polled_at = Utc::now();
+        let events = db.get_events_after(self.last_event_id).unwrap_or_default();
+        let records = db
+            .get_repos_updated_since(self.last_state_update)
+            .unwrap_or_default();
+        self.last_state_update = polled_at;
+
+        for event in events {
+            self.last_event_id = self.last_event_id.max(event.id);
+            let repo = event
+                .repo_full_name
+                .map(|name| format!("{}: ", name))
+                .unwrap_or_default();
+            self.add_log(format!("[daemon] {}{}", repo, event.summary));
+        }
+
+        let mut changed = false;
+        for record in records {
+            let Some(local_path) = record.local_path.as_deref().map(PathBuf::from) else {
+                continue;
+            };
+            let Some(repo) = self
+                .repositories
+                .iter_mut()
+                .find(|repo| repo.path == local_path)
+            else {
+                continue;
+            };
+
+            apply_recorded_state(repo, &record);
+            if record.last_sync_status == RepoStatus::Error {
+                self.recorded_failures.insert(local_path);
+            } else {
+                self.recorded_failures.remove(&local_path);
+            }
+            changed = true;
+        }
+
+        if changed {
+            self.sort_repositories();
+        }
+    }
+
+    /// Show a status message in place of the regular one for a few seconds
+    fn set_transient_status(&mut self, message: String) {
+        self.transient_status = Some((message, Instant::now() + TRANSIENT_STATUS_DURATION));
+    }
+
     /// Open config file in $EDITOR
     async fn open_config_in_editor(&mut self) -> Result<()> {
         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
@@ -361,47 +780,140 @@ impl App {
     }
 
     /// Refresh application data
-    async fn refresh_data(&mut self) -> Result<()> {
-        self.add_log("Refreshing data...".to_string());
+    ///
+    /// Uses the cached repository list while it is fresh, unless `full`.
+    async fn refresh_data(&mut self, full: bool) -> Result<()> {
+        self.add_log(if full {
+            "Refreshing data from GitHub...".to_string()
+        } else {
+            "Refreshing data...".to_string()
+        });
 
         // Update daemon status
         self.daemon_running = is_daemon_running(&self.config).unwrap_or(false);
 
         // Re-discover and analyze repositories
         if self.discovery_ok {
-            match GitHubDiscovery::new(self.config.clone()).await {
-                Ok(discovery) => match discovery.discover().await {
-                    Ok(specs) => {
-                        self.repo_specs = specs;
-                        match self.sync_engine.analyze_repos(&self.repo_specs).await {
-                            Ok(states) => {
-                                self.repositories = states;
-                                self.add_log(format!(
-                                    "Loaded {} repositories",
-                                    self.repositories.len()
-                                ));
-                            }
-                            Err(e) => {
-                                self.add_log(format!("ERROR: Failed to analyze repos: {}", e));
-                            }
-                        }
+            let cached = if full {
+                None
+            } else {
+                self.state_db
+                    .as_ref()
+                    .and_then(|db| load_cached_specs(db, &self.config))
+            };
+
+            let specs = match cached {
+                Some(cached) => {
+                    self.specs_cached_at = Some(cached.fetched_at);
+                    Some(cached.specs)
+                }
+                None => self.discover_specs().await,
+            };
+
+            if let Some(specs) = specs {
+                self.repo_specs = specs;
+                match self.sync_engine.analyze_repos(&self.repo_specs).await {
+                    Ok(states) => {
+                        self.repositories = states;
+                        self.sort_repositories();
+                        self.add_log(format!("Loaded {} repositories", self.repositories.len()));
                     }
                     Err(e) => {
-                        self.add_log(format!("ERROR: Failed to discover repos: {}", e));
-                        self.show_error = Some(format!("Failed to discover repos: {}", e));
+                        self.add_log(format!("ERROR: Failed to analyze repos: {}", e));
                     }
-                },
-                Err(e) => {
-                    self.add_log(format!("ERROR: Failed to refresh repositories: {}", e));
-                    self.show_error = Some(format!("Failed to refresh repositories: {}", e));
                 }
             }
         }
 
-        self.status_message = "Data refreshed".to_string();
+        self.last_refreshed = Instant::now();
+        self.status_message = if self.specs_cached_at.is_some() {
+            "Data refreshed (cached)".to_string()
+        } else {
+            "Data refreshed".to_string()
+        };
         Ok(())
     }
 
+    /// Re-analyze repository states in the background when `tui.auto_refresh`
+    /// is due, unless a sync, discovery or earlier refresh is still running
+    fn start_auto_refresh(&mut self) {
+        let Some(interval) = self.auto_refresh else {
+            return;
+        };
+        if !self.discovery_ok
+            || self.show_progress
+            || self.discovery_receiver.is_some()
+            || self.last_refreshed.elapsed() < interval
+        {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(32);
+        self.discovery_receiver = Some(rx);
+        let config = self.config.clone();
+        let specs = self.repo_specs.clone();
+        tokio::spawn(async move {
+            let discovered = if config.tui.auto_refresh_discovery {
+                match discover_and_cache(&config, &tx).await {
+                    Ok(specs) => Some(specs),
+                    Err(error) => {
+                        let _ = tx.send(DiscoveryMessage::RefreshFailed(error)).await;
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
+            let sync_engine = SyncEngine::new(config);
+            let states = sync_engine
+                .analyze_repos(discovered.as_ref().unwrap_or(&specs))
+                .await
+                .unwrap_or_default();
+            let _ = tx
+                .send(DiscoveryMessage::Refreshed(discovered, states))
+                .await;
+        });
+    }
+
+    /// Discover repositories from the configured providers and update the cache
+    async fn discover_specs(&mut self) -> Option<Vec<RepoSpec>> {
+        let sources = match configured_sources(&self.config).await {
+            Ok(sources) => sources,
+            Err(e) => {
+                self.add_log(format!("ERROR: Failed to refresh repositories: {}", e));
+                self.show_error = Some(format!("Failed to refresh repositories: {}", e));
+                return None;
+            }
+        };
+
+        match discover_from(&sources).await {
+            Ok(specs) => {
+                if let Some(db) = &self.state_db {
+                    if let Err(e) = store_cached_specs(db, &self.config, &specs) {
+                        self.add_log(format!("WARNING: Failed to cache repositories: {}", e));
+                    }
+                }
+                self.specs_cached_at = None;
+                Some(specs)
+            }
+            Err(e) => {
+                self.add_log(format!("ERROR: Failed to discover repos: {}", e));
+                self.show_error = Some(format!("Failed to discover repos: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Status line once loading finishes, flagging a cached repository list
+    fn ready_status(&self) -> String {
+        if self.specs_cached_at.is_some() {
+            "Ready (cached)".to_string()
+        } else {
+            "Ready".to_string()
+        }
+    }
+
     /// Start a sync operation (runs in background)
     async fn start_sync(&mut self) -> Result<()> {
         if self.repo_specs.is_empty() {
@@ -416,6 +928,12 @@ impl App {
         }
 
         self.add_log("Starting repository synchronization...".to_string());
+        if self.sync_engine.git_client().is_dry_run() {
+            self.add_log(format!(
+                "{} {} is set: no repository will be modified",
+                DRY_RUN_REASON_PREFIX, DRY_RUN_ENV
+            ));
+        }
         self.current_operation = Some("Synchronizing repositories".to_string());
         self.show_progress = true;
         self.status_message = "Syncing...".to_string();
@@ -423,7 +941,15 @@ impl App {
         // Get a sender to communicate back to the UI
         let sender = self.event_handler.sender();
         let specs_to_sync = self.repo_specs.clone();
-        let sync_engine = self.sync_engine.clone();
+
+        // Report each repository as soon as it finishes
+        let result_sender = sender.clone();
+        let sync_engine = self
+            .sync_engine
+            .clone()
+            .with_result_callback(Box::new(move |result| {
+                let _ = result_sender.send(AppEvent::StatusUpdate(describe_sync_result(result)));
+            }));
 
         // Spawn sync operation in background
         tokio::spawn(async move {
@@ -433,53 +959,6 @@ impl App {
 
             match sync_engine.sync_repos(specs_to_sync).await {
                 Ok(summary) => {
-                    // Send individual results as status updates
-                    for result in &summary.results {
-                        let msg = match result {
-                            SyncResult::Cloned { path, branch } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                let branch_info = branch.as_deref().map(|b| format!(" [{}]", b)).unwrap_or_default();
-                                format!("✓ Cloned: {}{}", name, branch_info)
-                            }
-                            SyncResult::Pulled {
-                                path,
-                                commits_updated,
-                                branch,
-                            } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                let branch_info = branch.as_deref().map(|b| format!(" [{}]", b)).unwrap_or_default();
-                                format!("✓ Pulled: {} ({} commits){}", name, commits_updated, branch_info)
-                            }
-                            SyncResult::BranchSwitched {
-                                path,
-                                from_branch,
-                                to_branch,
-                                commits_updated,
-                            } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                format!("↻ Switched: {} ({} → {}, {} commits)", name, from_branch, to_branch, commits_updated)
-                            }
-                            SyncResult::FetchedOnly { path, reason } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                format!("⚠ Fetched only: {} ({})", name, reason)
-                            }
-                            SyncResult::UpToDate { path, branch } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                let branch_info = branch.as_deref().map(|b| format!(" [{}]", b)).unwrap_or_default();
-                                format!("• Up to date: {}{}", name, branch_info)
-                            }
-                            SyncResult::Skipped { path, reason } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                format!("⏭ Skipped: {} ({})", name, reason)
-                            }
-                            SyncResult::Failed { path, error } => {
-                                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                                format!("✗ Failed: {} ({})", name, error)
-                            }
-                        };
-                        let _ = sender.send(AppEvent::StatusUpdate(msg));
-                    }
-
                     // Send completion summary
                     let _ = sender.send(AppEvent::StatusUpdate(format!(
                         "Sync completed: {} successful, {} failed, {} skipped ({:.1}s)",
@@ -538,9 +1017,16 @@ impl App {
                     self.status_message = msg.clone();
                     self.add_log(msg);
                 }
-                Ok(DiscoveryMessage::SpecsDiscovered(specs)) => {
+                Ok(DiscoveryMessage::SpecsDiscovered(specs, cached_at)) => {
                     let count = specs.len();
                     self.repo_specs = specs;
+                    self.specs_cached_at = cached_at;
+                    if let Some(fetched_at) = cached_at {
+                        self.add_log(format!(
+                            "Using cached repository list from {} (press R to re-fetch)",
+                            fetched_at.with_timezone(&chrono::Local).format("%H:%M")
+                        ));
+                    }
 
                     // Create placeholder RepoState entries for immediate display
                     // These will be updated when analysis completes
@@ -556,6 +1042,10 @@ impl App {
                             is_behind_remote: false,
                             has_conflicts: false,
                             current_branch: None,
+                            is_shallow: false,
+                            in_progress_operation: in_progress_operation(&spec.local_path)
+                                .map(str::to_string),
+                            branch_switch: None,
                             remote_url: Some(spec.clone_url.clone()),
                         })
                         .collect();
@@ -572,15 +1062,40 @@ impl App {
                     }
                 }
                 Ok(DiscoveryMessage::AnalysisCompleted(states)) => {
+                    // States come back in discovery order, like the specs
+                    // (placeholders are left unsorted until then)
                     self.repositories = states;
+                    self.sort_repositories();
+                    self.last_refreshed = Instant::now();
                     self.is_analyzing = false;
                     self.current_operation = None;
-                    self.status_message = "Ready".to_string();
+                    self.status_message = self.ready_status();
                     self.add_log(format!("Analysis complete for {} repositories", self.repositories.len()));
 
                     // Clear the receiver since we're done
                     self.discovery_receiver = None;
                 }
+                Ok(DiscoveryMessage::Refreshed(specs, states)) => {
+                    self.discovery_receiver = None;
+                    self.last_refreshed = Instant::now();
+
+                    // States analyzed before a sync started would be stale
+                    if !self.show_progress && !states.is_empty() {
+                        if let Some(specs) = specs {
+                            self.repo_specs = specs;
+                            self.specs_cached_at = None;
+                        }
+                        self.repositories = states;
+                        self.sort_repositories();
+                        self.status_message = self.ready_status();
+                    }
+                }
+                Ok(DiscoveryMessage::RefreshFailed(error)) => {
+                    self.discovery_receiver = None;
+                    self.last_refreshed = Instant::now();
+                    self.status_message = self.ready_status();
+                    self.add_log(format!("WARNING: Auto-refresh failed: {}", error));
+                }
                 Ok(DiscoveryMessage::Failed(error)) => {
                     self.is_loading = false;
                     self.is_analyzing = false;
@@ -615,6 +1130,20 @@ impl App {
                 Ok(AppEvent::Tick) => {
                     // Periodic update
                     self.daemon_running = is_daemon_running(&self.config).unwrap_or(false);
+
+                    if self.last_state_poll.elapsed() >= STATE_POLL_INTERVAL {
+                        self.poll_state_db();
+                    }
+
+                    self.start_auto_refresh();
+
+                    if self
+                        .transient_status
+                        .as_ref()
+                        .is_some_and(|(_, expires_at)| Instant::now() >= *expires_at)
+                    {
+                        self.transient_status = None;
+                    }
                 }
                 Ok(AppEvent::SyncCompleted(summary)) => {
                     self.last_sync = Some(Instant::now());
@@ -623,6 +1152,7 @@ impl App {
                         summary.successful_operations, summary.failed_operations
                     );
                     self.last_sync_summary = Some(summary);
+                    self.sort_repositories();
                     self.current_operation = None;
                     self.show_progress = false;
                 }
@@ -723,6 +1253,14 @@ impl App {
             self.draw_help_popup(frame, size);
         }
 
+        if self.show_repo_actions {
+            self.draw_repo_actions_popup(frame, size);
+        }
+
+        if let Some((name, output)) = &self.show_git_status {
+            self.draw_git_status_popup(frame, size, name, output);
+        }
+
         if let Some(ref error) = self.show_error.clone() {
             self.draw_error_popup(frame, size, error);
         }
@@ -745,12 +1283,19 @@ impl App {
         use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, List, ListItem};
 
+        let now = chrono::Utc::now();
+        let failed = self.failed_paths();
         let items: Vec<ListItem> = self
             .repositories
             .iter()
-            .map(|repo| {
-                let (status_icon, status_color) = if !repo.exists {
+            .enumerate()
+            .map(|(index, repo)| {
+                let (status_icon, status_color) = if failed.contains(repo.path.as_path()) {
+                    ("✗", self.colors.error)
+                } else if !repo.exists {
                     ("📥", self.colors.info)
+                } else if repo.in_progress_operation.is_some() {
+                    ("⏸", self.colors.error)
                 } else if repo.has_uncommitted_changes {
                     ("⚠", self.colors.warning)
                 } else if repo.has_conflicts {
@@ -769,19 +1314,42 @@ impl App {
                     .and_then(|n| n.to_str())
                     .unwrap_or("unknown");
 
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("{} ", status_icon),
-                        Style::default().fg(status_color),
-                    ),
-                    Span::styled(name, Style::default().fg(self.colors.text)),
-                ]))
+                let mut spans = vec![Span::styled(
+                    format!("{} ", status_icon),
+                    Style::default().fg(status_color),
+                )];
+
+                // Recency of the last push: red this week, yellow this month, gray older
+                let spec = self.repo_specs.get(index);
+                if let Some(activity) = spec.and_then(|spec| spec.activity(now)) {
+                    let activity_color = match activity {
+                        Activity::Week => self.colors.error,
+                        Activity::Month => self.colors.warning,
+                        Activity::Dormant => self.colors.border,
+                    };
+                    spans.push(Span::styled("● ", Style::default().fg(activity_color)));
+                }
+
+                spans.push(Span::styled(name, Style::default().fg(self.colors.text)));
+
+                // Tag repositories that are not owned by the user
+                if let Some(spec) = spec {
+                    if spec.source != RepoSource::Owned {
+                        spans.push(Span::styled(
+                            format!(" [{}]", spec.source),
+                            Style::default().fg(self.colors.border),
+                        ));
+                    }
+                }
+
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
         let title = format!(
-            "Repositories ({}) [r]efresh [s]ync",
-            self.repositories.len()
+            "Repositories ({}) [r]efresh [s]ync [i]gnore [o]rder: {}",
+            self.repositories.len(),
+            self.sort_mode.label()
         );
         let list = List::new(items)
             .block(
@@ -807,9 +1375,27 @@ impl App {
             "no sync"
         };
 
+        // Age of the repository states, when they refresh on their own
+        let refreshed = match self.auto_refresh {
+            Some(_) if self.discovery_ok => {
+                let secs = self.last_refreshed.elapsed().as_secs();
+                if secs < 60 {
+                    format!(" | refreshed {}s ago", secs)
+                } else {
+                    format!(" | refreshed {}m ago", secs / 60)
+                }
+            }
+            _ => String::new(),
+        };
+
         let status_text = format!(
-            " {} Daemon | {} | {} ",
-            daemon_status, self.status_message, sync_status
+            " {} Daemon | {} | {}{} ",
+            daemon_status,
+            self.transient_status
+                .as_ref()
+                .map_or(&self.status_message, |(message, _)| message),
+            sync_status,
+            refreshed
         );
 
         let paragraph = Paragraph::new(status_text).style(
@@ -906,9 +1492,13 @@ impl App {
   j/↓      Move down
   k/↑      Move up
   Enter    Select
-  r        Refresh repositories
+  r        Refresh repositories (cached list if fresh)
+  R        Refresh repositories from GitHub
   s        Start sync
   d        Toggle daemon
+  i        Ignore selected repository
+  o        Toggle issues-first/alphabetical order
+  x        Actions for selected repository (status, stash, shell)
   1/l      Switch to Log tab
   2/c      Switch to Config tab
   e        Edit config (when on Config)
@@ -929,6 +1519,56 @@ impl App {
         frame.render_widget(paragraph, popup_area);
     }
 
+    /// Draw the action menu for the selected repository
+    fn draw_repo_actions_popup(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::Style;
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+        let name = self
+            .selected_repo_path()
+            .map(|(_, name)| name)
+            .unwrap_or_default();
+        let actions_text = r#"  g        View git status
+  s        Stash changes (untracked files included)
+  t        Open $SHELL in the repository
+  Esc      Close
+"#;
+
+        let popup_area = centered_rect(50, 25, area);
+        frame.render_widget(Clear, popup_area);
+
+        let paragraph = Paragraph::new(actions_text)
+            .block(
+                Block::default()
+                    .title(format!("Actions: {}", name))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.colors.primary)),
+            )
+            .style(Style::default().fg(self.colors.text));
+
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    /// Draw `git status` output for a repository
+    fn draw_git_status_popup(&self, frame: &mut Frame, area: Rect, name: &str, output: &str) {
+        use ratatui::style::Style;
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+        let popup_area = centered_rect(70, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let paragraph = Paragraph::new(output)
+            .block(
+                Block::default()
+                    .title(format!("git status: {} (press q to close)", name))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.colors.primary)),
+            )
+            .style(Style::default().fg(self.colors.text));
+
+        frame.render_widget(paragraph, popup_area);
+    }
+
     /// Draw error popup
     fn draw_error_popup(&self, frame: &mut Frame, area: Rect, error: &str) {
         use ratatui::style::Style;
@@ -951,6 +1591,158 @@ impl App {
     }
 }
 
+/// Discover repositories from the configured providers, reporting progress, and cache the result
+async fn discover_and_cache(
+    config: &Config,
+    tx: &mpsc::Sender<DiscoveryMessage>,
+) -> std::result::Result<Vec<RepoSpec>, String> {
+    let _ = tx
+        .send(DiscoveryMessage::Progress(
+            "Connecting to providers...".to_string(),
+        ))
+        .await;
+    let sources = configured_sources(config)
+        .await
+        .map_err(|e| format!("Connection failed: {}", e))?;
+
+    let _ = tx
+        .send(DiscoveryMessage::Progress(
+            "Fetching repositories...".to_string(),
+        ))
+        .await;
+    let specs = discover_from(&sources)
+        .await
+        .map_err(|e| format!("Discovery failed: {}", e))?;
+
+    // Best effort: the next start falls back to discovery anyway
+    if let Ok(db) = StateDb::open() {
+        let _ = store_cached_specs(&db, config, &specs);
+    }
+
+    Ok(specs)
+}
+
+/// Sort rank for the issues-first ordering (lower sorts first)
+fn issue_rank(repo: &RepoState, failed: bool) -> u8 {
+    if failed
+        || repo.in_progress_operation.is_some()
+        || repo.has_conflicts
+        || repo.has_uncommitted_changes
+    {
+        0
+    } else if !repo.exists || repo.is_behind_remote || repo.is_ahead_of_remote {
+        1
+    } else {
+        2
+    }
+}
+
+/// Update a repository's list entry from the state recorded by another sync
+fn apply_recorded_state(repo: &mut RepoState, record: &state::RepoState) {
+    match record.last_sync_status {
+        RepoStatus::Ok => {
+            repo.exists = repo.path.exists();
+            repo.is_behind_remote = false;
+            repo.has_conflicts = false;
+        }
+        RepoStatus::Skipped => {
+            if record
+                .skip_reason
+                .as_deref()
+                .is_some_and(|reason| reason.contains("local changes"))
+            {
+                repo.has_uncommitted_changes = true;
+            }
+        }
+        RepoStatus::Error | RepoStatus::Unknown => {}
+    }
+
+    if record.current_branch.is_some() {
+        repo.current_branch = record.current_branch.clone();
+    }
+}
+
+/// One-line log message for a repository's sync result
+fn describe_sync_result(result: &SyncResult) -> String {
+    let name = |path: &std::path::Path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?")
+            .to_string()
+    };
+    let branch_info = |branch: &Option<String>| {
+        branch
+            .as_deref()
+            .map(|b| format!(" [{}]", b))
+            .unwrap_or_default()
+    };
+
+    match result {
+        SyncResult::Cloned { path, branch } => {
+            format!("✓ Cloned: {}{}", name(path), branch_info(branch))
+        }
+        SyncResult::Pulled {
+            path,
+            commits_updated,
+            branch,
+            remote,
+            ..
+        } => format!(
+            "✓ Pulled: {} ({} commits from {}){}",
+            name(path),
+            commits_updated,
+            remote,
+            branch_info(branch)
+        ),
+        SyncResult::Rebased {
+            path,
+            commits_replayed,
+            branch,
+            remote,
+            ..
+        } => format!(
+            "✓ Rebased: {} ({} local commits onto {}){}",
+            name(path),
+            commits_replayed,
+            remote,
+            branch_info(branch)
+        ),
+        SyncResult::Merged {
+            path,
+            commits_merged,
+            branch,
+            remote,
+            ..
+        } => format!(
+            "✓ Merged: {} ({} commits from {}){}",
+            name(path),
+            commits_merged,
+            remote,
+            branch_info(branch)
+        ),
+        SyncResult::BranchSwitched {
+            path,
+            from_branch,
+            to_branch,
+            commits_updated,
+        } => format!(
+            "↻ Switched: {} ({} → {}, {} commits)",
+            name(path),
+            from_branch,
+            to_branch,
+            commits_updated
+        ),
+        SyncResult::FetchedOnly { path, reason } => {
+            format!("⚠ Fetched only: {} ({})", name(path), reason)
+        }
+        SyncResult::UpToDate { path, branch } => {
+            format!("• Up to date: {}{}", name(path), branch_info(branch))
+        }
+        SyncResult::Skipped { path, reason } => format!("⏭ Skipped: {} ({})", name(path), reason),
+        SyncResult::Failed { path, error } => format!("✗ Failed: {} ({})", name(path), error),
+    }
+}
+
 /// Helper function to create a centered rect
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -64,6 +64,8 @@ impl<'a> RepositoryList<'a> {
             .map(|repo| {
                 let (status_icon, status_color) = if !repo.exists {
                     ("📥", self.colors.info) // Clone needed
+                } else if repo.in_progress_operation.is_some() {
+                    ("⏸", self.colors.error) // Interrupted merge/rebase
                 } else if repo.has_uncommitted_changes {
                     ("⚠️", self.colors.warning) // Uncommitted changes
                 } else if repo.has_conflicts {
@@ -321,7 +323,8 @@ impl<'a> HelpDialog<'a> {
             Line::from("  Shift+Tab  Previous tab"),
             Line::from(""),
             Line::from("Actions:"),
-            Line::from("  r          Refresh"),
+            Line::from("  r          Refresh (cached)"),
+            Line::from("  R          Refresh from GitHub"),
             Line::from("  s          Start sync"),
             Line::from("  d          Start daemon"),
             Line::from("  Enter      Select"),
@@ -563,6 +566,9 @@ mod tests {
             has_conflicts: false,
             remote_url: Some("https://github.com/test/repo".to_string()),
             current_branch: Some("main".to_string()),
+            is_shallow: false,
+            in_progress_operation: None,
+            branch_switch: None,
         }];
 
         let list = RepositoryList::new(&repos, &colors);
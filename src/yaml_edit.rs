@@ -0,0 +1,432 @@
+//! Format-preserving YAML updates
+//!
+//! `serde_yaml` drops comments when serializing, so rewriting a hand-edited
+//! configuration file would lose its documentation. This module applies
+//! changed values to the existing text line by line, leaving comments, blank
+//! lines and untouched keys exactly as they were.
+//!
+//! Only block-style mappings (the layout used by `config.example.yml`) can be
+//! edited in place. Callers should fall back to a full rewrite when
+//! `update_document` returns `None`.
+
+use serde_yaml::Value;
+
+/// Update YAML `content` that currently deserializes to `old` so that it
+/// deserializes to `new`
+///
+/// Only leaf values (scalars and lists) that differ are rewritten; missing
+/// keys are inserted at the end of their parent section. Returns `None` if
+/// the document uses a layout that can't be edited in place.
+pub fn update_document(content: &str, old: &Value, new: &Value) -> Option<String> {
+    let mut changes = Vec::new();
+    collect_changes(&mut Vec::new(), Some(old), new, &mut changes);
+
+    let mut document = Document::parse(content);
+    for (path, value) in &changes {
+        document.set(path, value)?;
+    }
+
+    Some(document.render())
+}
+
+/// Find the (0-based) line on which the key at `path` is defined in `content`
+pub fn key_line(content: &str, path: &[String]) -> Option<usize> {
+    let document = Document::parse(content);
+    let mut start = 0;
+    let mut end = document.lines.len();
+    let mut line = None;
+
+    for key in path {
+        let found = document.find_child(start, end, key)?;
+        line = Some(found);
+        start = found + 1;
+        end = document.block_end(found);
+    }
+
+    line
+}
+
+/// Collect the paths of leaf values in `new` that differ from `old`
+fn collect_changes(
+    path: &mut Vec<String>,
+    old: Option<&Value>,
+    new: &Value,
+    changes: &mut Vec<(Vec<String>, Value)>,
+) {
+    match new {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                path.push(key.to_string());
+                collect_changes(path, old.and_then(|o| o.get(key)), value, changes);
+                path.pop();
+            }
+        }
+        _ => {
+            if old != Some(new) {
+                changes.push((path.clone(), new.clone()));
+            }
+        }
+    }
+}
+
+/// A YAML document as editable lines
+struct Document {
+    lines: Vec<String>,
+}
+
+impl Document {
+    fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(|line| line.to_string()).collect(),
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut content = self.lines.join("\n");
+        content.push('\n');
+        content
+    }
+
+    /// Set the value at `path`, replacing it in place or inserting it
+    fn set(&mut self, path: &[String], value: &Value) -> Option<()> {
+        let mut parent: Option<usize> = None;
+        let mut start = 0;
+        let mut end = self.lines.len();
+
+        for (depth, key) in path.iter().enumerate() {
+            match self.find_child(start, end, key) {
+                Some(line) if depth + 1 == path.len() => return self.replace(line, value),
+                Some(line) => {
+                    // A flow-style or scalar parent can't take block children
+                    if has_inline_value(&self.lines[line]) {
+                        return None;
+                    }
+                    parent = Some(line);
+                    start = line + 1;
+                    end = self.block_end(line);
+                }
+                None => return self.insert(parent, end, &path[depth..], value),
+            }
+        }
+
+        None
+    }
+
+    /// Find the line of `key` among the entries of the block `start..end`
+    fn find_child(&self, start: usize, end: usize, key: &str) -> Option<usize> {
+        let child_indent = self.child_indent(start, end)?;
+        (start..end).find(|&i| {
+            indent(&self.lines[i]) == child_indent
+                && split_key(&self.lines[i]).is_some_and(|(k, _)| k == key)
+        })
+    }
+
+    /// Indentation of the first content line in `start..end`
+    fn child_indent(&self, start: usize, end: usize) -> Option<usize> {
+        self.lines[start..end]
+            .iter()
+            .find(|line| is_content(line))
+            .map(|line| indent(line))
+    }
+
+    /// End (exclusive) of the value block of the key at `line`
+    ///
+    /// Trailing blank and comment lines are left to the following key.
+    fn block_end(&self, line: usize) -> usize {
+        let key_indent = indent(&self.lines[line]);
+        let mut end = line + 1;
+
+        for i in line + 1..self.lines.len() {
+            let text = &self.lines[i];
+            if !is_content(text) {
+                continue;
+            }
+            let deeper = indent(text) > key_indent;
+            let sequence_item = indent(text) == key_indent && text.trim_start().starts_with('-');
+            if deeper || sequence_item {
+                end = i + 1;
+            } else {
+                break;
+            }
+        }
+
+        end
+    }
+
+    /// Replace the value of the key at `line`, keeping its trailing comment
+    fn replace(&mut self, line: usize, value: &Value) -> Option<()> {
+        let text = &self.lines[line];
+        let trimmed = text.trim_start();
+        let (_, rest) = split_key(text)?;
+        let (_, comment) = split_comment(rest);
+
+        // Keep the key exactly as written (it may be quoted)
+        let raw_key = &trimmed[..trimmed.len() - rest.len() - 1];
+        let new_lines = render_entry(indent(text), raw_key, value, comment)?;
+
+        let end = self.block_end(line);
+        self.lines.splice(line..end, new_lines);
+        Some(())
+    }
+
+    /// Insert `path` (relative to `parent`) with `value` at the end of the parent block
+    fn insert(
+        &mut self,
+        parent: Option<usize>,
+        end: usize,
+        path: &[String],
+        value: &Value,
+    ) -> Option<()> {
+        let (mut key_indent, position) = match parent {
+            Some(line) => {
+                let key_indent = self
+                    .child_indent(line + 1, end)
+                    .unwrap_or(indent(&self.lines[line]) + 2);
+                (key_indent, end)
+            }
+            None => {
+                if self.lines.last().is_some_and(|line| is_content(line)) {
+                    self.lines.push(String::new());
+                }
+                (0, self.lines.len())
+            }
+        };
+
+        let (last, sections) = path.split_last()?;
+        let mut new_lines = Vec::new();
+        for section in sections {
+            new_lines.push(format!("{}{}:", " ".repeat(key_indent), section));
+            key_indent += 2;
+        }
+        new_lines.extend(render_entry(key_indent, last, value, "")?);
+
+        self.lines.splice(position..position, new_lines);
+        Some(())
+    }
+}
+
+/// Render `key: value` lines at the given indentation
+fn render_entry(key_indent: usize, key: &str, value: &Value, comment: &str) -> Option<Vec<String>> {
+    let pad = " ".repeat(key_indent);
+
+    match value {
+        Value::Sequence(items) if !items.is_empty() => {
+            let mut lines = vec![format!("{}{}:{}", pad, key, comment)];
+            for item in items {
+                lines.push(format!("{}  - {}", pad, render_scalar(item)?));
+            }
+            Some(lines)
+        }
+        Value::Sequence(_) => Some(vec![format!("{}{}: []{}", pad, key, comment)]),
+        Value::Mapping(mapping) if mapping.is_empty() => {
+            Some(vec![format!("{}{}: {{}}{}", pad, key, comment)])
+        }
+        _ => Some(vec![format!(
+            "{}{}: {}{}",
+            pad,
+            key,
+            render_scalar(value)?,
+            comment
+        )]),
+    }
+}
+
+/// Render a scalar value (strings are double-quoted)
+fn render_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some("null".to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        // JSON string escaping is valid YAML double-quoted style
+        Value::String(s) => serde_json::to_string(s).ok(),
+        _ => None,
+    }
+}
+
+/// Number of leading spaces
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Whether a line holds YAML content (not blank, not a comment)
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+/// Split a mapping line into its (unquoted) key and the text after the colon
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if !is_content(trimmed) || trimmed.starts_with('-') {
+        return None;
+    }
+
+    let colon = match trimmed.find(": ") {
+        Some(colon) => colon,
+        None => trimmed.strip_suffix(':').map(|key| key.len())?,
+    };
+    let key = trimmed[..colon].trim_matches(|c| c == '"' || c == '\'');
+    Some((key, &trimmed[colon + 1..]))
+}
+
+/// Whether a mapping line has a value on the same line
+fn has_inline_value(line: &str) -> bool {
+    split_key(line).is_some_and(|(_, rest)| !split_comment(rest).0.trim().is_empty())
+}
+
+/// Split the text after a key's colon into value and trailing comment
+///
+/// The comment keeps its leading whitespace so it can be re-appended as-is.
+fn split_comment(rest: &str) -> (&str, &str) {
+    let mut quote: Option<char> = None;
+    let mut previous = ' ';
+
+    for (i, c) in rest.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => {
+                let value_end = rest[..i].trim_end().len();
+                return (&rest[..value_end], &rest[value_end..]);
+            }
+            None => {}
+        }
+        previous = c;
+    }
+
+    (rest, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(content: &str, new: &str) -> String {
+        let old: Value = serde_yaml::from_str(content).unwrap();
+        let new: Value = serde_yaml::from_str(new).unwrap();
+        update_document(content, &old, &new).unwrap()
+    }
+
+    #[test]
+    fn test_replace_scalar_keeps_comments() {
+        let content = "\
+# Top comment
+sync:
+  # Strategy comment
+  strategy: \"safe-pull\"  # inline comment
+  max_parallel: 4
+";
+        let updated = update(
+            content,
+            "sync:\n  strategy: fetch-only\n  max_parallel: 4\n",
+        );
+
+        assert_eq!(
+            updated,
+            "\
+# Top comment
+sync:
+  # Strategy comment
+  strategy: \"fetch-only\"  # inline comment
+  max_parallel: 4
+"
+        );
+    }
+
+    #[test]
+    fn test_replace_sequence() {
+        let content = "\
+github:
+  exclude_patterns:
+    - \"a\"
+    - \"b\"
+
+  # Forks
+  include_forks: false
+";
+        let updated = update(
+            content,
+            "github:\n  exclude_patterns: [a, c, d]\n  include_forks: false\n",
+        );
+
+        assert_eq!(
+            updated,
+            "\
+github:
+  exclude_patterns:
+    - \"a\"
+    - \"c\"
+    - \"d\"
+
+  # Forks
+  include_forks: false
+"
+        );
+    }
+
+    #[test]
+    fn test_insert_missing_keys() {
+        let content = "\
+base_directory: \"~/dev\"  # where repos go
+github:
+  include_forks: false
+";
+        let updated = update(
+            content,
+            "base_directory: ~/dev\ngithub:\n  include_forks: false\n  ignored_repos: [owner/repo]\nsync:\n  timeout: 60\n",
+        );
+
+        assert_eq!(
+            updated,
+            "\
+base_directory: \"~/dev\"  # where repos go
+github:
+  include_forks: false
+  ignored_repos:
+    - \"owner/repo\"
+
+sync:
+  timeout: 60
+"
+        );
+    }
+
+    #[test]
+    fn test_flow_mapping_not_editable() {
+        let content = "github: {include_forks: false}\n";
+        let old: Value = serde_yaml::from_str(content).unwrap();
+        let new: Value = serde_yaml::from_str("github:\n  include_forks: true\n").unwrap();
+        assert!(update_document(content, &old, &new).is_none());
+    }
+
+    #[test]
+    fn test_key_line() {
+        let content = "\
+# Comment
+github:
+  include_forks: false
+
+sync:
+  # Nested
+  strategy: \"safe-pull\"
+";
+        let path = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        assert_eq!(key_line(content, &path(&["github"])), Some(1));
+        assert_eq!(key_line(content, &path(&["sync", "strategy"])), Some(6));
+        assert_eq!(key_line(content, &path(&["sync", "missing"])), None);
+    }
+
+    #[test]
+    fn test_split_comment() {
+        assert_eq!(
+            split_comment(" \"auto\"  # a, b"),
+            (" \"auto\"", "  # a, b")
+        );
+        assert_eq!(split_comment(" \"a # b\""), (" \"a # b\"", ""));
+        assert_eq!(split_comment("  # only comment"), ("", "  # only comment"));
+    }
+}
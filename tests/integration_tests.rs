@@ -226,6 +226,101 @@ fn test_error_handling_invalid_config() {
     assert!(stderr.contains("parse") || stderr.contains("config") || stderr.contains("yaml"));
 }
 
+#[cfg(unix)]
+#[test]
+fn test_daemon_signals() {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().unwrap();
+    let pid_file = temp_dir.child("reposentry.pid");
+    let config_path = temp_dir.child("config.yml");
+    // GitLab only, so the daemon needs neither a network nor a token to start
+    let write_config = |interval: &str| {
+        std::fs::write(
+            config_path.path(),
+            format!(
+                "base_directory: \"{}\"\nproviders: [gitlab]\ndaemon:\n  interval: \"{}\"\n  pid_file: \"{}\"\n",
+                temp_dir.path().display(),
+                interval,
+                pid_file.path().display()
+            ),
+        )
+        .unwrap();
+    };
+    write_config("1h");
+
+    let mut child = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--config",
+            config_path.path().to_str().unwrap(),
+            "daemon",
+            "start",
+            "--foreground",
+        ])
+        .env("XDG_DATA_HOME", temp_dir.path())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start daemon");
+
+    let (lines, log) = mpsc::channel();
+    let stderr = child.stderr.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let _ = lines.send(line);
+        }
+    });
+    let wait_for_log = |needle: &str| {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while let Ok(line) = log.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            if line.contains(needle) {
+                return;
+            }
+        }
+        panic!("Daemon never logged '{}'", needle);
+    };
+
+    // The PID file holds the daemon's PID (not cargo's) once it's running
+    let started = Instant::now();
+    while !pid_file.path().exists() {
+        if let Some(status) = child.try_wait().unwrap() {
+            panic!("Daemon exited early ({})", status);
+        }
+        assert!(
+            started.elapsed() < Duration::from_secs(600),
+            "Daemon never wrote its PID file"
+        );
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    wait_for_log("Daemon loop started");
+    let pid: i32 = std::fs::read_to_string(pid_file.path())
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    let pid = Pid::from_raw(pid);
+
+    // SIGHUP reloads the configuration
+    write_config("2h");
+    kill(pid, Signal::SIGHUP).unwrap();
+    wait_for_log("Configuration reloaded");
+    wait_for_log("Sync interval is now 7200s");
+
+    // SIGTERM stops it cleanly, removing the PID file
+    kill(pid, Signal::SIGTERM).unwrap();
+    wait_for_log("SIGTERM received");
+    let status = child.wait().unwrap();
+    assert!(status.success());
+    assert!(!pid_file.path().exists());
+}
+
 #[test]
 fn test_compilation() {
     // Ensure the project compiles successfully